@@ -19,6 +19,7 @@ use sui_types::{
 use crate::{
     genesis, new_network_address, CommitteeConfig, ConsensuseConfig, GenesisConfig, NetworkConfig,
     ValidatorConfig, ValidatorInfo, AUTHORITIES_DB_NAME, CONSENSUS_DB_NAME, DEFAULT_STAKE,
+    DEFAULT_STAKE_AMOUNT,
 };
 
 pub struct ConfigBuilder<R = OsRng> {
@@ -87,6 +88,7 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                 ValidatorInfo {
                     public_key,
                     stake,
+                    stake_amount: DEFAULT_STAKE_AMOUNT,
                     network_address,
                 }
             })
@@ -123,7 +125,7 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                 .add_objects(objects);
 
             for validator in &validator_set {
-                builder = builder.add_validator(validator.public_key(), validator.stake());
+                builder = builder.add_validator(validator.clone());
             }
 
             builder.build()
@@ -212,6 +214,8 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     consensus_config,
                     committee_config: committe_config.clone(),
                     genesis: genesis.clone(),
+                    pruning_config: None,
+                    epoch_change_config: None,
                 }
             })
             .collect();
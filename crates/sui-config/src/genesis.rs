@@ -6,9 +6,20 @@ use move_binary_format::CompiledModule;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::{serde_as, DeserializeAs, SerializeAs};
 use std::path::PathBuf;
-use sui_types::{base_types::TxContext, crypto::PublicKeyBytes, object::Object};
+use sui_types::{
+    base_types::{encode_bytes_hex, TxContext},
+    gas::INITIAL_REFERENCE_GAS_PRICE,
+    gas_coin::GasCoin,
+    object::Object,
+    sui_system_state::{GasSchedule, SuiSystemState, Validator},
+};
 use tracing::info;
 
+use crate::ValidatorInfo;
+
+/// The SUI each validator starts with, to pay for the transactions changing the epoch.
+pub const VALIDATOR_GAS_VALUE: u64 = 10_000_000_000;
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Genesis {
@@ -81,7 +92,7 @@ pub struct Builder {
     move_modules: Vec<Vec<CompiledModule>>,
     objects: Vec<Object>,
     genesis_ctx: Option<TxContext>,
-    validators: Vec<(PublicKeyBytes, usize)>,
+    validators: Vec<ValidatorInfo>,
 }
 
 impl Builder {
@@ -124,15 +135,18 @@ impl Builder {
     //     self
     // }
 
-    //TODO actually use the validators added to genesis
-    pub fn add_validator(mut self, public_key: PublicKeyBytes, stake: usize) -> Self {
-        self.validators.push((public_key, stake));
+    /// Register a validator of the committee of the first epoch in the Sui system state.
+    pub fn add_validator(mut self, validator: ValidatorInfo) -> Self {
+        self.validators.push(validator);
         self
     }
 
     pub fn build(self) -> Genesis {
         let mut modules = Vec::new();
-        let objects = self.objects;
+        let mut objects = self.objects;
+
+        // The gas schedule is shared by the validators, and changed by their votes.
+        objects.push(GasSchedule::genesis().to_object());
 
         // Load Move Framework
         let move_framework_lib_path = self.move_framework.unwrap();
@@ -162,9 +176,43 @@ impl Builder {
         // add custom modules
         modules.extend(self.move_modules);
 
-        let genesis_ctx = self
+        let mut genesis_ctx = self
             .genesis_ctx
             .unwrap_or_else(sui_adapter::genesis::get_genesis_context);
+
+        // The system state holds the committee of the first epoch.
+        let validators = self
+            .validators
+            .iter()
+            .map(|validator| {
+                Validator::new(
+                    validator.sui_address(),
+                    validator.public_key(),
+                    encode_bytes_hex(&validator.sui_address()).into_bytes(),
+                    validator.network_address().to_vec(),
+                    INITIAL_REFERENCE_GAS_PRICE,
+                    validator.stake_amount(),
+                )
+            })
+            .collect();
+        for validator in &self.validators {
+            objects.push(Object::new_gas_coin(
+                genesis_ctx.fresh_id(),
+                validator.sui_address(),
+                VALIDATOR_GAS_VALUE,
+            ));
+        }
+
+        // Every coin in existence is minted by the treasury cap.
+        let coin_supply = objects
+            .iter()
+            .filter_map(|object| GasCoin::try_from(object).ok())
+            .try_fold(0u64, |supply, coin| supply.checked_add(coin.value()))
+            .expect("The SUI minted at genesis overflows");
+        objects.push(
+            SuiSystemState::genesis(validators, genesis_ctx.fresh_id(), coin_supply).to_object(),
+        );
+
         Genesis {
             modules,
             objects,
@@ -175,9 +223,17 @@ impl Builder {
 
 #[cfg(test)]
 mod test {
+    use std::path::PathBuf;
     use sui_framework::DEFAULT_FRAMEWORK_PATH;
+    use sui_types::{
+        crypto::get_key_pair,
+        gas::INITIAL_REFERENCE_GAS_PRICE,
+        gas_coin::GasCoin,
+        sui_system_state::{SuiSystemState, SUI_SYSTEM_STATE_OBJECT_ID},
+    };
 
-    use super::Genesis;
+    use super::{Builder, Genesis, VALIDATOR_GAS_VALUE};
+    use crate::{new_network_address, ValidatorInfo};
 
     #[test]
     fn roundtrip() {
@@ -194,4 +250,76 @@ mod test {
         let from_s = serde_json::from_str(&s).unwrap();
         assert_eq!(genesis, from_s);
     }
+
+    #[test]
+    fn genesis_committee() {
+        let validators: Vec<_> = (0..4)
+            .map(|_| ValidatorInfo {
+                public_key: *get_key_pair().1.public_key_bytes(),
+                stake: 1,
+                stake_amount: 1_000,
+                network_address: new_network_address(),
+            })
+            .collect();
+        let genesis = validators
+            .iter()
+            .fold(
+                Builder::new()
+                    .sui_framework(PathBuf::from(DEFAULT_FRAMEWORK_PATH))
+                    .move_framework(
+                        PathBuf::from(DEFAULT_FRAMEWORK_PATH)
+                            .join("deps")
+                            .join("move-stdlib"),
+                    ),
+                |builder, validator| builder.add_validator(validator.clone()),
+            )
+            .build();
+
+        // The system state holds the genesis validators as the committee of the first epoch.
+        let system_state = genesis
+            .objects()
+            .iter()
+            .find(|object| object.id() == SUI_SYSTEM_STATE_OBJECT_ID)
+            .unwrap();
+        let system_state = SuiSystemState::from_object(system_state).unwrap();
+        assert_eq!(
+            system_state.reference_gas_price,
+            INITIAL_REFERENCE_GAS_PRICE
+        );
+        let committee = system_state.get_committee().unwrap();
+        assert_eq!(committee.epoch, 0);
+        assert_eq!(committee.voting_rights.len(), validators.len());
+        for validator in &validators {
+            assert_eq!(committee.weight(&validator.public_key()), 1_000);
+            let validator_state = system_state
+                .validators
+                .active_validators
+                .iter()
+                .find(|v| v.sui_address == validator.sui_address())
+                .unwrap();
+            assert_eq!(
+                validator_state.net_address,
+                validator.network_address().to_vec()
+            );
+
+            // Each validator can pay for the transaction changing the epoch.
+            let gas_coin = genesis
+                .objects()
+                .iter()
+                .find(|object| object.get_single_owner() == Some(validator.sui_address()))
+                .unwrap();
+            assert_eq!(
+                GasCoin::try_from(gas_coin).unwrap().value(),
+                VALIDATOR_GAS_VALUE
+            );
+        }
+
+        // The treasury cap minted the storage fund, the stake and the coins.
+        assert_eq!(
+            system_state.treasury_cap.total_supply,
+            system_state.storage_fund.value
+                + 1_000 * validators.len() as u64
+                + VALIDATOR_GAS_VALUE * validators.len() as u64
+        );
+    }
 }
@@ -28,6 +28,9 @@ pub mod utils;
 
 const DEFAULT_STAKE: usize = 1;
 
+/// The SUI staked by each validator of the genesis committee.
+const DEFAULT_STAKE_AMOUNT: u64 = 100_000_000_000_000;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ValidatorConfig {
     key_pair: KeyPair,
@@ -39,6 +42,12 @@ pub struct ValidatorConfig {
     committee_config: CommitteeConfig,
 
     genesis: genesis::Genesis,
+
+    #[serde(default)]
+    pruning_config: Option<PruningConfig>,
+
+    #[serde(default)]
+    epoch_change_config: Option<EpochChangeConfig>,
 }
 
 impl Config for ValidatorConfig {}
@@ -75,6 +84,65 @@ impl ValidatorConfig {
     pub fn genesis(&self) -> &genesis::Genesis {
         &self.genesis
     }
+
+    pub fn pruning_config(&self) -> Option<&PruningConfig> {
+        self.pruning_config.as_ref()
+    }
+
+    pub fn epoch_change_config(&self) -> Option<&EpochChangeConfig> {
+        self.epoch_change_config.as_ref()
+    }
+}
+
+/// Configuration of a full node, which follows the validators of a network without
+/// taking part in the protocol.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FullNodeConfig {
+    db_path: PathBuf,
+    committee_config: CommitteeConfig,
+    genesis: genesis::Genesis,
+}
+
+impl Config for FullNodeConfig {}
+
+impl FullNodeConfig {
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    pub fn committee_config(&self) -> &CommitteeConfig {
+        &self.committee_config
+    }
+
+    pub fn genesis(&self) -> &genesis::Genesis {
+        &self.genesis
+    }
+}
+
+/// How much of the executed transaction history an authority keeps around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RetentionPolicy {
+    /// Keep at least this many of the most recently executed transactions.
+    Transactions(u64),
+    /// Keep at least this many of the most recent batches.
+    Batches(u64),
+}
+
+/// Configuration of the background task pruning old transactions from the authority store.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PruningConfig {
+    pub retention: RetentionPolicy,
+    /// How often the pruner runs, in milliseconds.
+    pub pruning_period_ms: u64,
+}
+
+/// Configuration of the background task ending the epochs of the committee.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EpochChangeConfig {
+    /// How long an epoch lasts, in milliseconds.
+    pub epoch_duration_ms: u64,
+    /// The gas budget of the transaction changing the epoch.
+    pub gas_budget: u64,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -139,9 +207,16 @@ impl CommitteeConfig {
 pub struct ValidatorInfo {
     public_key: PublicKeyBytes,
     stake: usize,
+    /// The SUI the validator stakes at genesis, recorded in the Sui system state.
+    #[serde(default = "default_stake_amount")]
+    stake_amount: u64,
     network_address: Multiaddr,
 }
 
+fn default_stake_amount() -> u64 {
+    DEFAULT_STAKE_AMOUNT
+}
+
 impl ValidatorInfo {
     pub fn sui_address(&self) -> SuiAddress {
         SuiAddress::from(self.public_key())
@@ -155,6 +230,10 @@ impl ValidatorInfo {
         self.stake
     }
 
+    pub fn stake_amount(&self) -> u64 {
+        self.stake_amount
+    }
+
     pub fn network_address(&self) -> &Multiaddr {
         &self.network_address
     }
@@ -195,6 +274,16 @@ impl NetworkConfig {
         self.validator_configs()[0].committee_config().committee()
     }
 
+    /// Returns the config of a full node following the validators of this network,
+    /// storing its state at `db_path`.
+    pub fn generate_fullnode_config(&self, db_path: PathBuf) -> FullNodeConfig {
+        FullNodeConfig {
+            db_path,
+            committee_config: self.validator_configs()[0].committee_config().clone(),
+            genesis: self.genesis.clone(),
+        }
+    }
+
     pub fn into_validator_configs(self) -> Vec<ValidatorConfig> {
         self.validator_configs
     }
@@ -226,6 +315,7 @@ const SUI_CONFIG_DIR: &str = "sui_config";
 pub const SUI_NETWORK_CONFIG: &str = "network.conf";
 pub const SUI_WALLET_CONFIG: &str = "wallet.conf";
 pub const SUI_GATEWAY_CONFIG: &str = "gateway.conf";
+pub const SUI_FULLNODE_CONFIG: &str = "fullnode.conf";
 pub const SUI_DEV_NET_URL: &str = "https://gateway.devnet.sui.io:9000";
 
 pub const AUTHORITIES_DB_NAME: &str = "authorities_db";
@@ -307,13 +397,7 @@ impl GenesisConfig {
             }
 
             for (object_id, value) in preload_objects_map {
-                let new_object = Object::with_id_owner_gas_coin_object_for_testing(
-                    object_id,
-                    sui_types::base_types::SequenceNumber::new(),
-                    address,
-                    value,
-                );
-                preload_objects.push(new_object);
+                preload_objects.push(Object::new_gas_coin(object_id, address, value));
             }
         }
 
@@ -6,7 +6,7 @@ use async_trait::async_trait;
 use sui::wallet_commands::WalletContext;
 use sui_types::{
     base_types::{ObjectID, SuiAddress},
-    gas_coin::GasCoin,
+    gas_coin::{GasCoin, GAS},
     messages::{ExecutionStatus, Transaction},
     object::Object,
 };
@@ -66,6 +66,22 @@ impl SimpleFaucet {
         })
     }
 
+    /// Check that the faucet owns enough SUI to send `amounts`, and pay for the gas.
+    async fn check_balance(&self, amounts: &[u64]) -> Result<(), FaucetError> {
+        let balance = self
+            .wallet
+            .gateway
+            .get_balance(self.active_address, GAS::type_tag())
+            .await
+            .map_err(|err| FaucetError::Wallet(err.to_string()))?;
+        let gas_budgets = (amounts.len() as u128 + 1) * DEFAULT_GAS_BUDGET as u128;
+        let required = amounts.iter().map(|amount| *amount as u128).sum::<u128>() + gas_budgets;
+        if balance.total_balance < required {
+            return Err(FaucetError::InsuffientBalance);
+        }
+        Ok(())
+    }
+
     async fn get_coins(&self, amounts: &[u64]) -> Result<Vec<Object>, FaucetError> {
         let result = self
             .split_coins(
@@ -117,7 +133,7 @@ impl SimpleFaucet {
                 coin_id,
                 amounts.to_vec().clone(),
                 Some(gas_object_id),
-                budget,
+                Some(budget),
             )
             .await?;
         let signature = context
@@ -146,7 +162,13 @@ impl SimpleFaucet {
 
         let data = context
             .gateway
-            .transfer_coin(signer, coin_id, Some(gas_object_id), budget, recipient)
+            .transfer_coin(
+                signer,
+                coin_id,
+                Some(gas_object_id),
+                Some(budget),
+                recipient,
+            )
             .await?;
         let signature = context
             .keystore
@@ -173,6 +195,7 @@ impl Faucet for SimpleFaucet {
         recipient: SuiAddress,
         amounts: &[u64],
     ) -> Result<FaucetReceipt, FaucetError> {
+        self.check_balance(amounts).await?;
         let coins = self.get_coins(amounts).await?;
         let coin_ids = coins.iter().map(|c| c.id()).collect::<Vec<ObjectID>>();
         self.transfer_coins(&coin_ids, recipient).await?;
@@ -326,7 +326,8 @@ export function isTransactionData(obj: any, _argumentName?: string): obj is Tran
         isTransactionKind(obj.kind) as boolean &&
         isTransactionDigest(obj.sender) as boolean &&
         isRawObjectRef(obj.gas_payment) as boolean &&
-        isSequenceNumber(obj.gas_budget) as boolean
+        isSequenceNumber(obj.gas_budget) as boolean &&
+        isSequenceNumber(obj.gas_price) as boolean
     )
 }
 
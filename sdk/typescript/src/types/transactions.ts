@@ -23,6 +23,7 @@ export type TransactionData = {
   sender: string;
   gas_payment: RawObjectRef;
   gas_budget: number;
+  gas_price: number;
 };
 
 // TODO: support u64
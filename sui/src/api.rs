@@ -8,7 +8,11 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::{base64, serde_as};
 use sui_core::gateway_state::{
-    gateway_responses::{TransactionEffectsResponse, TransactionResponse},
+    gateway_requests::{MoveCallParams, SingleTransactionParams, TransferCoinParams},
+    gateway_responses::{
+        Balance, EventPage, ObjectChangeNotification, OwnedObjectPage, TransactionEffectsResponse,
+        TransactionPage, TransactionResponse,
+    },
     GatewayTxSeqNumber,
 };
 use sui_core::sui_json::SuiJsonValue;
@@ -20,9 +24,10 @@ use sui_types::{
     base_types::{ObjectID, SuiAddress, TransactionDigest},
     crypto,
     crypto::SignableBytes,
+    event::{EventEnvelope, EventFilter, EventID},
     json_schema,
     json_schema::Base64,
-    messages::TransactionData,
+    messages::{DryRunTransactionResponse, TransactionData},
     object::ObjectRead,
 };
 
@@ -52,7 +57,7 @@ pub trait RpcGateway {
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> RpcResult<TransactionBytes>;
 
@@ -67,7 +72,7 @@ pub trait RpcGateway {
         type_arguments: Vec<SuiTypeTag>,
         arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Publish Move module.
@@ -77,7 +82,7 @@ pub trait RpcGateway {
         sender: SuiAddress,
         compiled_modules: Vec<Base64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 
     #[method(name = "splitCoin")]
@@ -87,17 +92,48 @@ pub trait RpcGateway {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 
+    /// Create a transaction merging the given coins into the primary coin, in a single transaction.
     #[method(name = "mergeCoins")]
     async fn merge_coin(
         &self,
         signer: SuiAddress,
         primary_coin: ObjectID,
-        coin_to_merge: ObjectID,
+        coins_to_merge: Vec<ObjectID>,
         gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> RpcResult<TransactionBytes>;
+
+    /// Create a transaction merging all coins of the signer with the given type parameter into the one with the largest balance.
+    #[method(name = "mergeAllCoins")]
+    async fn merge_all_coins(
+        &self,
+        signer: SuiAddress,
+        coin_type: SuiTypeTag,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> RpcResult<TransactionBytes>;
+
+    /// Create a transaction merging gas coins of the signer, for when no single coin covers the gas budget.
+    /// Return nothing if a coin, not in the excluded coins, already covers the gas budget.
+    #[method(name = "mergeGasCoins")]
+    async fn merge_gas_coins(
+        &self,
+        signer: SuiAddress,
         gas_budget: u64,
+        excluded_coins: Vec<ObjectID>,
+    ) -> RpcResult<Option<TransactionBytes>>;
+
+    /// Create a batch transaction, executing the given transfers and Move calls in order, in a single certificate.
+    #[method(name = "batchTransaction")]
+    async fn batch_transaction(
+        &self,
+        signer: SuiAddress,
+        single_transaction_params: Vec<SingleTransactionRequestParams>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Execute the transaction using the transaction data, signature and public key.
@@ -107,6 +143,11 @@ pub trait RpcGateway {
         signed_transaction: SignedTransaction,
     ) -> RpcResult<TransactionResponse>;
 
+    /// Execute a transaction against the current state, without committing it, and return
+    /// its effects, events and gas cost. The transaction does not need to be signed.
+    #[method(name = "dryRunTransaction")]
+    async fn dry_run_transaction(&self, tx_bytes: Base64) -> RpcResult<DryRunTransactionResponse>;
+
     /// Synchronize client state with validators.
     #[method(name = "syncAccountState")]
     async fn sync_account_state(&self, address: SuiAddress) -> RpcResult<()>;
@@ -115,6 +156,26 @@ pub trait RpcGateway {
     #[method(name = "getOwnedObjects")]
     async fn get_owned_objects(&self, owner: SuiAddress) -> RpcResult<ObjectResponse>;
 
+    /// Return a page of the objects owned by an address, in object ID order, starting at the cursor.
+    /// The objects can be filtered by Move type, and returned along with their type and owner.
+    #[method(name = "getOwnedObjectsPage")]
+    async fn get_owned_objects_page(
+        &self,
+        owner: SuiAddress,
+        object_type: Option<SuiTypeTag>,
+        cursor: Option<ObjectID>,
+        limit: u64,
+        show_details: Option<bool>,
+    ) -> RpcResult<OwnedObjectPage>;
+
+    /// Return the number and total value of the coins of the given type parameter owned by an address.
+    #[method(name = "getBalance")]
+    async fn get_balance(&self, address: SuiAddress, coin_type: SuiTypeTag) -> RpcResult<Balance>;
+
+    /// Return the balances of an address in every coin type it owns.
+    #[method(name = "getAllBalances")]
+    async fn get_all_balances(&self, address: SuiAddress) -> RpcResult<Vec<Balance>>;
+
     #[method(name = "getTotalTransactionNumber")]
     async fn get_total_transaction_number(&self) -> RpcResult<u64>;
 
@@ -137,12 +198,56 @@ pub trait RpcGateway {
         digest: TransactionDigest,
     ) -> RpcResult<TransactionEffectsResponse>;
 
+    /// Return a page of the transactions sent by or to an address, in execution order, starting at the cursor.
+    #[method(name = "getTransactionsByAddress")]
+    async fn get_transactions_by_address(
+        &self,
+        address: SuiAddress,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> RpcResult<TransactionPage>;
+
+    /// Return a page of the transactions that touched an object, in execution order, starting at the cursor.
+    #[method(name = "getTransactionsByObject")]
+    async fn get_transactions_by_object(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> RpcResult<TransactionPage>;
+
+    /// Return a page of the events matching the filter, in execution order, starting at the cursor.
+    #[method(name = "getEvents")]
+    async fn get_events(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+        limit: u64,
+    ) -> RpcResult<EventPage>;
+
     /// Low level API to get object info. Client Applications should prefer to use
     /// `get_object_typed_info` instead.
     #[method(name = "getObjectInfoRaw")]
     async fn get_object_info(&self, object_id: ObjectID) -> RpcResult<ObjectRead>;
 }
 
+/// Subscriptions to the gateway, which are served over WebSocket.
+#[rpc(server, client, namespace = "sui")]
+pub trait RpcGatewaySubscriptions {
+    /// Subscribe to the transactions executed from now on, along with their sequence numbers.
+    #[subscription(name = "subscribeTransactions", item = (GatewayTxSeqNumber, TransactionDigest))]
+    fn subscribe_transactions(&self);
+
+    /// Subscribe to the changes made to the objects owned by an address, before or after
+    /// the transactions executed from now on.
+    #[subscription(name = "subscribeObjectChanges", item = ObjectChangeNotification)]
+    fn subscribe_object_changes(&self, address: SuiAddress);
+
+    /// Subscribe to the events matching the filter, emitted by the transactions executed from now on.
+    #[subscription(name = "subscribeEvents", item = EventEnvelope)]
+    fn subscribe_events(&self, filter: EventFilter);
+}
+
 #[serde_as]
 #[derive(Serialize, Deserialize, JsonSchema)]
 pub struct SignedTransaction {
@@ -190,3 +295,77 @@ impl TransactionBytes {
         TransactionData::from_signable_bytes(&self.tx_bytes)
     }
 }
+
+/// A single transaction of a batch transaction.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub enum SingleTransactionRequestParams {
+    TransferCoin(TransferCoinRequestParams),
+    MoveCall(MoveCallRequestParams),
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct TransferCoinRequestParams {
+    pub recipient: SuiAddress,
+    pub object_id: ObjectID,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct MoveCallRequestParams {
+    pub package_object_id: ObjectID,
+    #[schemars(with = "json_schema::Identifier")]
+    pub module: Identifier,
+    #[schemars(with = "json_schema::Identifier")]
+    pub function: Identifier,
+    pub type_arguments: Vec<SuiTypeTag>,
+    pub arguments: Vec<SuiJsonValue>,
+}
+
+impl TryFrom<SingleTransactionRequestParams> for SingleTransactionParams {
+    type Error = anyhow::Error;
+
+    fn try_from(params: SingleTransactionRequestParams) -> Result<Self, Self::Error> {
+        Ok(match params {
+            SingleTransactionRequestParams::TransferCoin(params) => {
+                SingleTransactionParams::TransferCoin(TransferCoinParams {
+                    recipient: params.recipient,
+                    object_id: params.object_id,
+                })
+            }
+            SingleTransactionRequestParams::MoveCall(params) => {
+                SingleTransactionParams::MoveCall(MoveCallParams {
+                    package_object_id: params.package_object_id,
+                    module: params.module,
+                    function: params.function,
+                    type_arguments: params
+                        .type_arguments
+                        .into_iter()
+                        .map(|tag| tag.try_into())
+                        .collect::<Result<Vec<_>, _>>()?,
+                    arguments: params.arguments,
+                })
+            }
+        })
+    }
+}
+
+impl From<SingleTransactionParams> for SingleTransactionRequestParams {
+    fn from(params: SingleTransactionParams) -> Self {
+        match params {
+            SingleTransactionParams::TransferCoin(params) => {
+                SingleTransactionRequestParams::TransferCoin(TransferCoinRequestParams {
+                    recipient: params.recipient,
+                    object_id: params.object_id,
+                })
+            }
+            SingleTransactionParams::MoveCall(params) => {
+                SingleTransactionRequestParams::MoveCall(MoveCallRequestParams {
+                    package_object_id: params.package_object_id,
+                    module: params.module,
+                    function: params.function,
+                    type_arguments: params.type_arguments.into_iter().map(Into::into).collect(),
+                    arguments: params.arguments,
+                })
+            }
+        }
+    }
+}
@@ -13,12 +13,12 @@ use std::{
 };
 use sui::{
     api::{RpcGatewayOpenRpc, RpcGatewayServer},
-    config::sui_config_dir,
+    config::{sui_config_dir, SUI_FULLNODE_CONFIG},
     sui_node::SuiNode,
 };
 use tracing::info;
 
-const DEFAULT_NODE_SERVER_PORT: &str = "5002";
+const DEFAULT_NODE_SERVER_PORT: &str = "5003";
 const DEFAULT_NODE_SERVER_ADDR_IPV4: &str = "127.0.0.1";
 
 #[derive(Parser)]
@@ -52,7 +52,7 @@ async fn main() -> anyhow::Result<()> {
     let options: SuiNodeOpt = SuiNodeOpt::parse();
     let config_path = options
         .config
-        .unwrap_or(sui_config_dir()?.join("node.conf"));
+        .unwrap_or(sui_config_dir()?.join(SUI_FULLNODE_CONFIG));
     info!("Node config file path: {:?}", config_path);
 
     let server_builder = HttpServerBuilder::default();
@@ -72,10 +72,12 @@ async fn main() -> anyhow::Result<()> {
         .build(SocketAddr::new(IpAddr::V4(options.host), options.port))
         .await?;
 
+    let node = SuiNode::new(&config_path).await?;
+
     let mut module = RpcModule::new(());
     let open_rpc = RpcGatewayOpenRpc::open_rpc();
     module.register_method("rpc.discover", move |_, _| Ok(open_rpc.clone()))?;
-    module.merge(SuiNode::new(&config_path)?.into_rpc())?;
+    module.merge(node.rpc().into_rpc())?;
 
     info!(
         "Available JSON-RPC methods : {:?}",
@@ -84,8 +86,10 @@ async fn main() -> anyhow::Result<()> {
 
     let addr = server.local_addr()?;
     let server_handle = server.start(module)?;
-    info!("Sui RPC Gateway listening on local_addr:{}", addr);
+    info!("Sui full node listening on local_addr:{}", addr);
 
-    server_handle.await;
-    Ok(())
+    tokio::select! {
+        _ = server_handle => Ok(()),
+        result = node.wait_for_completion() => result,
+    }
 }
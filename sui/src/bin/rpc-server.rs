@@ -4,6 +4,7 @@
 use clap::Parser;
 use jsonrpsee::{
     http_server::{AccessControlBuilder, HttpServerBuilder},
+    ws_server::WsServerBuilder,
     RpcModule,
 };
 use jsonrpsee_core::middleware::Middleware;
@@ -17,13 +18,14 @@ use std::{
     time::Instant,
 };
 use sui::{
-    api::{RpcGatewayOpenRpc, RpcGatewayServer},
+    api::{RpcGatewayOpenRpc, RpcGatewayServer, RpcGatewaySubscriptionsServer},
     config::sui_config_dir,
     rpc_gateway::RpcGatewayImpl,
 };
 use tracing::info;
 
 const DEFAULT_RPC_SERVER_PORT: &str = "5001";
+const DEFAULT_WS_SERVER_PORT: &str = "5002";
 const DEFAULT_RPC_SERVER_ADDR_IPV4: &str = "127.0.0.1";
 const PROM_PORT_ADDR: &str = "127.0.0.1:9184";
 
@@ -44,6 +46,10 @@ struct RpcGatewayOpt {
     #[clap(long, default_value = DEFAULT_RPC_SERVER_PORT)]
     port: u16,
 
+    /// Port of the WebSocket endpoint, which serves the subscriptions along with the methods
+    #[clap(long, default_value = DEFAULT_WS_SERVER_PORT)]
+    ws_port: u16,
+
     #[clap(long, default_value = DEFAULT_RPC_SERVER_ADDR_IPV4)]
     host: Ipv4Addr,
 }
@@ -82,25 +88,41 @@ async fn main() -> anyhow::Result<()> {
         .build(SocketAddr::new(IpAddr::V4(options.host), options.port))
         .await?;
 
+    let ws_server = WsServerBuilder::default()
+        .build(SocketAddr::new(IpAddr::V4(options.host), options.ws_port))
+        .await?;
+
+    let rpc_gateway = RpcGatewayImpl::new(&config_path).await?;
     let mut module = RpcModule::new(());
     let open_rpc = RpcGatewayOpenRpc::open_rpc();
     module.register_method("rpc.discover", move |_, _| Ok(open_rpc.clone()))?;
-    module.merge(RpcGatewayImpl::new(&config_path)?.into_rpc())?;
+    module.merge(rpc_gateway.clone().into_rpc())?;
+
+    // Subscriptions need a persistent connection, so they are only served over WebSocket.
+    let mut ws_module = module.clone();
+    ws_module.merge(rpc_gateway.subscriptions().into_rpc())?;
 
     info!(
         "Available JSON-RPC methods : {:?}",
-        module.method_names().collect::<Vec<_>>()
+        ws_module.method_names().collect::<Vec<_>>()
     );
 
     let addr = server.local_addr()?;
     let server_handle = server.start(module)?;
     info!(local_addr =? addr, "Sui RPC Gateway listening on local_addr");
 
+    let ws_addr = ws_server.local_addr()?;
+    let ws_server_handle = ws_server.start(ws_module)?;
+    info!(local_addr =? ws_addr, "Sui RPC Gateway WebSocket endpoint listening on local_addr");
+
     let prom_binding = PROM_PORT_ADDR.parse().unwrap();
     info!("Starting Prometheus HTTP endpoint at {}", PROM_PORT_ADDR);
     prometheus_exporter::start(prom_binding).expect("Failed to start Prometheus exporter");
 
-    server_handle.await;
+    tokio::select! {
+        _ = server_handle => (),
+        _ = ws_server_handle => (),
+    }
     Ok(())
 }
 
@@ -225,10 +225,11 @@ async fn handle_command(
                 cache.insert(CacheKey::flag("--address"), addresses.clone());
                 cache.insert(CacheKey::flag("--to"), addresses);
             }
-            WalletCommandResult::Objects(ref objects) => {
-                let objects = objects
+            WalletCommandResult::Objects(ref page) => {
+                let objects = page
+                    .data
                     .iter()
-                    .map(|(object_id, _, _)| format!("{object_id}"))
+                    .map(|object| format!("{}", object.object_ref.0))
                     .collect::<Vec<_>>();
                 cache.insert(CacheKey::new("object", "--id"), objects.clone());
                 cache.insert(CacheKey::flag("--gas"), objects.clone());
@@ -11,8 +11,9 @@ use std::{
 };
 use sui_config::ValidatorInfo;
 use sui_core::{
-    authority_client::NetworkAuthorityClient,
-    gateway_state::{GatewayClient, GatewayState},
+    authority_aggregator::QuorumDriverConfig,
+    authority_client::{AuthorityConnector, NetworkAuthorityClient},
+    gateway_state::{GatewayClient, GatewayState, DEFAULT_GAS_ESTIMATION_MARGIN},
 };
 use sui_types::{
     base_types::AuthorityName,
@@ -64,7 +65,12 @@ impl GatewayType {
                 let path = config.db_folder_path.clone();
                 let committee = config.make_committee();
                 let authority_clients = config.make_authority_clients();
-                Box::new(GatewayState::new(path, committee, authority_clients)?)
+                Box::new(
+                    GatewayState::new(path, committee, authority_clients)?
+                        .with_gas_estimation_margin(config.gas_estimation_margin)
+                        .with_quorum_driver_config(config.quorum_driver.clone())
+                        .with_authority_connector(config.make_authority_connector()),
+                )
             }
             GatewayType::RPC(url) => Box::new(RpcGatewayClient::new(url.clone())?),
         })
@@ -79,6 +85,16 @@ pub struct GatewayConfig {
     pub recv_timeout: Duration,
     pub buffer_size: usize,
     pub db_folder_path: PathBuf,
+    /// Margin added to automatically estimated gas budgets, in percent.
+    #[serde(default = "default_gas_estimation_margin")]
+    pub gas_estimation_margin: u64,
+    /// How transactions and certificates are driven to a quorum of authorities.
+    #[serde(default)]
+    pub quorum_driver: QuorumDriverConfig,
+}
+
+fn default_gas_estimation_margin() -> u64 {
+    DEFAULT_GAS_ESTIMATION_MARGIN
 }
 
 impl Config for GatewayConfig {}
@@ -105,6 +121,14 @@ impl GatewayConfig {
         }
         authority_clients
     }
+
+    /// Connects to the authorities joining the committee after an epoch change.
+    pub fn make_authority_connector(&self) -> AuthorityConnector<NetworkAuthorityClient> {
+        let mut config = mysten_network::config::Config::new();
+        config.connect_timeout = Some(self.send_timeout);
+        config.request_timeout = Some(self.recv_timeout);
+        NetworkAuthorityClient::connector(config)
+    }
 }
 
 impl Default for GatewayConfig {
@@ -116,6 +140,8 @@ impl Default for GatewayConfig {
             recv_timeout: Duration::from_micros(4000000),
             buffer_size: 650000,
             db_folder_path: Default::default(),
+            gas_estimation_margin: DEFAULT_GAS_ESTIMATION_MARGIN,
+            quorum_driver: QuorumDriverConfig::default(),
         }
     }
 }
@@ -2,16 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::path::Path;
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
 use ed25519_dalek::ed25519::signature::Signature;
+use futures::stream::BoxStream;
 use jsonrpsee::core::RpcResult;
+use jsonrpsee::SubscriptionSink;
 use move_core_types::identifier::Identifier;
-use tracing::debug;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+use tracing::{debug, warn};
 
 use sui_core::gateway_state::{
-    gateway_responses::{TransactionEffectsResponse, TransactionResponse},
+    gateway_requests::SingleTransactionParams,
+    gateway_responses::{
+        Balance, EventPage, OwnedObjectPage, TransactionEffectsResponse, TransactionPage,
+        TransactionResponse,
+    },
     GatewayClient, GatewayState, GatewayTxSeqNumber,
 };
 use sui_core::sui_json::SuiJsonValue;
@@ -19,26 +28,34 @@ use sui_types::{
     base_types::{ObjectID, SuiAddress, TransactionDigest},
     crypto,
     crypto::SignableBytes,
+    event::{EventFilter, EventID},
     json_schema::Base64,
-    messages::{Transaction, TransactionData},
+    messages::{DryRunTransactionResponse, Transaction, TransactionData},
     object::ObjectRead,
 };
 
 use crate::rpc_gateway::responses::SuiTypeTag;
 use crate::{
-    api::{RpcGatewayServer, SignedTransaction, TransactionBytes},
+    api::{
+        RpcGatewayServer, RpcGatewaySubscriptionsServer, SignedTransaction,
+        SingleTransactionRequestParams, TransactionBytes,
+    },
     config::{GatewayConfig, PersistedConfig},
     rpc_gateway::responses::{GetObjectInfoResponse, NamedObjectRef, ObjectResponse},
 };
 
 pub mod responses;
 
+#[derive(Clone)]
 pub struct RpcGatewayImpl {
-    gateway: GatewayClient,
+    gateway: Arc<GatewayClient>,
 }
 
 impl RpcGatewayImpl {
-    pub fn new(config_path: &Path) -> anyhow::Result<Self> {
+    /// Create a gateway from its config, finishing or releasing the transactions it
+    /// left pending before serving any request. The gateway first moves to the current
+    /// committee, in case the epoch changed since it was configured.
+    pub async fn new(config_path: &Path) -> anyhow::Result<Self> {
         let config: GatewayConfig = PersistedConfig::read(config_path).map_err(|e| {
             anyhow!(
                 "Failed to read config file at {:?}: {}. Have you run `sui genesis` first?",
@@ -48,12 +65,29 @@ impl RpcGatewayImpl {
         })?;
         let committee = config.make_committee();
         let authority_clients = config.make_authority_clients();
-        let gateway = Box::new(GatewayState::new(
-            config.db_folder_path,
-            committee,
-            authority_clients,
-        )?);
-        Ok(Self { gateway })
+        let connect_authority = config.make_authority_connector();
+        let gateway = GatewayState::new(config.db_folder_path, committee, authority_clients)?
+            .with_gas_estimation_margin(config.gas_estimation_margin)
+            .with_quorum_driver_config(config.quorum_driver)
+            .with_authority_connector(connect_authority);
+        if let Err(e) = gateway.reconfigure_from_system_state().await {
+            warn!("Failed to read the committee from the Sui system state: {e}");
+        }
+        gateway.recover_pending_transactions().await?;
+        Ok(Self::new_with_gateway(Box::new(gateway)))
+    }
+
+    pub fn new_with_gateway(gateway: GatewayClient) -> Self {
+        Self {
+            gateway: Arc::new(gateway),
+        }
+    }
+
+    /// Return the subscription handler, sharing this gateway.
+    pub fn subscriptions(&self) -> RpcGatewaySubscriptionsImpl {
+        RpcGatewaySubscriptionsImpl {
+            gateway: self.gateway.clone(),
+        }
     }
 }
 
@@ -64,7 +98,7 @@ impl RpcGatewayServer for RpcGatewayImpl {
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> RpcResult<TransactionBytes> {
         let data = self
@@ -79,7 +113,7 @@ impl RpcGatewayServer for RpcGatewayImpl {
         sender: SuiAddress,
         compiled_modules: Vec<Base64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
         let compiled_modules = compiled_modules
             .into_iter()
@@ -99,7 +133,7 @@ impl RpcGatewayServer for RpcGatewayImpl {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
         let data = self
             .gateway
@@ -112,13 +146,58 @@ impl RpcGatewayServer for RpcGatewayImpl {
         &self,
         signer: SuiAddress,
         primary_coin: ObjectID,
-        coin_to_merge: ObjectID,
+        coins_to_merge: Vec<ObjectID>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> RpcResult<TransactionBytes> {
+        let data = self
+            .gateway
+            .merge_coins(signer, primary_coin, coins_to_merge, gas, gas_budget)
+            .await?;
+        Ok(TransactionBytes::from_data(data)?)
+    }
+
+    async fn merge_all_coins(
+        &self,
+        signer: SuiAddress,
+        coin_type: SuiTypeTag,
         gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> RpcResult<TransactionBytes> {
+        let data = self
+            .gateway
+            .merge_all_coins(signer, coin_type.try_into()?, gas, gas_budget)
+            .await?;
+        Ok(TransactionBytes::from_data(data)?)
+    }
+
+    async fn merge_gas_coins(
+        &self,
+        signer: SuiAddress,
         gas_budget: u64,
+        excluded_coins: Vec<ObjectID>,
+    ) -> RpcResult<Option<TransactionBytes>> {
+        let data = self
+            .gateway
+            .merge_gas_coins(signer, gas_budget, excluded_coins)
+            .await?;
+        Ok(data.map(TransactionBytes::from_data).transpose()?)
+    }
+
+    async fn batch_transaction(
+        &self,
+        signer: SuiAddress,
+        single_transaction_params: Vec<SingleTransactionRequestParams>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
+        let single_transaction_params = single_transaction_params
+            .into_iter()
+            .map(SingleTransactionParams::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
         let data = self
             .gateway
-            .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
+            .batch_transaction(signer, single_transaction_params, gas, gas_budget)
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -135,6 +214,42 @@ impl RpcGatewayServer for RpcGatewayImpl {
         Ok(ObjectResponse { objects })
     }
 
+    async fn get_owned_objects_page(
+        &self,
+        owner: SuiAddress,
+        object_type: Option<SuiTypeTag>,
+        cursor: Option<ObjectID>,
+        limit: u64,
+        show_details: Option<bool>,
+    ) -> RpcResult<OwnedObjectPage> {
+        let object_type = match object_type.map(TryInto::try_into).transpose()? {
+            Some(TypeTag::Struct(tag)) => Some(tag),
+            Some(tag) => return Err(anyhow!("{} is not a Move struct type", tag).into()),
+            None => None,
+        };
+        Ok(self
+            .gateway
+            .get_owned_objects_page(
+                owner,
+                object_type,
+                cursor,
+                limit,
+                show_details.unwrap_or_default(),
+            )
+            .await?)
+    }
+
+    async fn get_balance(&self, address: SuiAddress, coin_type: SuiTypeTag) -> RpcResult<Balance> {
+        Ok(self
+            .gateway
+            .get_balance(address, coin_type.try_into()?)
+            .await?)
+    }
+
+    async fn get_all_balances(&self, address: SuiAddress) -> RpcResult<Vec<Balance>> {
+        Ok(self.gateway.get_all_balances(address).await?)
+    }
+
     async fn get_object_info(&self, object_id: ObjectID) -> RpcResult<ObjectRead> {
         Ok(self.gateway.get_object_info(object_id).await?)
     }
@@ -162,6 +277,11 @@ impl RpcGatewayServer for RpcGatewayImpl {
             .await?)
     }
 
+    async fn dry_run_transaction(&self, tx_bytes: Base64) -> RpcResult<DryRunTransactionResponse> {
+        let data = TransactionData::from_signable_bytes(&tx_bytes)?;
+        Ok(self.gateway.dry_run_transaction(data).await?)
+    }
+
     async fn move_call(
         &self,
         signer: SuiAddress,
@@ -171,7 +291,7 @@ impl RpcGatewayServer for RpcGatewayImpl {
         type_arguments: Vec<SuiTypeTag>,
         rpc_arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> RpcResult<TransactionBytes> {
         let data = async {
             self.gateway
@@ -225,4 +345,73 @@ impl RpcGatewayServer for RpcGatewayImpl {
     ) -> RpcResult<TransactionEffectsResponse> {
         Ok(self.gateway.get_transaction(digest).await?)
     }
+
+    async fn get_transactions_by_address(
+        &self,
+        address: SuiAddress,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> RpcResult<TransactionPage> {
+        Ok(self
+            .gateway
+            .get_transactions_by_address(address, cursor, limit)
+            .await?)
+    }
+
+    async fn get_transactions_by_object(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> RpcResult<TransactionPage> {
+        Ok(self
+            .gateway
+            .get_transactions_by_object(object_id, cursor, limit)
+            .await?)
+    }
+
+    async fn get_events(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+        limit: u64,
+    ) -> RpcResult<EventPage> {
+        Ok(self.gateway.get_events(filter, cursor, limit).await?)
+    }
+}
+
+/// Serves the subscriptions to the gateway.
+pub struct RpcGatewaySubscriptionsImpl {
+    gateway: Arc<GatewayClient>,
+}
+
+impl RpcGatewaySubscriptionsServer for RpcGatewaySubscriptionsImpl {
+    fn subscribe_transactions(&self, sink: SubscriptionSink) -> RpcResult<()> {
+        pipe_to_sink(sink, self.gateway.subscribe_transactions()?);
+        Ok(())
+    }
+
+    fn subscribe_object_changes(
+        &self,
+        sink: SubscriptionSink,
+        address: SuiAddress,
+    ) -> RpcResult<()> {
+        pipe_to_sink(sink, self.gateway.subscribe_object_changes(address)?);
+        Ok(())
+    }
+
+    fn subscribe_events(&self, sink: SubscriptionSink, filter: EventFilter) -> RpcResult<()> {
+        pipe_to_sink(sink, self.gateway.subscribe_events(filter)?);
+        Ok(())
+    }
+}
+
+/// Forward the items of the stream to the subscriber, until either ends.
+fn pipe_to_sink<T: Serialize + Send + 'static>(
+    sink: SubscriptionSink,
+    stream: BoxStream<'static, T>,
+) {
+    tokio::spawn(async move {
+        sink.pipe_from_stream(stream).await;
+    });
 }
@@ -1,19 +1,25 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use move_core_types::identifier::Identifier;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use tokio::runtime::Handle;
 
-use sui_core::gateway_state::gateway_responses::{TransactionEffectsResponse, TransactionResponse};
+use sui_core::gateway_state::gateway_requests::SingleTransactionParams;
+use sui_core::gateway_state::gateway_responses::{
+    Balance, EventPage, ObjectChangeNotification, OwnedObjectPage, TransactionEffectsResponse,
+    TransactionPage, TransactionResponse,
+};
 use sui_core::gateway_state::{GatewayAPI, GatewayTxSeqNumber};
 use sui_core::sui_json::SuiJsonValue;
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::event::{EventEnvelope, EventFilter, EventID};
 use sui_types::json_schema::Base64;
-use sui_types::messages::{Transaction, TransactionData};
+use sui_types::messages::{DryRunTransactionResponse, Transaction, TransactionData};
 use sui_types::object::ObjectRead;
 
 use crate::api::{RpcGatewayClient as RpcGateway, SignedTransaction, TransactionBytes};
@@ -43,12 +49,22 @@ impl GatewayAPI for RpcGatewayClient {
         Ok(self.client.execute_transaction(signed_tx).await?)
     }
 
+    async fn dry_run_transaction(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionResponse, Error> {
+        Ok(self
+            .client
+            .dry_run_transaction(Base64(tx_data.to_bytes()))
+            .await?)
+    }
+
     async fn transfer_coin(
         &self,
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
@@ -72,7 +88,7 @@ impl GatewayAPI for RpcGatewayClient {
         type_arguments: Vec<TypeTag>,
         arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
             .client
@@ -98,7 +114,7 @@ impl GatewayAPI for RpcGatewayClient {
         signer: SuiAddress,
         package_bytes: Vec<Vec<u8>>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let package_bytes = package_bytes.into_iter().map(Base64).collect();
         let bytes: TransactionBytes = self
@@ -114,7 +130,7 @@ impl GatewayAPI for RpcGatewayClient {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
             .client
@@ -127,13 +143,62 @@ impl GatewayAPI for RpcGatewayClient {
         &self,
         signer: SuiAddress,
         primary_coin: ObjectID,
-        coin_to_merge: ObjectID,
+        coins_to_merge: Vec<ObjectID>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, Error> {
+        let bytes: TransactionBytes = self
+            .client
+            .merge_coin(signer, primary_coin, coins_to_merge, gas, gas_budget)
+            .await?;
+        bytes.to_data()
+    }
+
+    async fn merge_all_coins(
+        &self,
+        signer: SuiAddress,
+        coin_type: TypeTag,
         gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, Error> {
+        let bytes: TransactionBytes = self
+            .client
+            .merge_all_coins(signer, coin_type.into(), gas, gas_budget)
+            .await?;
+        bytes.to_data()
+    }
+
+    async fn merge_gas_coins(
+        &self,
+        signer: SuiAddress,
         gas_budget: u64,
+        excluded_coins: Vec<ObjectID>,
+    ) -> Result<Option<TransactionData>, Error> {
+        let bytes: Option<TransactionBytes> = self
+            .client
+            .merge_gas_coins(signer, gas_budget, excluded_coins)
+            .await?;
+        bytes.map(TransactionBytes::to_data).transpose()
+    }
+
+    async fn batch_transaction(
+        &self,
+        signer: SuiAddress,
+        single_transaction_params: Vec<SingleTransactionParams>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, Error> {
         let bytes: TransactionBytes = self
             .client
-            .merge_coin(signer, primary_coin, coin_to_merge, gas, gas_budget)
+            .batch_transaction(
+                signer,
+                single_transaction_params
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                gas,
+                gas_budget,
+            )
             .await?;
         bytes.to_data()
     }
@@ -152,6 +217,34 @@ impl GatewayAPI for RpcGatewayClient {
         Ok(object_refs)
     }
 
+    async fn get_owned_objects_page(
+        &self,
+        account_addr: SuiAddress,
+        object_type: Option<StructTag>,
+        cursor: Option<ObjectID>,
+        limit: u64,
+        show_details: bool,
+    ) -> Result<OwnedObjectPage, Error> {
+        Ok(self
+            .client
+            .get_owned_objects_page(
+                account_addr,
+                object_type.map(|tag| TypeTag::Struct(tag).into()),
+                cursor,
+                limit,
+                Some(show_details),
+            )
+            .await?)
+    }
+
+    async fn get_balance(&self, address: SuiAddress, coin_type: TypeTag) -> Result<Balance, Error> {
+        Ok(self.client.get_balance(address, coin_type.into()).await?)
+    }
+
+    async fn get_all_balances(&self, address: SuiAddress) -> Result<Vec<Balance>, Error> {
+        Ok(self.client.get_all_balances(address).await?)
+    }
+
     fn get_total_transaction_number(&self) -> Result<u64, Error> {
         let handle = Handle::current();
         let _ = handle.enter();
@@ -189,4 +282,63 @@ impl GatewayAPI for RpcGatewayClient {
     ) -> Result<TransactionEffectsResponse, Error> {
         Ok(self.client.get_transaction(digest).await?)
     }
+
+    async fn get_transactions_by_address(
+        &self,
+        address: SuiAddress,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> Result<TransactionPage, Error> {
+        Ok(self
+            .client
+            .get_transactions_by_address(address, cursor, limit)
+            .await?)
+    }
+
+    async fn get_transactions_by_object(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> Result<TransactionPage, Error> {
+        Ok(self
+            .client
+            .get_transactions_by_object(object_id, cursor, limit)
+            .await?)
+    }
+
+    async fn get_events(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+        limit: u64,
+    ) -> Result<EventPage, Error> {
+        Ok(self.client.get_events(filter, cursor, limit).await?)
+    }
+
+    fn subscribe_events(
+        &self,
+        _filter: EventFilter,
+    ) -> Result<BoxStream<'static, EventEnvelope>, Error> {
+        Err(anyhow!(
+            "Subscriptions are not supported over the HTTP gateway client"
+        ))
+    }
+
+    fn subscribe_transactions(
+        &self,
+    ) -> Result<BoxStream<'static, (GatewayTxSeqNumber, TransactionDigest)>, Error> {
+        Err(anyhow!(
+            "Subscriptions are not supported over the HTTP gateway client"
+        ))
+    }
+
+    fn subscribe_object_changes(
+        &self,
+        _address: SuiAddress,
+    ) -> Result<BoxStream<'static, ObjectChangeNotification>, Error> {
+        Err(anyhow!(
+            "Subscriptions are not supported over the HTTP gateway client"
+        ))
+    }
 }
@@ -3,7 +3,7 @@
 use crate::{
     config::{
         sui_config_dir, Config, GatewayConfig, GatewayType, PersistedConfig, WalletConfig,
-        SUI_GATEWAY_CONFIG, SUI_NETWORK_CONFIG, SUI_WALLET_CONFIG,
+        SUI_FULLNODE_CONFIG, SUI_GATEWAY_CONFIG, SUI_NETWORK_CONFIG, SUI_WALLET_CONFIG,
     },
     keystore::{Keystore, KeystoreType, SuiKeystore},
 };
@@ -18,15 +18,18 @@ use std::time::Duration;
 use std::{collections::BTreeMap, num::NonZeroUsize};
 use sui_config::{builder::ConfigBuilder, NetworkConfig};
 use sui_config::{GenesisConfig, ValidatorConfig};
-use sui_core::authority::{AuthorityState, AuthorityStore};
+use sui_core::authority::{AuthorityState, AuthorityStore, ReplicaStore};
 use sui_core::authority_active::ActiveAuthority;
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_core::authority_server::AuthorityServer;
 use sui_core::authority_server::AuthorityServerHandle;
 use sui_core::consensus_adapter::ConsensusListener;
-use sui_types::base_types::decode_bytes_hex;
-use sui_types::base_types::SuiAddress;
+use sui_core::epoch_change::EpochChangeDriver;
+use sui_core::replica::ReplicaNode;
+use sui_types::base_types::{decode_bytes_hex, encode_bytes_hex};
+use sui_types::base_types::{SuiAddress, TxSequenceNumber};
 use sui_types::error::SuiResult;
+use sui_types::snapshot::StateSnapshot;
 use tokio::sync::mpsc::channel;
 use tracing::{error, info};
 
@@ -60,6 +63,49 @@ pub enum SuiCommand {
         #[clap(short, long, help = "Forces overwriting existing configuration")]
         force: bool,
     },
+    /// Write a snapshot of the live object set of a stopped authority to a file.
+    #[clap(name = "export-snapshot")]
+    ExportSnapshot {
+        #[clap(long, help = "Path to the database of the authority")]
+        db_path: PathBuf,
+        #[clap(
+            long,
+            help = "Next sequence number of the batch to snapshot, which must be the last batch"
+        )]
+        batch: TxSequenceNumber,
+        #[clap(long, help = "File to write the snapshot to")]
+        output: PathBuf,
+    },
+    /// Add to a snapshot file the batch of another stopped authority signed with the
+    /// same state root, until the state root is signed by a quorum.
+    #[clap(name = "attest-snapshot")]
+    AttestSnapshot {
+        #[clap(long, help = "Path to the database of the attesting authority")]
+        db_path: PathBuf,
+        #[clap(long, help = "Snapshot file to attest")]
+        input: PathBuf,
+        #[clap(long, help = "File to write the attested snapshot to")]
+        output: PathBuf,
+    },
+    /// Bootstrap the empty database of an authority from a snapshot file.
+    #[clap(name = "import-snapshot")]
+    ImportSnapshot {
+        #[clap(long, help = "Network config used to verify the snapshot")]
+        config: Option<PathBuf>,
+        #[clap(long, help = "Path to the database of the authority")]
+        db_path: PathBuf,
+        #[clap(long, help = "Snapshot file to import")]
+        input: PathBuf,
+    },
+    /// Follow the authorities of a network and re-execute their transactions into a
+    /// local store that keeps all object versions.
+    #[clap(name = "replica")]
+    Replica {
+        #[clap(long, help = "Network config of the authorities to follow")]
+        config: Option<PathBuf>,
+        #[clap(long, help = "Path to the database of the replica")]
+        db_path: PathBuf,
+    },
     #[clap(name = "signtool")]
     SignTool {
         #[clap(long)]
@@ -172,9 +218,11 @@ impl SuiCommand {
                 let network_path = sui_config_dir.join(SUI_NETWORK_CONFIG);
                 let wallet_path = sui_config_dir.join(SUI_WALLET_CONFIG);
                 let gateway_path = sui_config_dir.join(SUI_GATEWAY_CONFIG);
+                let fullnode_path = sui_config_dir.join(SUI_FULLNODE_CONFIG);
                 let keystore_path = sui_config_dir.join("wallet.key");
                 let db_folder_path = sui_config_dir.join("client_db");
                 let gateway_db_folder_path = sui_config_dir.join("gateway_client_db");
+                let fullnode_db_path = sui_config_dir.join("fullnode_db");
 
                 let genesis_conf = match from_config {
                     Some(q) => PersistedConfig::read(q)?,
@@ -225,6 +273,12 @@ impl SuiCommand {
                 .save()?;
                 info!("Gateway config file is stored in {:?}.", gateway_path);
 
+                network_config
+                    .generate_fullnode_config(fullnode_db_path)
+                    .persisted(&fullnode_path)
+                    .save()?;
+                info!("Full node config file is stored in {:?}.", fullnode_path);
+
                 let wallet_gateway_config = GatewayConfig {
                     db_folder_path,
                     validator_set: validator_set.to_owned(),
@@ -244,6 +298,108 @@ impl SuiCommand {
 
                 Ok(())
             }
+            SuiCommand::ExportSnapshot {
+                db_path,
+                batch,
+                output,
+            } => {
+                let store = AuthorityStore::open(db_path, None);
+                let snapshot = store.export_snapshot(*batch)?;
+                fs::write(output, bcs::to_bytes(&snapshot)?)?;
+                info!(
+                    "Snapshot of {} objects at sequence number {} written to {:?}, digest: {}",
+                    snapshot.objects.len(),
+                    snapshot.batch.batch.next_sequence_number,
+                    output,
+                    encode_bytes_hex(&snapshot.digest)
+                );
+                Ok(())
+            }
+            SuiCommand::AttestSnapshot {
+                db_path,
+                input,
+                output,
+            } => {
+                let mut snapshot: StateSnapshot = bcs::from_bytes(&fs::read(input)?)?;
+                let state_root = snapshot.signed_state_root()?;
+                let store = AuthorityStore::open(db_path, None);
+                let batch = store
+                    .signed_batch_with_state_root(state_root)?
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "No batch of the authority is signed with the state root {}",
+                            encode_bytes_hex(&state_root)
+                        )
+                    })?;
+                snapshot.add_attestation(batch)?;
+                fs::write(output, bcs::to_bytes(&snapshot)?)?;
+                info!(
+                    "Snapshot attested by {} authorities written to {:?}",
+                    snapshot.attestations.len() + 1,
+                    output
+                );
+                Ok(())
+            }
+            SuiCommand::ImportSnapshot {
+                config,
+                db_path,
+                input,
+            } => {
+                let network_config_path = config
+                    .clone()
+                    .unwrap_or(sui_config_dir()?.join(SUI_NETWORK_CONFIG));
+                let network_config: NetworkConfig = PersistedConfig::read(&network_config_path)
+                    .map_err(|err| {
+                        err.context(format!(
+                            "Cannot open Sui network config file at {:?}",
+                            network_config_path
+                        ))
+                    })?;
+
+                let snapshot: StateSnapshot = bcs::from_bytes(&fs::read(input)?)?;
+                snapshot.verify(&network_config.committee())?;
+
+                let store = AuthorityStore::open(db_path, None);
+                store.import_snapshot(&snapshot)?;
+                info!(
+                    "Imported {} objects at sequence number {} into {:?}",
+                    snapshot.objects.len(),
+                    snapshot.batch.batch.next_sequence_number,
+                    db_path
+                );
+                Ok(())
+            }
+            SuiCommand::Replica { config, db_path } => {
+                let network_config_path = config
+                    .clone()
+                    .unwrap_or(sui_config_dir()?.join(SUI_NETWORK_CONFIG));
+                let network_config: NetworkConfig = PersistedConfig::read(&network_config_path)
+                    .map_err(|err| {
+                        err.context(format!(
+                            "Cannot open Sui network config file at {:?}",
+                            network_config_path
+                        ))
+                    })?;
+
+                let net_config = mysten_network::config::Config::new();
+                let mut authority_clients = BTreeMap::new();
+                for validator in network_config.validator_set() {
+                    let channel = net_config.connect_lazy(validator.network_address())?;
+                    authority_clients
+                        .insert(validator.public_key(), NetworkAuthorityClient::new(channel));
+                }
+
+                let store = Arc::new(ReplicaStore::open(db_path, None));
+                let replica = ReplicaNode::new(
+                    network_config.committee(),
+                    store,
+                    authority_clients,
+                    network_config.validator_configs()[0].genesis(),
+                )
+                .await?;
+                Arc::new(replica).run().await;
+                Ok(())
+            }
             SuiCommand::SignTool {
                 keystore_path,
                 address,
@@ -410,19 +566,38 @@ pub async fn make_authority(
         /* max_pending_transactions */ 1_000_000,
     );
 
+    // Spawn the pruner if the validator is configured to prune old transactions.
+    if let Some(pruning_config) = validator_config.pruning_config() {
+        let state = authority_state.clone();
+        let pruning_config = pruning_config.clone();
+        tokio::task::spawn(async move { state.run_pruner_service(pruning_config).await });
+    }
+
     // If we have network information make authority clients
     // to all authorities in the system.
-    let _active_authority: Option<()> = {
-        let mut authority_clients = BTreeMap::new();
-        let mut config = mysten_network::config::Config::new();
-        config.connect_timeout = Some(Duration::from_secs(5));
-        config.request_timeout = Some(Duration::from_secs(5));
-        for validator in validator_config.committee_config().validator_set() {
-            let channel = config.connect_lazy(validator.network_address()).unwrap();
-            let client = NetworkAuthorityClient::new(channel);
-            authority_clients.insert(validator.public_key(), client);
-        }
+    let mut authority_clients = BTreeMap::new();
+    let mut config = mysten_network::config::Config::new();
+    config.connect_timeout = Some(Duration::from_secs(5));
+    config.request_timeout = Some(Duration::from_secs(5));
+    for validator in validator_config.committee_config().validator_set() {
+        let channel = config.connect_lazy(validator.network_address()).unwrap();
+        let client = NetworkAuthorityClient::new(channel);
+        authority_clients.insert(validator.public_key(), client);
+    }
 
+    // Spawn the epoch change driver if the validator is configured to end the epochs.
+    if let Some(epoch_change_config) = validator_config.epoch_change_config() {
+        let driver = EpochChangeDriver::new(
+            authority_state.clone(),
+            authority_clients.clone(),
+            validator_config.key_pair().copy(),
+            NetworkAuthorityClient::connector(config.clone()),
+        );
+        let epoch_change_config = epoch_change_config.clone();
+        tokio::task::spawn(async move { driver.run(epoch_change_config).await });
+    }
+
+    let _active_authority: Option<()> = {
         let _active_authority = ActiveAuthority::new(authority_state.clone(), authority_clients)?;
 
         // TODO: turn on to start the active part of validators
@@ -1,144 +1,61 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
-use crate::{
-    api::{RpcGatewayServer, SignedTransaction, TransactionBytes},
-    rpc_gateway::responses::{GetObjectInfoResponse, ObjectResponse, SuiTypeTag},
-};
 use anyhow::anyhow;
-use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
-use move_core_types::identifier::Identifier;
+use sui_config::{FullNodeConfig, PersistedConfig};
+use sui_core::{authority_client::NetworkAuthorityClient, full_node::FullNodeState};
+use tokio::task::JoinHandle;
 
-use sui_core::gateway_state::{
-    gateway_responses::{TransactionEffectsResponse, TransactionResponse},
-    GatewayTxSeqNumber,
-};
-use sui_core::sui_json::SuiJsonValue;
-use sui_types::{
-    base_types::{ObjectID, SuiAddress, TransactionDigest},
-    json_schema::Base64,
-    object::ObjectRead,
-};
+use crate::rpc_gateway::RpcGatewayImpl;
 
-pub struct SuiNode {}
-
-impl SuiNode {
-    pub fn new(_config_path: &Path) -> anyhow::Result<Self> {
-        Ok(Self {})
-    }
+/// A full node, serving the JSON-RPC gateway API from the state it replicates from the
+/// validators of the network.
+pub struct SuiNode {
+    rpc: RpcGatewayImpl,
+    replica_handle: JoinHandle<()>,
 }
 
-#[async_trait]
-impl RpcGatewayServer for SuiNode {
-    async fn transfer_coin(
-        &self,
-        _signer: SuiAddress,
-        _object_id: ObjectID,
-        _gas: Option<ObjectID>,
-        _gas_budget: u64,
-        _recipient: SuiAddress,
-    ) -> RpcResult<TransactionBytes> {
-        Err(anyhow!("Sui Node only supports read-only methods").into())
-    }
-
-    async fn publish(
-        &self,
-        _sender: SuiAddress,
-        _compiled_modules: Vec<Base64>,
-        _gas: Option<ObjectID>,
-        _gas_budget: u64,
-    ) -> RpcResult<TransactionBytes> {
-        Err(anyhow!("Sui Node only supports read-only methods").into())
-    }
-
-    async fn split_coin(
-        &self,
-        _signer: SuiAddress,
-        _coin_object_id: ObjectID,
-        _split_amounts: Vec<u64>,
-        _gas: Option<ObjectID>,
-        _gas_budget: u64,
-    ) -> RpcResult<TransactionBytes> {
-        Err(anyhow!("Sui Node only supports read-only methods").into())
-    }
-
-    async fn merge_coin(
-        &self,
-        _signer: SuiAddress,
-        _primary_coin: ObjectID,
-        _coin_to_merge: ObjectID,
-        _gas: Option<ObjectID>,
-        _gas_budget: u64,
-    ) -> RpcResult<TransactionBytes> {
-        Err(anyhow!("Sui Node only supports read-only methods").into())
-    }
-
-    async fn get_owned_objects(&self, _owner: SuiAddress) -> RpcResult<ObjectResponse> {
-        todo!()
-    }
-
-    async fn get_object_info(&self, _object_id: ObjectID) -> RpcResult<ObjectRead> {
-        todo!()
-    }
-
-    async fn get_object_typed_info(
-        &self,
-        _object_id: ObjectID,
-    ) -> RpcResult<GetObjectInfoResponse> {
-        todo!()
-    }
-
-    async fn execute_transaction(
-        &self,
-        _signed_tx: SignedTransaction,
-    ) -> RpcResult<TransactionResponse> {
-        Err(anyhow!("Sui Node only supports read-only methods").into())
-    }
-
-    async fn move_call(
-        &self,
-        _signer: SuiAddress,
-        _package_object_id: ObjectID,
-        _module: Identifier,
-        _function: Identifier,
-        _type_arguments: Vec<SuiTypeTag>,
-        _rpc_arguments: Vec<SuiJsonValue>,
-        _gas: Option<ObjectID>,
-        _gas_budget: u64,
-    ) -> RpcResult<TransactionBytes> {
-        Err(anyhow!("Sui Node only supports read-only methods").into())
-    }
-
-    async fn sync_account_state(&self, _address: SuiAddress) -> RpcResult<()> {
-        todo!()
-    }
-
-    async fn get_total_transaction_number(&self) -> RpcResult<u64> {
-        todo!()
-    }
-
-    async fn get_transactions_in_range(
-        &self,
-        _start: GatewayTxSeqNumber,
-        _end: GatewayTxSeqNumber,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
-        todo!()
-    }
-
-    async fn get_recent_transactions(
-        &self,
-        _count: u64,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
-        todo!()
-    }
-
-    async fn get_transaction(
-        &self,
-        _digest: TransactionDigest,
-    ) -> RpcResult<TransactionEffectsResponse> {
-        todo!()
+impl SuiNode {
+    pub async fn new(config_path: &Path) -> anyhow::Result<Self> {
+        let config: FullNodeConfig = PersistedConfig::read(config_path).map_err(|e| {
+            anyhow!(
+                "Failed to read config file at {:?}: {}. Have you run `sui genesis` first?",
+                config_path,
+                e
+            )
+        })?;
+
+        let net_config = mysten_network::config::Config::new();
+        let mut authority_clients = BTreeMap::new();
+        for validator in config.committee_config().validator_set() {
+            let channel = net_config.connect_lazy(validator.network_address())?;
+            authority_clients.insert(validator.public_key(), NetworkAuthorityClient::new(channel));
+        }
+
+        let state = FullNodeState::new(
+            config.db_path().to_path_buf(),
+            config.committee_config().committee(),
+            authority_clients,
+            config.genesis(),
+        )
+        .await?;
+        let replica_handle = state.spawn_replica();
+        Ok(Self {
+            rpc: RpcGatewayImpl::new_with_gateway(Box::new(state)),
+            replica_handle,
+        })
+    }
+
+    /// Return the JSON-RPC handler, serving the state of this node.
+    pub fn rpc(&self) -> RpcGatewayImpl {
+        self.rpc.clone()
+    }
+
+    /// Wait for the node to stop following the validators, which only happens if there
+    /// are no validators to follow.
+    pub async fn wait_for_completion(self) -> anyhow::Result<()> {
+        Ok(self.replica_handle.await?)
     }
 }
@@ -7,27 +7,28 @@ use std::{
 
 use anyhow::anyhow;
 use move_core_types::identifier::Identifier;
+use move_core_types::parser::parse_type_tag;
 use serde_json::{json, Value};
 use tracing_test::traced_test;
 
 use sui::{
     config::{
-        Config, GatewayConfig, GatewayType, PersistedConfig, WalletConfig, SUI_GATEWAY_CONFIG,
-        SUI_NETWORK_CONFIG, SUI_WALLET_CONFIG,
+        Config, GatewayConfig, GatewayType, PersistedConfig, WalletConfig, SUI_FULLNODE_CONFIG,
+        SUI_GATEWAY_CONFIG, SUI_NETWORK_CONFIG, SUI_WALLET_CONFIG,
     },
     keystore::KeystoreType,
     sui_commands::{SuiCommand, SuiNetwork},
     wallet_commands::{WalletCommandResult, WalletCommands, WalletContext},
 };
 use sui_config::{AccountConfig, GenesisConfig, NetworkConfig, ObjectConfig};
-use sui_core::gateway_state::gateway_responses::SwitchResponse;
+use sui_core::gateway_state::gateway_responses::{Balance, SwitchResponse};
 use sui_core::sui_json::SuiJsonValue;
 use sui_types::{
     base_types::{ObjectID, SequenceNumber, SuiAddress},
     crypto::get_key_pair,
-    gas_coin::GasCoin,
-    messages::TransactionEffects,
-    object::{Object, ObjectRead, GAS_VALUE_FOR_TESTING},
+    gas_coin::{GasCoin, GAS},
+    messages::{ExecutionStatus, TransactionEffects},
+    object::{Object, ObjectRead, Owner, GAS_VALUE_FOR_TESTING},
 };
 
 use test_utils::network::start_test_network;
@@ -81,9 +82,10 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
         .flat_map(|r| r.map(|file| file.file_name().to_str().unwrap().to_owned()))
         .collect::<Vec<_>>();
 
-    assert_eq!(4, files.len());
+    assert_eq!(5, files.len());
     assert!(files.contains(&SUI_WALLET_CONFIG.to_string()));
     assert!(files.contains(&SUI_GATEWAY_CONFIG.to_string()));
+    assert!(files.contains(&SUI_FULLNODE_CONFIG.to_string()));
     assert!(files.contains(&SUI_NETWORK_CONFIG.to_string()));
     assert!(files.contains(&"wallet.key".to_string()));
 
@@ -250,7 +252,8 @@ async fn airdrop_call_move_and_get_created_object(
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        dry_run: false,
     }
     .execute(context)
     .await?;
@@ -279,6 +282,9 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
     // Print objects owned by `address`
     WalletCommands::Objects {
         address: Some(address),
+        object_type: None,
+        cursor: None,
+        limit: None,
     }
     .execute(&mut context)
     .await?
@@ -295,6 +301,69 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[traced_test]
+#[tokio::test]
+async fn test_objects_command_with_type_filter_and_pagination() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let list = |object_type: &str, cursor, limit| WalletCommands::Objects {
+        address: Some(address),
+        object_type: Some(parse_type_tag(object_type).unwrap()),
+        cursor,
+        limit,
+    };
+    let page = |result| match result {
+        WalletCommandResult::Objects(page) => page,
+        _ => panic!("WalletCommands::Objects returns wrong type {}", result),
+    };
+
+    // All the genesis objects of the address are gas coins.
+    let coin_type = GasCoin::type_();
+    let coins = page(
+        list("0x2::Coin::Coin", None, None)
+            .execute(&mut context)
+            .await?,
+    );
+    assert_eq!(coins.data.len(), 5);
+    assert_eq!(coins.next_cursor, None);
+    for coin in &coins.data {
+        assert_eq!(coin.object_type.as_ref(), Some(&coin_type));
+        assert_eq!(coin.owner, Some(Owner::AddressOwner(address)));
+    }
+    let gas_coins = page(
+        list("0x2::Coin::Coin<0x2::SUI::SUI>", None, None)
+            .execute(&mut context)
+            .await?,
+    );
+    assert_eq!(gas_coins.data, coins.data);
+    let objects = page(
+        list("0x2::ObjectBasics::Object", None, None)
+            .execute(&mut context)
+            .await?,
+    );
+    assert!(objects.data.is_empty());
+
+    // Walk through the coins by pages of 2.
+    let mut cursor = None;
+    let mut paged_coins = vec![];
+    loop {
+        let result = page(
+            list("0x2::Coin::Coin", cursor, Some(2))
+                .execute(&mut context)
+                .await?,
+        );
+        assert!(result.data.len() <= 2);
+        paged_coins.extend(result.data);
+        cursor = result.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(paged_coins, coins.data);
+
+    network.kill().await?;
+    Ok(())
+}
+
 #[traced_test]
 #[tokio::test]
 async fn test_create_example_nft_command() -> Result<(), anyhow::Error> {
@@ -365,6 +434,9 @@ async fn test_custom_genesis() -> Result<(), anyhow::Error> {
     // Print objects owned by `address`
     WalletCommands::Objects {
         address: Some(address),
+        object_type: None,
+        cursor: None,
+        limit: None,
     }
     .execute(&mut context)
     .await?
@@ -448,6 +520,101 @@ async fn test_object_info_get_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_balance_command() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let sui_balance = Balance {
+        coin_type: GAS::type_tag(),
+        coin_object_count: 5,
+        total_balance: 5 * GAS_VALUE_FOR_TESTING as u128,
+    };
+
+    let balances = WalletCommands::Balance {
+        address: Some(address),
+        coin_type: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let balances = if let WalletCommandResult::Balance(balances) = balances {
+        balances
+    } else {
+        panic!("Command failed")
+    };
+    assert_eq!(balances, vec![sui_balance.clone()]);
+
+    // Query a single coin type.
+    let balance = context
+        .gateway
+        .get_balance(address, GAS::type_tag())
+        .await?;
+    assert_eq!(balance, sui_balance);
+    let other_type = parse_type_tag("0x2::ObjectBasics::Object")?;
+    let balance = context
+        .gateway
+        .get_balance(address, other_type.clone())
+        .await?;
+    assert_eq!(
+        balance,
+        Balance {
+            coin_type: other_type,
+            coin_object_count: 0,
+            total_balance: 0,
+        }
+    );
+
+    network.kill().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transactions_command() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.config.accounts.get(1).cloned().unwrap();
+
+    let object_refs = context.gateway.get_owned_objects(address).await?;
+    let gas = object_refs.first().unwrap().0;
+    let object_to_send = object_refs.get(1).unwrap().0;
+
+    let digest = match (WalletCommands::Transfer {
+        to: recipient,
+        coin_object_id: object_to_send,
+        gas: Some(gas),
+        gas_budget: Some(50000),
+    }
+    .execute(&mut context)
+    .await?)
+    {
+        WalletCommandResult::Transfer(_, cert, _) => *cert.digest(),
+        _ => panic!("Command failed"),
+    };
+
+    // The transfer shows up in the transactions of the sender, the recipient and the coin.
+    for (address, object) in [
+        (Some(address), None),
+        (Some(recipient), None),
+        (None, Some(object_to_send)),
+    ] {
+        let page = WalletCommands::Transactions {
+            address,
+            object,
+            cursor: None,
+            limit: None,
+        }
+        .execute(&mut context)
+        .await?;
+        let page = if let WalletCommandResult::Transactions(page) = page {
+            page
+        } else {
+            panic!("Command failed")
+        };
+        assert!(page.data.iter().any(|(_, d)| *d == digest));
+        assert_eq!(page.next_cursor, None);
+    }
+
+    network.kill().await?;
+    Ok(())
+}
+
 #[traced_test]
 #[tokio::test]
 async fn test_gas_command() -> Result<(), anyhow::Error> {
@@ -498,7 +665,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
         to: recipient,
         coin_object_id: object_to_send,
         gas: Some(object_id),
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(&mut context)
     .await?;
@@ -588,14 +755,18 @@ async fn get_move_objects(
     // Fetch objects owned by `address`
     let objects_result = WalletCommands::Objects {
         address: Some(address),
+        object_type: None,
+        cursor: None,
+        limit: None,
     }
     .execute(context)
     .await?;
 
     match objects_result {
-        WalletCommandResult::Objects(object_refs) => {
+        WalletCommandResult::Objects(page) => {
             let mut objs = vec![];
-            for (id, ..) in object_refs {
+            for object in page.data {
+                let id = object.object_ref.0;
                 objs.push((id, get_move_object(context, id).await?));
             }
             Ok(objs)
@@ -642,6 +813,9 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
     // Print objects owned by `address1`
     WalletCommands::Objects {
         address: Some(address1),
+        object_type: None,
+        cursor: None,
+        limit: None,
     }
     .execute(&mut context)
     .await?
@@ -679,7 +853,8 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args,
         gas: None,
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        dry_run: false,
     }
     .execute(&mut context)
     .await?;
@@ -726,7 +901,8 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        dry_run: false,
     }
     .execute(&mut context)
     .await;
@@ -754,7 +930,8 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        dry_run: false,
     }
     .execute(&mut context)
     .await;
@@ -781,7 +958,8 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        dry_run: false,
     }
     .execute(&mut context)
     .await?;
@@ -796,6 +974,113 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[traced_test]
+#[tokio::test]
+async fn test_move_call_dry_run() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context.gateway.get_owned_objects(address).await?;
+    let gas = object_refs.first().unwrap().0;
+
+    let args_json = json!([123u8, format!("0x{:02x}", address)]);
+    let mut args = vec![];
+    for a in args_json.as_array().unwrap() {
+        args.push(SuiJsonValue::new(a.clone()).unwrap());
+    }
+
+    let resp = WalletCommands::Call {
+        package: ObjectID::from_hex_literal("0x2").unwrap(),
+        module: Identifier::new("ObjectBasics").unwrap(),
+        function: Identifier::new("create").unwrap(),
+        type_args: vec![],
+        args,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        dry_run: true,
+    }
+    .execute(&mut context)
+    .await?;
+    resp.print(true);
+
+    // The effects show the object that would be created, and the gas it would cost.
+    let response = if let WalletCommandResult::DryRun(response) = resp {
+        response
+    } else {
+        panic!("Expected a dry run result");
+    };
+    assert!(matches!(
+        response.effects.status,
+        ExecutionStatus::Success { .. }
+    ));
+    assert_eq!(response.effects.created.len(), 1);
+    assert!(response.gas_cost_summary.gas_used() > 0);
+    assert!(logs_contain("Gas cost:"));
+
+    // Nothing was executed: the gas object is unchanged and no object was created.
+    assert_eq!(
+        context.gateway.get_owned_objects(address).await?,
+        object_refs
+    );
+    let ((created_id, _, _), _) = response.effects.created[0];
+    assert!(matches!(
+        context.gateway.get_object_info(created_id).await?,
+        ObjectRead::NotExists(_)
+    ));
+
+    network.kill().await?;
+    Ok(())
+}
+
+#[traced_test]
+#[tokio::test]
+async fn test_batch_command() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context.gateway.get_owned_objects(address).await?;
+    let gas = object_refs[0].0;
+    let coin = object_refs[1].0;
+    let (recipient, _) = get_key_pair();
+    let package = ObjectID::from_hex_literal("0x2").unwrap();
+
+    // A transfer and a Move call, in YAML.
+    let batch = format!(
+        "- TransferCoin:
+    recipient: \"{recipient}\"
+    object_id: \"{coin}\"
+- MoveCall:
+    package_object_id: \"{package}\"
+    module: ObjectBasics
+    function: create
+    type_arguments: []
+    arguments: [123, \"0x{address:02x}\"]
+"
+    );
+    let batch_dir = tempfile::tempdir()?;
+    let path = batch_dir.path().join("batch.yaml");
+    std::fs::write(&path, batch)?;
+
+    let resp = WalletCommands::Batch {
+        path,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+    }
+    .execute(&mut context)
+    .await?;
+    resp.print(true);
+
+    let effects = if let WalletCommandResult::Batch(_, effects) = resp {
+        effects
+    } else {
+        panic!("Expected a batch result");
+    };
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.created.len(), 1);
+    let (transferred, owner) = effects.mutated_excluding_gas().next().unwrap();
+    assert_eq!(transferred.0, coin);
+    assert_eq!(*owner, Owner::AddressOwner(recipient));
+
+    network.kill().await?;
+    Ok(())
+}
+
 #[allow(clippy::assertions_on_constants)]
 #[traced_test]
 #[tokio::test]
@@ -814,7 +1099,7 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     let resp = WalletCommands::Publish {
         path,
         gas: Some(gas_obj_id),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
     }
     .execute(&mut context)
     .await?;
@@ -879,7 +1164,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
         gas: Some(gas_obj_id),
         to: recipient,
         coin_object_id: obj_id,
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(&mut context)
     .await?;
@@ -973,7 +1258,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
         gas: None,
         to: recipient,
         coin_object_id: obj_id,
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(&mut context)
     .await?;
@@ -1041,12 +1326,20 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     .await?;
 
     // Run a command with address omitted
-    let os = WalletCommands::Objects { address: None }
-        .execute(&mut context)
-        .await?;
+    let os = WalletCommands::Objects {
+        address: None,
+        object_type: None,
+        cursor: None,
+        limit: None,
+    }
+    .execute(&mut context)
+    .await?;
 
-    let mut cmd_objs = if let WalletCommandResult::Objects(v) = os {
-        v
+    let mut cmd_objs = if let WalletCommandResult::Objects(page) = os {
+        page.data
+            .into_iter()
+            .map(|object| object.object_ref)
+            .collect::<Vec<_>>()
     } else {
         panic!("Command failed")
     };
@@ -1201,9 +1494,9 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
     // Test with gas specified
     let resp = WalletCommands::MergeCoin {
         primary_coin,
-        coin_to_merge,
+        coins_to_merge: vec![coin_to_merge],
         gas: Some(gas),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
     }
     .execute(&mut context)
     .await?;
@@ -1229,17 +1522,19 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
     let object_refs = context.gateway.get_owned_objects(address).await?;
 
     let primary_coin = object_refs.get(1).unwrap().0;
-    let coin_to_merge = object_refs.get(2).unwrap().0;
+    let coins_to_merge = vec![object_refs.get(2).unwrap().0, object_refs.get(3).unwrap().0];
 
-    let total_value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap())
-        + get_gas_value(&get_object(coin_to_merge, &mut context).await.unwrap());
+    let mut total_value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap());
+    for coin_to_merge in &coins_to_merge {
+        total_value += get_gas_value(&get_object(*coin_to_merge, &mut context).await.unwrap());
+    }
 
-    // Test with no gas specified
+    // Test merging several coins with no gas specified
     let resp = WalletCommands::MergeCoin {
         primary_coin,
-        coin_to_merge,
+        coins_to_merge: coins_to_merge.clone(),
         gas: None,
-        gas_budget: 1000,
+        gas_budget: Some(1000),
     }
     .execute(&mut context)
     .await?;
@@ -1253,8 +1548,47 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
     // Check total value is expected
     assert_eq!(get_gas_value(&g.updated_coin), total_value);
 
-    // Check that old coin is deleted
-    assert_eq!(get_object(coin_to_merge, &mut context).await, None);
+    // Check that old coins are deleted
+    for coin_to_merge in coins_to_merge {
+        assert_eq!(get_object(coin_to_merge, &mut context).await, None);
+    }
+
+    network.kill().await?;
+    Ok(())
+}
+
+#[traced_test]
+#[tokio::test]
+async fn test_merge_all_coins() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+
+    let object_refs = context.gateway.get_owned_objects(address).await?;
+    let mut total_value = 0;
+    for (id, _, _) in &object_refs {
+        total_value += get_gas_value(&get_object(*id, &mut context).await.unwrap());
+    }
+    let gas = object_refs.first().unwrap().0;
+    let gas_value = get_gas_value(&get_object(gas, &mut context).await.unwrap());
+
+    let resp = WalletCommands::MergeAllCoins {
+        address: None,
+        coin_type: GAS::type_tag(),
+        gas: Some(gas),
+        gas_budget: Some(1000),
+    }
+    .execute(&mut context)
+    .await?;
+
+    let g = if let WalletCommandResult::MergeCoin(r) = resp {
+        r
+    } else {
+        panic!("Command failed")
+    };
+
+    // All coins but the gas payment are merged into one
+    assert_eq!(get_gas_value(&g.updated_coin), total_value - gas_value);
+    let object_refs = context.gateway.get_owned_objects(address).await?;
+    assert_eq!(object_refs.len(), 2);
 
     network.kill().await?;
     Ok(())
@@ -1276,7 +1610,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test with gas specified
     let resp = WalletCommands::SplitCoin {
         gas: Some(gas),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
         coin_id: coin,
         amounts: vec![1000, 10],
     }
@@ -1314,7 +1648,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test with no gas specified
     let resp = WalletCommands::SplitCoin {
         gas: None,
-        gas_budget: 1000,
+        gas_budget: Some(1000),
         coin_id: coin,
         amounts: vec![1000, 10],
     }
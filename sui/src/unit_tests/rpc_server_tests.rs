@@ -2,25 +2,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
+    collections::BTreeMap,
     net::SocketAddr,
     path::{Path, PathBuf},
     str::FromStr,
 };
 
+use futures::StreamExt;
 use jsonrpsee::{
     http_client::{HttpClient, HttpClientBuilder},
     http_server::{HttpServerBuilder, HttpServerHandle},
+    ws_client::WsClientBuilder,
+    ws_server::{WsServerBuilder, WsServerHandle},
 };
 use move_core_types::identifier::Identifier;
 
 use sui::{
-    api::{RpcGatewayClient, RpcGatewayServer, SignedTransaction, TransactionBytes},
+    api::{
+        RpcGatewayClient, RpcGatewayServer, RpcGatewaySubscriptionsClient,
+        RpcGatewaySubscriptionsServer, SignedTransaction, TransactionBytes,
+    },
     config::{PersistedConfig, WalletConfig, SUI_GATEWAY_CONFIG, SUI_WALLET_CONFIG},
     keystore::{Keystore, SuiKeystore},
     rpc_gateway::{responses::ObjectResponse, RpcGatewayImpl},
     sui_commands::SuiNetwork,
 };
-use sui_core::gateway_state::gateway_responses::{TransactionEffectsResponse, TransactionResponse};
+use sui_core::gateway_state::gateway_responses::{
+    ObjectChangeKind, TransactionEffectsResponse, TransactionResponse,
+};
 use sui_core::gateway_state::GatewayTxSeqNumber;
 use sui_core::sui_json::SuiJsonValue;
 use sui_framework::build_move_package_to_bytes;
@@ -69,7 +78,7 @@ async fn test_transfer_coin() -> Result<(), anyhow::Error> {
             *address,
             objects.first().unwrap().0,
             Some(objects.last().unwrap().0),
-            1000,
+            Some(1000),
             *address,
         )
         .await?;
@@ -111,7 +120,7 @@ async fn test_publish() -> Result<(), anyhow::Error> {
     .collect::<Vec<_>>();
 
     let tx_data: TransactionBytes = http_client
-        .publish(*address, compiled_modules, Some(gas.0), 10000)
+        .publish(*address, compiled_modules, Some(gas.0), Some(10000))
         .await?;
 
     let keystore = SuiKeystore::load_or_create(&test_network.working_dir.join("wallet.key"))?;
@@ -159,7 +168,7 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
             vec![],
             json_args,
             Some(gas.0),
-            1000,
+            Some(1000),
         )
         .await?;
 
@@ -218,7 +227,7 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
     let mut tx_responses = Vec::new();
     for (id, _, _) in &objects[..objects.len() - 1] {
         let tx_data: TransactionBytes = http_client
-            .transfer_coin(*address, *id, Some(*gas_id), 1000, *address)
+            .transfer_coin(*address, *id, Some(*gas_id), Some(1000), *address)
             .await?;
 
         let keystore = SuiKeystore::load_or_create(&test_network.working_dir.join("wallet.key"))?;
@@ -263,6 +272,68 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_subscriptions() -> Result<(), anyhow::Error> {
+    let working_dir = tempfile::tempdir()?.path().to_path_buf();
+    let _network = start_test_network(&working_dir, None).await?;
+    let (server_addr, _ws_server_handle) =
+        start_ws_rpc_gateway(&working_dir.join(SUI_GATEWAY_CONFIG)).await?;
+    let wallet_conf: WalletConfig = PersistedConfig::read(&working_dir.join(SUI_WALLET_CONFIG))?;
+    let ws_client = WsClientBuilder::default()
+        .build(format!("ws://{}", server_addr))
+        .await?;
+    let sender = wallet_conf.accounts[0];
+    let recipient = wallet_conf.accounts[1];
+
+    ws_client.sync_account_state(sender).await?;
+    let result: ObjectResponse = ws_client.get_owned_objects(sender).await?;
+    let objects = result
+        .objects
+        .into_iter()
+        .map(|o| o.to_object_ref())
+        .collect::<Result<Vec<_>, _>>()?;
+    let (coin_id, _, _) = objects.first().unwrap();
+    let (gas_id, _, _) = objects.last().unwrap();
+
+    let mut transactions = ws_client.subscribe_transactions().await?;
+    let mut sender_changes = ws_client.subscribe_object_changes(sender).await?;
+    let mut recipient_changes = ws_client.subscribe_object_changes(recipient).await?;
+
+    let tx_data: TransactionBytes = ws_client
+        .transfer_coin(sender, *coin_id, Some(*gas_id), Some(1000), recipient)
+        .await?;
+    let keystore = SuiKeystore::load_or_create(&working_dir.join("wallet.key"))?;
+    let signature = keystore.sign(&sender, &tx_data.tx_bytes)?;
+    let (cert, _) = ws_client
+        .execute_transaction(SignedTransaction::new(tx_data.tx_bytes, signature))
+        .await?
+        .to_effect_response()?;
+    let digest = *cert.digest();
+
+    let (seq, tx_digest) = transactions.next().await.unwrap()?;
+    assert_eq!((seq, tx_digest), (0, digest));
+
+    // The sender sees its coin leave and its gas coin being mutated, in any order.
+    let mut kinds = BTreeMap::new();
+    for _ in 0..2 {
+        let change = sender_changes.next().await.unwrap()?;
+        assert_eq!(change.tx_digest, digest);
+        kinds.insert(change.object_ref.0, change.kind);
+    }
+    assert_eq!(
+        kinds,
+        BTreeMap::from([
+            (*coin_id, ObjectChangeKind::Sent),
+            (*gas_id, ObjectChangeKind::Mutated)
+        ])
+    );
+
+    let change = recipient_changes.next().await.unwrap()?;
+    assert_eq!(change.object_ref.0, *coin_id);
+    assert_eq!(change.kind, ObjectChangeKind::Received);
+    Ok(())
+}
+
 async fn setup_test_network() -> Result<TestNetwork, anyhow::Error> {
     let working_dir = tempfile::tempdir()?.path().to_path_buf();
     let _network = start_test_network(&working_dir, None).await?;
@@ -292,6 +363,18 @@ async fn start_rpc_gateway(
 ) -> Result<(SocketAddr, HttpServerHandle), anyhow::Error> {
     let server = HttpServerBuilder::default().build("127.0.0.1:0").await?;
     let addr = server.local_addr()?;
-    let handle = server.start(RpcGatewayImpl::new(config_path)?.into_rpc())?;
+    let handle = server.start(RpcGatewayImpl::new(config_path).await?.into_rpc())?;
+    Ok((addr, handle))
+}
+
+async fn start_ws_rpc_gateway(
+    config_path: &Path,
+) -> Result<(SocketAddr, WsServerHandle), anyhow::Error> {
+    let server = WsServerBuilder::default().build("127.0.0.1:0").await?;
+    let addr = server.local_addr()?;
+    let rpc_gateway = RpcGatewayImpl::new(config_path).await?;
+    let mut module = rpc_gateway.clone().into_rpc();
+    module.merge(rpc_gateway.subscriptions().into_rpc())?;
+    let handle = server.start(module)?;
     Ok((addr, handle))
 }
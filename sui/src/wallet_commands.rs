@@ -5,7 +5,7 @@ use core::fmt;
 use std::{
     collections::BTreeSet,
     fmt::{Debug, Display, Formatter, Write},
-    path::Path,
+    path::{Path, PathBuf},
     sync::{Arc, RwLock},
     time::Instant,
 };
@@ -19,20 +19,28 @@ use serde_json::json;
 use tracing::info;
 
 use sui_core::gateway_state::{
-    gateway_responses::{MergeCoinResponse, PublishResponse, SplitCoinResponse, SwitchResponse},
-    GatewayClient,
+    gateway_requests::{MoveCallParams, SingleTransactionParams},
+    gateway_responses::{
+        Balance, MergeCoinResponse, OwnedObjectPage, PublishResponse, SplitCoinResponse,
+        SwitchResponse, TransactionPage,
+    },
+    GatewayClient, GatewayTxSeqNumber, MAX_OBJECT_PAGE_SIZE, MAX_TX_RANGE_SIZE,
 };
 use sui_core::sui_json::SuiJsonValue;
 use sui_framework::build_move_package_to_bytes;
 use sui_types::{
-    base_types::{ObjectID, ObjectRef, SuiAddress},
+    base_types::{ObjectID, SuiAddress},
     gas_coin::GasCoin,
-    messages::{CertifiedTransaction, ExecutionStatus, Transaction, TransactionEffects},
+    messages::{
+        CertifiedTransaction, DryRunTransactionResponse, ExecutionStatus, Transaction,
+        TransactionData, TransactionEffects,
+    },
     object::{Object, ObjectRead, ObjectRead::Exists},
     SUI_FRAMEWORK_ADDRESS,
 };
 
 use crate::{
+    api::SingleTransactionRequestParams,
     config::{Config, GatewayType, PersistedConfig, WalletConfig},
     keystore::Keystore,
 };
@@ -92,8 +100,9 @@ pub enum WalletCommands {
         gas: Option<ObjectID>,
 
         /// Gas budget for running module initializers
+        /// If not provided, the budget is estimated by simulating the transaction
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Call Move function
@@ -126,8 +135,29 @@ pub enum WalletCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
+        /// If not provided, the budget is estimated by simulating the transaction
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        /// Only simulate the call against the current state, and show its effects
+        /// and gas cost without executing it
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Execute a batch of coin transfers and Move calls atomically, in a single transaction
+    #[clap(name = "batch")]
+    Batch {
+        /// Path to a JSON or YAML file with the list of transactions of the batch
+        #[clap(long)]
+        path: PathBuf,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for the batch
+        /// If not provided, the budget is estimated by simulating the transaction
+        #[clap(long)]
+        gas_budget: Option<u64>,
     },
 
     /// Transfer coin object
@@ -147,8 +177,9 @@ pub enum WalletCommands {
         gas: Option<ObjectID>,
 
         /// Gas budget for this transfer
+        /// If not provided, the budget is estimated by simulating the transaction
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
     /// Synchronize client state with authorities.
     #[clap(name = "sync")]
@@ -171,6 +202,17 @@ pub enum WalletCommands {
         /// Address owning the objects
         #[clap(long)]
         address: Option<SuiAddress>,
+        /// Only list the objects of this Move type, e.g. 0x2::Coin::Coin<0x2::SUI::SUI>.
+        /// A type without type parameters, e.g. 0x2::Coin::Coin, matches all its instantiations
+        #[clap(long, parse(try_from_str = parse_type_tag))]
+        object_type: Option<TypeTag>,
+        /// ID of the first object to list, from the previous page
+        #[clap(long)]
+        cursor: Option<ObjectID>,
+        /// Maximum number of objects to list
+        /// If not provided, all the objects are listed
+        #[clap(long)]
+        limit: Option<u64>,
     },
 
     /// Obtain all gas objects owned by the address.
@@ -181,6 +223,36 @@ pub enum WalletCommands {
         address: Option<SuiAddress>,
     },
 
+    /// Obtain the number and total value of the coins owned by the address, by coin type.
+    #[clap(name = "balance")]
+    Balance {
+        /// Address owning the coins
+        #[clap(long)]
+        address: Option<SuiAddress>,
+        /// Type parameter of the coins, e.g. 0x2::SUI::SUI
+        /// If not provided, the balances in all coin types are shown
+        #[clap(long, parse(try_from_str = parse_type_tag))]
+        coin_type: Option<TypeTag>,
+    },
+
+    /// Obtain the transactions sent by or to the address, or the transactions that touched an object.
+    #[clap(name = "transactions")]
+    Transactions {
+        /// Address sending or receiving the transactions
+        #[clap(long, conflicts_with = "object")]
+        address: Option<SuiAddress>,
+        /// Object touched by the transactions, in 20 bytes Hex string
+        #[clap(long)]
+        object: Option<ObjectID>,
+        /// Sequence number of the first transaction to list, from the previous page
+        #[clap(long)]
+        cursor: Option<GatewayTxSeqNumber>,
+        /// Maximum number of transactions to list
+        /// If not provided, all the transactions are listed
+        #[clap(long)]
+        limit: Option<u64>,
+    },
+
     /// Split a coin object into multiple coins.
     SplitCoin {
         /// Coin to Split, in 20 bytes Hex string
@@ -194,25 +266,45 @@ pub enum WalletCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
+        /// If not provided, the budget is estimated by simulating the transaction
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
-    /// Merge two coin objects into one coin
+    /// Merge coin objects into one coin, in a single transaction
     MergeCoin {
         /// Coin to merge into, in 20 bytes Hex string
         #[clap(long)]
         primary_coin: ObjectID,
-        /// Coin to be merged, in 20 bytes Hex string
+        /// Coins to be merged, in 20 bytes Hex string
+        #[clap(long, multiple_occurrences = false, multiple_values = true)]
+        coins_to_merge: Vec<ObjectID>,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
-        coin_to_merge: ObjectID,
+        gas: Option<ObjectID>,
+        /// Gas budget for this call
+        /// If not provided, the budget is estimated by simulating the transaction
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Merge all coins of an address with the given type into the one with the largest balance
+    MergeAllCoins {
+        /// Address owning the coins
+        #[clap(long)]
+        address: Option<SuiAddress>,
+        /// Type parameter of the coins to merge
+        #[clap(long, parse(try_from_str = parse_type_tag), default_value = "0x2::SUI::SUI")]
+        coin_type: TypeTag,
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
+        /// If not provided, the budget is estimated by simulating the transaction
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Create an example NFT
@@ -260,6 +352,9 @@ impl WalletCommands {
                 let sender = sender.unwrap_or(context.active_address()?);
 
                 let compiled_modules = build_move_package_to_bytes(Path::new(path), false)?;
+                context
+                    .merge_gas_coins(sender, gas, gas_budget, vec![])
+                    .await?;
                 let data = context
                     .gateway
                     .publish(sender, compiled_modules, *gas, *gas_budget)
@@ -291,12 +386,62 @@ impl WalletCommands {
                 gas,
                 gas_budget,
                 args,
+                dry_run,
             } => {
-                let (cert, effects) = call_move(
-                    package, module, function, type_args, gas, gas_budget, args, context,
-                )
-                .await?;
-                WalletCommandResult::Call(cert, effects)
+                if *dry_run {
+                    let (_, data) = move_call_data(
+                        package, module, function, type_args, gas, gas_budget, args, context,
+                    )
+                    .await?;
+                    let response = context.gateway.dry_run_transaction(data).await?;
+                    WalletCommandResult::DryRun(response)
+                } else {
+                    let (cert, effects) = call_move(
+                        package, module, function, type_args, gas, gas_budget, args, context,
+                    )
+                    .await?;
+                    WalletCommandResult::Call(cert, effects)
+                }
+            }
+
+            WalletCommands::Batch {
+                path,
+                gas,
+                gas_budget,
+            } => {
+                let single_transaction_params = read_batch_transaction_params(path)?;
+                let gas_owner = context.try_get_object_owner(gas).await?;
+                let sender = gas_owner.unwrap_or(context.active_address()?);
+                let mut input_objects = Vec::new();
+                for params in &single_transaction_params {
+                    input_objects.extend(context.input_object_ids(params).await?);
+                }
+                context
+                    .merge_gas_coins(sender, gas, gas_budget, input_objects)
+                    .await?;
+
+                let data = context
+                    .gateway
+                    .batch_transaction(sender, single_transaction_params, *gas, *gas_budget)
+                    .await?;
+                let signature = context
+                    .keystore
+                    .read()
+                    .unwrap()
+                    .sign(&sender, &data.to_bytes())?;
+                let (cert, effects) = context
+                    .gateway
+                    .execute_transaction(Transaction::new(data, signature))
+                    .await?
+                    .to_effect_response()?;
+
+                if matches!(effects.status, ExecutionStatus::Failure { .. }) {
+                    return Err(anyhow!(
+                        "Error executing batch transaction: {:#?}",
+                        effects.status
+                    ));
+                }
+                WalletCommandResult::Batch(cert, effects)
             }
 
             WalletCommands::Transfer {
@@ -306,6 +451,9 @@ impl WalletCommands {
                 gas_budget,
             } => {
                 let from = context.get_object_owner(object_id).await?;
+                context
+                    .merge_gas_coins(from, gas, gas_budget, vec![*object_id])
+                    .await?;
                 let time_start = Instant::now();
 
                 let data = context
@@ -335,12 +483,42 @@ impl WalletCommands {
                 WalletCommandResult::Addresses(context.config.accounts.clone())
             }
 
-            WalletCommands::Objects { address } => {
+            WalletCommands::Objects {
+                address,
+                object_type,
+                cursor,
+                limit,
+            } => {
                 let address = match address {
                     Some(a) => *a,
                     None => context.active_address()?,
                 };
-                WalletCommandResult::Objects(context.gateway.get_owned_objects(address).await?)
+                let object_type = match object_type {
+                    Some(TypeTag::Struct(tag)) => Some(tag.clone()),
+                    Some(tag) => return Err(anyhow!("{} is not a Move struct type", tag)),
+                    None => None,
+                };
+                let page_size = limit.unwrap_or(MAX_OBJECT_PAGE_SIZE);
+                let mut page = context
+                    .gateway
+                    .get_owned_objects_page(address, object_type.clone(), *cursor, page_size, true)
+                    .await?;
+                // Without a limit, list all the objects.
+                while limit.is_none() && page.next_cursor.is_some() {
+                    let next_page = context
+                        .gateway
+                        .get_owned_objects_page(
+                            address,
+                            object_type.clone(),
+                            page.next_cursor,
+                            page_size,
+                            true,
+                        )
+                        .await?;
+                    page.data.extend(next_page.data);
+                    page.next_cursor = next_page.next_cursor;
+                }
+                WalletCommandResult::Objects(page)
             }
 
             WalletCommands::SyncClientState { address } => {
@@ -371,6 +549,61 @@ impl WalletCommands {
                     .collect();
                 WalletCommandResult::Gas(coins)
             }
+            WalletCommands::Balance { address, coin_type } => {
+                let address = match address {
+                    Some(a) => *a,
+                    None => context.active_address()?,
+                };
+                let balances = match coin_type {
+                    Some(coin_type) => vec![
+                        context
+                            .gateway
+                            .get_balance(address, coin_type.clone())
+                            .await?,
+                    ],
+                    None => context.gateway.get_all_balances(address).await?,
+                };
+                WalletCommandResult::Balance(balances)
+            }
+            WalletCommands::Transactions {
+                address,
+                object,
+                cursor,
+                limit,
+            } => {
+                let page_size = limit.unwrap_or(MAX_TX_RANGE_SIZE);
+                let mut page = TransactionPage {
+                    data: vec![],
+                    next_cursor: *cursor,
+                };
+                // Without a limit, list all the transactions.
+                loop {
+                    let next_page = match object {
+                        Some(object_id) => {
+                            context
+                                .gateway
+                                .get_transactions_by_object(*object_id, page.next_cursor, page_size)
+                                .await?
+                        }
+                        None => {
+                            let address = match address {
+                                Some(a) => *a,
+                                None => context.active_address()?,
+                            };
+                            context
+                                .gateway
+                                .get_transactions_by_address(address, page.next_cursor, page_size)
+                                .await?
+                        }
+                    };
+                    page.data.extend(next_page.data);
+                    page.next_cursor = next_page.next_cursor;
+                    if limit.is_some() || page.next_cursor.is_none() {
+                        break;
+                    }
+                }
+                WalletCommandResult::Transactions(page)
+            }
             WalletCommands::SplitCoin {
                 coin_id,
                 amounts,
@@ -378,6 +611,9 @@ impl WalletCommands {
                 gas_budget,
             } => {
                 let signer = context.get_object_owner(coin_id).await?;
+                context
+                    .merge_gas_coins(signer, gas, gas_budget, vec![*coin_id])
+                    .await?;
                 let data = context
                     .gateway
                     .split_coin(signer, *coin_id, amounts.clone(), *gas, *gas_budget)
@@ -396,14 +632,53 @@ impl WalletCommands {
             }
             WalletCommands::MergeCoin {
                 primary_coin,
-                coin_to_merge,
+                coins_to_merge,
                 gas,
                 gas_budget,
             } => {
                 let signer = context.get_object_owner(primary_coin).await?;
+                let input_coins = std::iter::once(*primary_coin)
+                    .chain(coins_to_merge.iter().copied())
+                    .collect();
+                context
+                    .merge_gas_coins(signer, gas, gas_budget, input_coins)
+                    .await?;
                 let data = context
                     .gateway
-                    .merge_coins(signer, *primary_coin, *coin_to_merge, *gas, *gas_budget)
+                    .merge_coins(
+                        signer,
+                        *primary_coin,
+                        coins_to_merge.clone(),
+                        *gas,
+                        *gas_budget,
+                    )
+                    .await?;
+                let signature = context
+                    .keystore
+                    .read()
+                    .unwrap()
+                    .sign(&signer, &data.to_bytes())?;
+                let response = context
+                    .gateway
+                    .execute_transaction(Transaction::new(data, signature))
+                    .await?
+                    .to_merge_coin_response()?;
+
+                WalletCommandResult::MergeCoin(response)
+            }
+            WalletCommands::MergeAllCoins {
+                address,
+                coin_type,
+                gas,
+                gas_budget,
+            } => {
+                let signer = match address {
+                    Some(a) => *a,
+                    None => context.active_address()?,
+                };
+                let data = context
+                    .gateway
+                    .merge_all_coins(signer, coin_type.clone(), *gas, *gas_budget)
                     .await?;
                 let signature = context
                     .keystore
@@ -469,7 +744,7 @@ impl WalletCommands {
                     &Identifier::new("mint").unwrap(),
                     &[],
                     gas,
-                    &gas_budget.unwrap_or(3000),
+                    &Some(gas_budget.unwrap_or(3000)),
                     &args,
                     context,
                 )
@@ -569,6 +844,63 @@ impl WalletContext {
         }
     }
 
+    /// The objects which are inputs of `params`: the transferred object, or the arguments
+    /// which are objects in the signature of the called function.
+    pub async fn input_object_ids(
+        &self,
+        params: &SingleTransactionParams,
+    ) -> Result<Vec<ObjectID>, anyhow::Error> {
+        match params {
+            SingleTransactionParams::TransferCoin(params) => Ok(vec![params.object_id]),
+            SingleTransactionParams::MoveCall(params) => {
+                let package = self
+                    .gateway
+                    .get_object_info(params.package_object_id)
+                    .await?
+                    .into_object()?;
+                params.object_ids(&package)
+            }
+        }
+    }
+
+    /// When the gateway picks the gas coin of a transaction of `signer`, and none of its
+    /// gas coins covers `gas_budget` on its own, merge them until one does. The coins in
+    /// `excluded_coins`, which are inputs of the transaction, are left alone.
+    pub async fn merge_gas_coins(
+        &self,
+        signer: SuiAddress,
+        gas: &Option<ObjectID>,
+        gas_budget: &Option<u64>,
+        excluded_coins: Vec<ObjectID>,
+    ) -> Result<(), anyhow::Error> {
+        let gas_budget = match (gas, gas_budget) {
+            (None, Some(gas_budget)) => *gas_budget,
+            _ => return Ok(()),
+        };
+        if let Some(data) = self
+            .gateway
+            .merge_gas_coins(signer, gas_budget, excluded_coins)
+            .await?
+        {
+            let signature = self
+                .keystore
+                .read()
+                .unwrap()
+                .sign(&signer, &data.to_bytes())?;
+            let response = self
+                .gateway
+                .execute_transaction(Transaction::new(data, signature))
+                .await?
+                .to_merge_coin_response()?;
+            info!(
+                "Merged gas coins of {} into {}",
+                signer,
+                response.updated_coin.id()
+            );
+        }
+        Ok(())
+    }
+
     /// Find a gas object which fits the budget
     pub async fn gas_for_owner_budget(
         &self,
@@ -602,6 +934,19 @@ impl Display for WalletCommandResult {
             WalletCommandResult::Call(cert, effects) => {
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
             }
+            WalletCommandResult::Batch(cert, effects) => {
+                write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
+            }
+            WalletCommandResult::DryRun(response) => {
+                let gas_cost = &response.gas_cost_summary;
+                writeln!(writer, "{}", "----- Dry Run Effects ----".bold())?;
+                write!(writer, "{}", response.effects)?;
+                writeln!(
+                    writer,
+                    "Gas cost: {} computation, {} storage, {} storage rebate",
+                    gas_cost.computation_cost, gas_cost.storage_cost, gas_cost.storage_rebate
+                )?;
+            }
             WalletCommandResult::Transfer(time_elapsed, cert, effects) => {
                 writeln!(writer, "Transfer confirmed after {} us", time_elapsed)?;
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
@@ -612,23 +957,33 @@ impl Display for WalletCommandResult {
                     writeln!(writer, "{}", address)?;
                 }
             }
-            WalletCommandResult::Objects(object_refs) => {
+            WalletCommandResult::Objects(page) => {
                 writeln!(
                     writer,
-                    " {0: ^42} | {1: ^10} | {2: ^68}",
+                    " {0: ^42} | {1: ^10} | {2: ^68} | Type",
                     "Object ID", "Version", "Digest"
                 )?;
-                writeln!(writer, "{}", ["-"; 126].join(""))?;
-                for (id, version, digest) in object_refs {
+                writeln!(writer, "{}", ["-"; 140].join(""))?;
+                for object in &page.data {
+                    let (id, version, digest) = object.object_ref;
+                    let object_type = object
+                        .object_type
+                        .as_ref()
+                        .map(|tag| tag.to_string())
+                        .unwrap_or_default();
                     writeln!(
                         writer,
-                        " {0: ^42} | {1: ^10} | {2: ^34?}",
+                        " {0: ^42} | {1: ^10} | {2: ^34?} | {3}",
                         id,
                         version.value(),
-                        digest
+                        digest,
+                        object_type
                     )?;
                 }
-                writeln!(writer, "Showing {} results.", object_refs.len())?;
+                writeln!(writer, "Showing {} results.", page.data.len())?;
+                if let Some(cursor) = page.next_cursor {
+                    writeln!(writer, "Next page cursor: {}", cursor)?;
+                }
             }
             WalletCommandResult::SyncClientState => {
                 writeln!(writer, "Client state sync complete.")?;
@@ -657,6 +1012,34 @@ impl Display for WalletCommandResult {
                     )?;
                 }
             }
+            WalletCommandResult::Balance(balances) => {
+                writeln!(
+                    writer,
+                    " {0: ^50} | {1: ^7} | {2: ^20}",
+                    "Coin Type", "Coins", "Balance"
+                )?;
+                writeln!(writer, "{}", ["-"; 84].join(""))?;
+                for balance in balances {
+                    writeln!(
+                        writer,
+                        " {0: ^50} | {1: ^7} | {2: ^20}",
+                        balance.coin_type.to_string(),
+                        balance.coin_object_count,
+                        balance.total_balance
+                    )?;
+                }
+            }
+            WalletCommandResult::Transactions(page) => {
+                writeln!(writer, " {0: ^10} | {1: ^46}", "Seq", "Digest")?;
+                writeln!(writer, "{}", ["-"; 60].join(""))?;
+                for (seq, digest) in &page.data {
+                    writeln!(writer, " {0: ^10} | {1: ^46}", seq, format!("{:?}", digest))?;
+                }
+                writeln!(writer, "Showing {} results.", page.data.len())?;
+                if let Some(cursor) = page.next_cursor {
+                    writeln!(writer, "Next page cursor: {}", cursor)?;
+                }
+            }
             WalletCommandResult::SplitCoin(response) => {
                 write!(writer, "{}", response)?;
             }
@@ -683,19 +1066,34 @@ impl Display for WalletCommandResult {
     }
 }
 
-async fn call_move(
+/// Build the data of a Move call transaction, sent by the owner of the gas object or
+/// the active address.
+async fn move_call_data(
     package: &ObjectID,
     module: &Identifier,
     function: &Identifier,
     type_args: &[TypeTag],
     gas: &Option<ObjectID>,
-    gas_budget: &u64,
+    gas_budget: &Option<u64>,
     args: &[SuiJsonValue],
     context: &mut WalletContext,
-) -> Result<(CertifiedTransaction, TransactionEffects), anyhow::Error> {
+) -> Result<(SuiAddress, TransactionData), anyhow::Error> {
     let gas_owner = context.try_get_object_owner(gas).await?;
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
+    // The object arguments are kept out of the merge.
+    let params = SingleTransactionParams::MoveCall(MoveCallParams {
+        package_object_id: *package,
+        module: module.to_owned(),
+        function: function.to_owned(),
+        type_arguments: type_args.to_owned(),
+        arguments: args.to_vec(),
+    });
+    let input_objects = context.input_object_ids(&params).await?;
+    context
+        .merge_gas_coins(sender, gas, gas_budget, input_objects)
+        .await?;
+
     let data = context
         .gateway
         .move_call(
@@ -709,6 +1107,23 @@ async fn call_move(
             *gas_budget,
         )
         .await?;
+    Ok((sender, data))
+}
+
+async fn call_move(
+    package: &ObjectID,
+    module: &Identifier,
+    function: &Identifier,
+    type_args: &[TypeTag],
+    gas: &Option<ObjectID>,
+    gas_budget: &Option<u64>,
+    args: &[SuiJsonValue],
+    context: &mut WalletContext,
+) -> Result<(CertifiedTransaction, TransactionEffects), anyhow::Error> {
+    let (sender, data) = move_call_data(
+        package, module, function, type_args, gas, gas_budget, args, context,
+    )
+    .await?;
     let signature = context
         .keystore
         .read()
@@ -727,6 +1142,22 @@ async fn call_move(
     Ok((cert, effects))
 }
 
+/// Read the transactions of a batch from a JSON or YAML file, in the format of the
+/// `sui_batchTransaction` JSON-RPC method.
+fn read_batch_transaction_params(
+    path: &Path,
+) -> Result<Vec<SingleTransactionParams>, anyhow::Error> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read batch file {:?}: {}", path, e))?;
+    // JSON is a subset of YAML, so both are parsed as YAML.
+    let params: Vec<SingleTransactionRequestParams> = serde_yaml::from_str(&content)
+        .map_err(|e| anyhow!("Cannot parse batch file {:?}: {}", path, e))?;
+    params
+        .into_iter()
+        .map(SingleTransactionParams::try_from)
+        .collect()
+}
+
 fn unwrap_or<'a>(val: &'a mut Option<String>, default: &'a str) -> &'a str {
     match val {
         Some(v) => v,
@@ -789,6 +1220,8 @@ pub enum WalletCommandResult {
     Publish(PublishResponse),
     Object(ObjectRead),
     Call(CertifiedTransaction, TransactionEffects),
+    Batch(CertifiedTransaction, TransactionEffects),
+    DryRun(DryRunTransactionResponse),
     Transfer(
         // Skipping serialisation for elapsed time.
         #[serde(skip)] u128,
@@ -796,10 +1229,12 @@ pub enum WalletCommandResult {
         TransactionEffects,
     ),
     Addresses(Vec<SuiAddress>),
-    Objects(Vec<ObjectRef>),
+    Objects(OwnedObjectPage),
     SyncClientState,
     NewAddress(SuiAddress),
     Gas(Vec<GasCoin>),
+    Balance(Vec<Balance>),
+    Transactions(TransactionPage),
     SplitCoin(SplitCoinResponse),
     MergeCoin(MergeCoinResponse),
     Switch(SwitchResponse),
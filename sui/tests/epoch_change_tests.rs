@@ -0,0 +1,79 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use sui::sui_commands::make_authority;
+use sui_adapter::genesis;
+use sui_core::{
+    authority::AuthorityState, authority_client::NetworkAuthorityClient,
+    epoch_change::EpochChangeDriver,
+};
+use test_utils::{
+    authority::{test_authority_configs, test_authority_store},
+    messages::MAX_GAS,
+};
+
+/// Every validator ends the epoch, and the authorities move to the committee of the
+/// next one, recorded in the Sui system state.
+#[tokio::test]
+async fn change_epoch() {
+    // Spawn the authorities with the genesis objects, which hold the Sui system state and
+    // the gas coins of the validators. Note that it is important to not drop the handles
+    // (or the authorities will stop).
+    let configs = test_authority_configs();
+    let mut states = Vec::new();
+    let mut _handles = Vec::new();
+    for validator in configs.validator_configs() {
+        let state = AuthorityState::new(
+            validator.committee_config().committee(),
+            validator.public_key(),
+            Arc::pin(validator.key_pair().copy()),
+            Arc::new(test_authority_store()),
+            genesis::clone_genesis_compiled_modules(),
+            &mut genesis::get_genesis_context(),
+        )
+        .await;
+        for object in validator.genesis().objects() {
+            state.insert_genesis_object(object.clone()).await;
+        }
+        let server = make_authority(validator, state).await.unwrap();
+        states.push(server.state.clone());
+        _handles.push(server.spawn().await.unwrap());
+    }
+
+    let drivers: Vec<_> = configs
+        .validator_configs()
+        .iter()
+        .zip(&states)
+        .map(|(validator, state)| {
+            let clients: BTreeMap<_, _> = configs
+                .validator_set()
+                .iter()
+                .map(|info| {
+                    let client =
+                        NetworkAuthorityClient::connect_lazy(info.network_address()).unwrap();
+                    (info.public_key(), client)
+                })
+                .collect();
+            EpochChangeDriver::new(
+                state.clone(),
+                clients,
+                validator.key_pair().copy(),
+                NetworkAuthorityClient::connector(mysten_network::config::Config::new()),
+            )
+        })
+        .collect();
+
+    // Every validator halts and submits the epoch change.
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let results =
+        futures::future::join_all(drivers.iter().map(|driver| driver.change_epoch(MAX_GAS))).await;
+    for result in results {
+        result.unwrap();
+    }
+
+    for (state, driver) in states.iter().zip(&drivers) {
+        assert_eq!(state.epoch(), 1);
+        assert!(!state.is_halted());
+        assert_eq!(driver.net().committee.epoch, 1);
+    }
+}
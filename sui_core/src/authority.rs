@@ -17,14 +17,16 @@ use move_core_types::{
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use narwhal_executor::{ExecutionIndices, ExecutionState};
 use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use prometheus_exporter::prometheus::{
     register_histogram, register_int_counter, Histogram, IntCounter,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap, HashSet, VecDeque},
     pin::Pin,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
 };
@@ -33,14 +35,16 @@ use sui_config::genesis::Genesis;
 use sui_types::{
     base_types::*,
     batch::{TxSequenceNumber, UpdateItem},
-    committee::Committee,
+    committee::{Committee, EpochId},
     crypto::AuthoritySignature,
     error::{SuiError, SuiResult},
     fp_bail, fp_ensure,
-    gas::SuiGasStatus,
+    gas::{GasCostSummary, SuiCostSchedule, SuiGasStatus, INITIAL_REFERENCE_GAS_PRICE},
     messages::*,
     object::{Data, Object},
+    state_commitment::{ObjectProof, StateDigest},
     storage::{BackingPackageStore, DeleteKind, Storage},
+    sui_system_state::{SuiSystemState, SUI_SYSTEM_STATE_OBJECT_ID},
     MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
 };
 use tracing::{debug, instrument, log};
@@ -65,10 +69,14 @@ mod temporary_store;
 pub use temporary_store::AuthorityTemporaryStore;
 
 mod authority_store;
-pub use authority_store::{AuthorityStore, GatewayStore, SuiDataStore};
+pub use authority_store::{AuthorityStore, GatewayStore, ReplicaStore, SuiDataStore};
 
 pub mod authority_notifier;
 
+pub mod authority_pruner;
+
+pub mod authority_scheduler;
+
 const MAX_ITEMS_LIMIT: u64 = 100_000;
 const BROADCAST_CAPACITY: usize = 10_000;
 
@@ -84,6 +92,7 @@ pub struct AuthorityMetrics {
     num_input_objs: Histogram,
     num_shared_objects: Histogram,
     batch_size: Histogram,
+    pruned_transactions: IntCounter,
 }
 
 // Override default Prom buckets for positive numbers in 0-50k range
@@ -145,6 +154,11 @@ impl AuthorityMetrics {
                 POSITIVE_INT_BUCKETS.to_vec()
             )
             .unwrap(),
+            pruned_transactions: register_int_counter!(
+                "total_pruned_transactions",
+                "Total number of executed transactions pruned from the store"
+            )
+            .unwrap(),
         }
     }
 }
@@ -168,12 +182,32 @@ pub static METRICS: Lazy<AuthorityMetrics> = Lazy::new(AuthorityMetrics::new);
 pub type StableSyncAuthoritySigner =
     Pin<Arc<dyn signature::Signer<AuthoritySignature> + Send + Sync>>;
 
+/// A certificate executed against the store, whose effects are not yet committed.
+struct ExecutedCertificate {
+    certificate: CertifiedTransaction,
+    temporary_store: AuthorityTemporaryStore<AuthorityStore>,
+    effects: TransactionEffects,
+}
+
 pub struct AuthorityState {
     // Fixed size, static, identity of the authority
     /// The name of this authority.
     pub name: AuthorityName,
-    /// Committee of this Sui instance.
-    pub committee: Committee,
+    /// Committee of this Sui instance, swapped at each epoch change.
+    committee: RwLock<Arc<Committee>>,
+    /// Set once the authority stops signing transactions of the current epoch, until it
+    /// moves to the committee of the next one.
+    halted: AtomicBool,
+    /// Held shared while a certificate without shared objects executes outside of
+    /// consensus, and exclusively to seal the epoch: once sealed, such certificates only
+    /// execute when sequenced, so that every authority executes the same ones.
+    direct_execution: tokio::sync::RwLock<()>,
+    /// The minimum gas price of the transactions signed in the current epoch.
+    reference_gas_price: AtomicU64,
+    /// The cost schedules recorded in the store, keyed by the epoch they are in effect at,
+    /// along with their version. Certificates are metered with the schedule of the epoch
+    /// they were certified in, so that they always execute the same way.
+    cost_schedules: RwLock<BTreeMap<EpochId, (u64, Arc<SuiCostSchedule>)>>,
     /// The signature key of the authority.
     pub secret: StableSyncAuthoritySigner,
 
@@ -211,21 +245,309 @@ impl AuthorityState {
         self.batch_channels.subscribe()
     }
 
+    /// The committee of the current epoch.
+    pub fn committee(&self) -> Arc<Committee> {
+        self.committee.read().clone()
+    }
+
+    /// The current epoch of this authority.
+    pub fn epoch(&self) -> EpochId {
+        self.committee.read().epoch
+    }
+
+    /// Stop signing new transactions of the current epoch, except for the one changing
+    /// the epoch. Certificates are still executed, so that the last transactions of the
+    /// epoch can complete.
+    pub fn halt_validator(&self) {
+        self.halted.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst)
+    }
+
+    /// Resume signing transactions of the current epoch, when the end of the epoch was
+    /// abandoned before this authority sealed it.
+    pub fn resume_validator(&self) {
+        self.halted.store(false, Ordering::SeqCst);
+    }
+
+    /// Stop executing certificates without shared objects outside of consensus, once the
+    /// ones in flight completed, and return those this authority executed during the
+    /// epoch, in order. They must be sequenced before the epoch is sealed.
+    pub async fn seal_direct_execution(&self) -> SuiResult<Vec<CertifiedTransaction>> {
+        fp_ensure!(
+            self.is_halted(),
+            SuiError::InvalidEpochChange {
+                error: format!("Epoch {} has not ended", self.epoch()),
+            }
+        );
+        let _guard = self.direct_execution.write().await;
+        self._database.epoch_certificates(self.epoch())
+    }
+
+    /// The announcement that this authority sequenced all the certificates it executed
+    /// outside of consensus during the current epoch.
+    pub fn new_end_of_epoch(&self) -> SignedEndOfEpoch {
+        SignedEndOfEpoch::new(self.epoch(), self.name, &*self.secret)
+    }
+
+    /// The stake of the authorities whose end of the current epoch was sequenced. The
+    /// epoch changes once it reaches a quorum.
+    pub fn sealed_stake(&self) -> SuiResult<usize> {
+        let committee = self.committee();
+        Ok(self
+            ._database
+            .sealed_authorities(committee.epoch)?
+            .iter()
+            .map(|authority| committee.weight(authority))
+            .sum())
+    }
+
+    /// The minimum gas price of the transactions signed in the current epoch, derived by
+    /// the Sui system state from the gas prices announced by the validators.
+    pub fn reference_gas_price(&self) -> u64 {
+        self.reference_gas_price.load(Ordering::SeqCst)
+    }
+
+    /// The cost schedule in effect at `epoch`, which must be one this authority went through.
+    pub fn cost_schedule(&self, epoch: EpochId) -> SuiResult<Arc<SuiCostSchedule>> {
+        let cost_schedules = self.cost_schedules.read();
+        let (_, cost_schedule) =
+            cost_schedules
+                .get(&epoch)
+                .ok_or_else(|| SuiError::InvalidCostSchedule {
+                    error: format!("No cost schedule is recorded for epoch {epoch}"),
+                })?;
+        Ok(cost_schedule.clone())
+    }
+
+    /// Load the cost schedule in effect at the current epoch, recording it in the store if
+    /// the authority just moved to the epoch.
+    fn load_cost_schedule(&self) -> SuiResult {
+        let epoch = self.epoch();
+        let (version, cost_schedule) = self._database.epoch_cost_schedule(epoch)?;
+        let mut cost_schedules = self.cost_schedules.write();
+        if cost_schedules.values().next_back().map(|(v, _)| *v) != Some(version) {
+            log::info!("Moving to version {version} of the cost schedule at epoch {epoch}");
+        }
+        cost_schedules.insert(epoch, (version, Arc::new(cost_schedule)));
+        Ok(())
+    }
+
+    /// Move to the committee of a later epoch, and resume signing transactions.
+    pub fn reconfigure(&self, committee: Committee) -> SuiResult {
+        let mut current = self.committee.write();
+        fp_ensure!(
+            committee.epoch > current.epoch,
+            SuiError::InvalidCommittee {
+                error: format!(
+                    "Cannot move from epoch {} to epoch {}",
+                    current.epoch, committee.epoch
+                ),
+            }
+        );
+        log::info!("Moving to the committee of epoch {}", committee.epoch);
+        *current = Arc::new(committee);
+        self.halted.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// The gas fees collected from the transactions certified in the current epoch, as
+    /// far as this authority executed them.
+    pub fn epoch_gas_charges(&self) -> SuiResult<GasCostSummary> {
+        self._database.epoch_gas_charges(self.epoch())
+    }
+
+    async fn framework_object_ref(&self) -> SuiResult<ObjectRef> {
+        let framework_id = ObjectID::from(SUI_FRAMEWORK_ADDRESS);
+        let framework = self
+            .get_object(&framework_id)
+            .await?
+            .ok_or(SuiError::ObjectNotFound {
+                object_id: framework_id,
+            })?;
+        Ok(framework.compute_object_reference())
+    }
+
+    /// The transaction reporting the gas fees collected by this authority during the
+    /// current epoch. It must be sent by an active validator, and is priced at the
+    /// reference gas price.
+    pub async fn new_report_gas_charges_transaction(
+        &self,
+        sender: SuiAddress,
+        system_state_id: ObjectID,
+        gas_payment: ObjectRef,
+        gas_budget: u64,
+    ) -> SuiResult<TransactionData> {
+        let mut data = TransactionData::new_report_gas_charges(
+            sender,
+            self.framework_object_ref().await?,
+            system_state_id,
+            gas_payment,
+            &self.epoch_gas_charges()?,
+            gas_budget,
+        );
+        data.gas_price = self.reference_gas_price();
+        Ok(data)
+    }
+
+    /// The transaction ending the current epoch, which distributes the gas fees reported
+    /// by the validators as staking rewards. It must be sent by an active validator, and
+    /// is priced at the reference gas price.
+    pub async fn new_change_epoch_transaction(
+        &self,
+        sender: SuiAddress,
+        system_state_id: ObjectID,
+        gas_payment: ObjectRef,
+        gas_budget: u64,
+    ) -> SuiResult<TransactionData> {
+        let mut data = TransactionData::new_change_epoch(
+            sender,
+            self.framework_object_ref().await?,
+            system_state_id,
+            gas_payment,
+            self.epoch() + 1,
+            gas_budget,
+        );
+        data.gas_price = self.reference_gas_price();
+        Ok(data)
+    }
+
+    /// The transactions reporting the gas fees and changing the epoch are only signed once
+    /// this authority halted, and only the change to the next epoch is signed.
+    fn check_epoch_end_transaction(&self, data: &TransactionData) -> SuiResult {
+        fp_ensure!(
+            self.is_halted(),
+            SuiError::InvalidEpochChange {
+                error: format!("Epoch {} has not ended", self.epoch()),
+            }
+        );
+        if data.is_report_gas_charges() {
+            return Ok(());
+        }
+        let expected = match &data.kind {
+            TransactionKind::Single(SingleTransactionKind::Call(call)) => {
+                match call.arguments.first() {
+                    Some(CallArg::SharedObject(system_state_id)) => {
+                        TransactionData::new_change_epoch(
+                            data.signer(),
+                            call.package,
+                            *system_state_id,
+                            data.gas(),
+                            self.epoch() + 1,
+                            data.gas_budget,
+                        )
+                    }
+                    _ => fp_bail!(SuiError::InvalidEpochChange {
+                        error: "The system state is not the first argument".to_owned(),
+                    }),
+                }
+            }
+            _ => fp_bail!(SuiError::InvalidEpochChange {
+                error: "Not a single Move call".to_owned(),
+            }),
+        };
+        fp_ensure!(
+            expected.kind == data.kind,
+            SuiError::InvalidEpochChange {
+                error: format!("Expected a change to epoch {}", self.epoch() + 1),
+            }
+        );
+        Ok(())
+    }
+
+    /// Read the Sui system state after the epoch changed, and move to the committee
+    /// derived from its active validators, to its reference gas price, and to the cost
+    /// schedule of the new epoch. Nothing changes if the system state is still at the
+    /// current epoch.
+    async fn reconfigure_from_system_state(&self, system_state_id: &ObjectID) -> SuiResult {
+        let object = self
+            .get_object(system_state_id)
+            .await?
+            .ok_or(SuiError::ObjectNotFound {
+                object_id: *system_state_id,
+            })?;
+        let system_state = SuiSystemState::from_object(&object)?;
+        if system_state.epoch <= self.epoch() {
+            return Ok(());
+        }
+        self._database
+            .release_transaction_locks_before(system_state.epoch)?;
+        self.reconfigure(system_state.get_committee()?)?;
+        self.reference_gas_price
+            .store(system_state.reference_gas_price, Ordering::SeqCst);
+        self.load_cost_schedule()
+    }
+
+    /// Once the epoch change is executed, the system state holds the next committee.
+    async fn reconfigure_after_epoch_change(
+        &self,
+        certificate: &CertifiedTransaction,
+    ) -> SuiResult {
+        if certificate.data.is_change_epoch() {
+            if let Some(system_state_id) = certificate.shared_input_objects().next() {
+                self.reconfigure_from_system_state(system_state_id).await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn handle_transaction_impl(
         &self,
         transaction: Transaction,
     ) -> Result<TransactionInfoResponse, SuiError> {
         let transaction_digest = *transaction.digest();
-        // Ensure an idempotent answer.
-        if self._database.transaction_exists(&transaction_digest)? {
-            self.metrics.tx_already_processed.inc();
-            let transaction_info = self.make_transaction_info(&transaction_digest).await?;
-            return Ok(transaction_info);
+        // Ensure an idempotent answer. A transaction signed in a past epoch and not executed
+        // is signed again, since its certificate would now be rejected: the locks it may
+        // still hold are released first.
+        if let Some(signed_transaction) = self._database.get_transaction(&transaction_digest)? {
+            if signed_transaction.auth_sign_info.epoch == self.epoch()
+                || self._database.effects_exists(&transaction_digest)?
+            {
+                self.metrics.tx_already_processed.inc();
+                let transaction_info = self.make_transaction_info(&transaction_digest).await?;
+                return Ok(transaction_info);
+            }
+            let owned_objects: Vec<_> = signed_transaction
+                .data
+                .input_objects()?
+                .into_iter()
+                .filter_map(|kind| match kind {
+                    InputObjectKind::ImmOrOwnedMoveObject(object_ref) => Some(object_ref),
+                    _ => None,
+                })
+                .collect();
+            self._database
+                .release_transaction_lock(&owned_objects, &transaction_digest)?;
         }
 
+        // At the end of an epoch, only the transactions reporting its gas fees and moving
+        // to the next one are signed.
+        if transaction.data.is_change_epoch() || transaction.data.is_report_gas_charges() {
+            self.check_epoch_end_transaction(&transaction.data)?;
+        } else {
+            fp_ensure!(
+                !self.is_halted(),
+                SuiError::ValidatorHaltedAtEpochEnd {
+                    epoch: self.epoch()
+                }
+            );
+        }
+
+        fp_ensure!(
+            transaction.data.gas_price >= self.reference_gas_price(),
+            SuiError::GasPriceUnderReference {
+                gas_price: transaction.data.gas_price,
+                reference_gas_price: self.reference_gas_price(),
+            }
+        );
+
+        let cost_schedule = self.cost_schedule(self.epoch())?;
         let (_gas_status, all_objects) = transaction_input_checker::check_transaction_input(
             &self._database,
             &transaction,
+            &cost_schedule,
             &self.metrics.shared_obj_tx,
         )
         .await?;
@@ -233,7 +555,7 @@ impl AuthorityState {
         let owned_objects = transaction_input_checker::filter_owned_objects(&all_objects);
 
         let signed_transaction =
-            SignedTransaction::new(self.committee.epoch, transaction, self.name, &*self.secret);
+            SignedTransaction::new(self.epoch(), transaction, self.name, &*self.secret);
 
         // Check and write locks, to signed transaction, into the database
         // The call to self.set_transaction_lock checks the lock is not conflicting,
@@ -279,20 +601,55 @@ impl AuthorityState {
     pub async fn handle_confirmation_transaction(
         &self,
         confirmation_transaction: ConfirmationTransaction,
+    ) -> SuiResult<TransactionInfoResponse> {
+        self.confirm_certificate(confirmation_transaction, false)
+            .await
+    }
+
+    /// Once the authority halted, the epoch may be sealed, so certificates without shared
+    /// objects are only executed when sequenced by consensus.
+    fn check_direct_execution(&self, certificate: &CertifiedTransaction) -> SuiResult {
+        fp_ensure!(
+            certificate.contains_shared_object() || !self.is_halted(),
+            SuiError::ValidatorHaltedAtEpochEnd {
+                epoch: self.epoch()
+            }
+        );
+        Ok(())
+    }
+
+    /// Execute a certificate, unless it already was. The certificate is `sequenced` when
+    /// it comes from consensus.
+    async fn confirm_certificate(
+        &self,
+        confirmation_transaction: ConfirmationTransaction,
+        sequenced: bool,
     ) -> SuiResult<TransactionInfoResponse> {
         self.metrics.total_certs.inc();
         let transaction_digest = *confirmation_transaction.certificate.digest();
 
-        // Ensure an idempotent answer.
+        // Ensure an idempotent answer. If the certificate changed the epoch, the
+        // reconfiguration that failed after its effects were committed is retried.
         if self._database.effects_exists(&transaction_digest)? {
+            self.reconfigure_after_epoch_change(&confirmation_transaction.certificate)
+                .await?;
             let info = self.make_transaction_info(&transaction_digest).await?;
             debug!("Transaction {transaction_digest:?} already executed");
             return Ok(info);
         }
 
+        let _direct_execution = self.direct_execution.read().await;
+        if !sequenced {
+            self.check_direct_execution(&confirmation_transaction.certificate)?;
+        }
+
         // Check the certificate and retrieve the transfer data.
         tracing::trace_span!("cert_check_signature")
-            .in_scope(|| confirmation_transaction.certificate.verify(&self.committee))
+            .in_scope(|| {
+                confirmation_transaction
+                    .certificate
+                    .verify(&self.committee())
+            })
             .map_err(|e| {
                 self.metrics.signature_errors.inc();
                 e
@@ -363,12 +720,25 @@ impl AuthorityState {
         &self,
         confirmation_transaction: ConfirmationTransaction,
     ) -> Result<TransactionInfoResponse, SuiError> {
-        let certificate = confirmation_transaction.certificate;
+        let executed = self
+            .execute_certificate(confirmation_transaction.certificate)
+            .await?;
+        self.commit_certificate(executed).await
+    }
+
+    /// Executes a certificate against the current state of the store, without writing
+    /// its effects. The certificate must have been verified.
+    async fn execute_certificate(
+        &self,
+        certificate: CertifiedTransaction,
+    ) -> SuiResult<ExecutedCertificate> {
         let transaction_digest = *certificate.digest();
 
+        let cost_schedule = self.cost_schedule(certificate.auth_sign_info.epoch)?;
         let (gas_status, objects_by_kind) = transaction_input_checker::check_transaction_input(
             &self._database,
             &certificate,
+            &cost_schedule,
             &self.metrics.shared_obj_tx,
         )
         .await?;
@@ -422,19 +792,43 @@ impl AuthorityState {
             gas_status,
         )?;
 
+        Ok(ExecutedCertificate {
+            certificate,
+            temporary_store,
+            effects,
+        })
+    }
+
+    /// Signs the effects of an executed certificate and writes them to the store, along
+    /// with the objects it changed. Certificates are sequenced in the order they are
+    /// committed.
+    async fn commit_certificate(
+        &self,
+        executed: ExecutedCertificate,
+    ) -> SuiResult<TransactionInfoResponse> {
+        let ExecutedCertificate {
+            certificate,
+            temporary_store,
+            effects,
+        } = executed;
+        let transaction_digest = *certificate.digest();
+
         self.metrics.total_effects.inc();
         self.metrics
             .total_events
             .inc_by(effects.events.len() as u64);
 
         // TODO: Distribute gas charge and rebate, which can be retrieved from effects.
-        let signed_effects =
-            effects.to_sign_effects(self.committee.epoch, &self.name, &*self.secret);
+        let signed_effects = effects.to_sign_effects(self.epoch(), &self.name, &*self.secret);
 
         // Update the database in an atomic manner
         self.update_state(temporary_store, &certificate, &signed_effects)
             .await?;
 
+        // The effects stay committed if the reconfiguration fails, and it is retried the
+        // next time the certificate is handled.
+        self.reconfigure_after_epoch_change(&certificate).await?;
+
         Ok(TransactionInfoResponse {
             signed_transaction: self._database.get_transaction(&transaction_digest)?,
             certified_transaction: Some(certificate),
@@ -469,7 +863,7 @@ impl AuthorityState {
         }
 
         // Check the certificate.
-        certificate.verify(&self.committee)?;
+        certificate.verify(&self.committee())?;
 
         // Persist the certificate since we are about to lock one or more shared object.
         // We thus need to make sure someone (if not the client) can continue the protocol.
@@ -482,6 +876,47 @@ impl AuthorityState {
             .persist_certificate_and_lock_shared_objects(certificate, last_consensus_index)
     }
 
+    /// Execute a certificate sequenced by consensus, after assigning the locks of its
+    /// shared objects.
+    async fn handle_sequenced_certificate(
+        &self,
+        certificate: CertifiedTransaction,
+        execution_indices: ExecutionIndices,
+    ) -> SuiResult<TransactionInfoResponse> {
+        // Ensure an idempotent answer.
+        let digest = *certificate.digest();
+        if self._database.effects_exists(&digest)? {
+            let info = self.make_transaction_info(&digest).await?;
+            debug!("Sequenced transaction {digest:?} already executed");
+            return Ok(info);
+        }
+
+        // The epoch only changes once a quorum sealed it, so that the certificates of the
+        // epoch executed by any of them are executed by all.
+        if certificate.data.is_change_epoch() {
+            let committee = self.committee();
+            fp_ensure!(
+                self.sealed_stake()? >= committee.quorum_threshold(),
+                SuiError::InvalidEpochChange {
+                    error: format!("Epoch {} is not sealed", committee.epoch),
+                }
+            );
+        }
+
+        // Assign locks to shared objects.
+        self.handle_consensus_certificate(certificate.clone(), execution_indices)
+            .await?;
+        debug!("Shared objects locks successfully attributed to transaction {digest:?}");
+
+        // Attempt to execute the transaction. This will only succeed if the authority
+        // already executed all its dependencies.
+        let info = self
+            .confirm_certificate(ConfirmationTransaction { certificate }, true)
+            .await?;
+        debug!("Executed transaction {digest:?}");
+        Ok(info)
+    }
+
     /// Check if we need to submit this transaction to consensus. We usually do, unless (i) we already
     /// processed the transaction and we can immediately return the effects, or (ii) we already locked
     /// all shared-objects of the transaction and can (re-)attempt execution.
@@ -529,6 +964,24 @@ impl AuthorityState {
             .await
     }
 
+    /// Executes a transaction against the current state, and returns the effects it would
+    /// have. Validators do not serve dry runs to clients, which run them against the store
+    /// of a gateway or full node instead.
+    pub async fn dry_run_transaction(
+        &self,
+        transaction_data: TransactionData,
+    ) -> SuiResult<DryRunTransactionResponse> {
+        let cost_schedule = self.cost_schedule(self.epoch())?;
+        execution_engine::dry_run_transaction(
+            &self._database,
+            transaction_data,
+            &cost_schedule,
+            &self.move_vm,
+            &self._native_functions,
+        )
+        .await
+    }
+
     pub async fn handle_account_info_request(
         &self,
         request: AccountInfoRequest,
@@ -570,7 +1023,8 @@ impl AuthorityState {
             None => (None, None),
         };
 
-        // Return the latest version of the object and the current lock if any, if requested.
+        // Return the latest version of the object and the current lock if any, if requested,
+        // or the historical version of the object if the store still holds it.
         let object_and_lock = match request.request_kind {
             ObjectInfoRequestKind::LatestObjectInfo(request_layout) => {
                 match self.get_object(&request.object_id).await {
@@ -600,7 +1054,15 @@ impl AuthorityState {
                     _ => None,
                 }
             }
-            ObjectInfoRequestKind::PastObjectInfo(_) => None,
+            ObjectInfoRequestKind::PastObjectInfo(seq) => self
+                ._database
+                .get_object_by_version(&request.object_id, seq)?
+                .map(|object| ObjectResponse {
+                    object,
+                    // Locks are only meaningful for the latest version of an object.
+                    lock: None,
+                    layout: None,
+                }),
         };
 
         Ok(ObjectInfoResponse {
@@ -647,6 +1109,13 @@ impl AuthorityState {
         };
         let end = start + request.length;
 
+        // Ensure the requested range has not been pruned.
+        let lowest_retained = self._database.lowest_retained_sequence()?;
+        fp_ensure!(
+            start >= lowest_retained,
+            SuiError::PrunedSequenceRangeError { lowest_retained }
+        );
+
         let (batches, transactions) = self._database.batches_and_transactions(start, end)?;
 
         let mut dq_batches = std::collections::VecDeque::from(batches);
@@ -757,8 +1226,17 @@ impl AuthorityState {
         let native_functions =
             sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
 
+        let cost_schedules = store
+            .cost_schedules()
+            .into_iter()
+            .map(|(epoch, (version, cost_schedule))| (epoch, (version, Arc::new(cost_schedule))))
+            .collect();
         let mut state = AuthorityState {
-            committee,
+            cost_schedules: RwLock::new(cost_schedules),
+            committee: RwLock::new(Arc::new(committee)),
+            halted: AtomicBool::new(false),
+            direct_execution: tokio::sync::RwLock::new(()),
+            reference_gas_price: AtomicU64::new(INITIAL_REFERENCE_GAS_PRICE),
             name,
             secret,
             _native_functions: native_functions.clone(),
@@ -780,6 +1258,30 @@ impl AuthorityState {
             .init_batches_from_database()
             .expect("Init batches failed!");
 
+        // Certificates cannot be executed until the cost schedule of their epoch is known,
+        // but the authority still starts, so that it can be reconfigured.
+        if let Err(e) = state.load_cost_schedule() {
+            log::error!(
+                "Failed to load the cost schedule of epoch {}: {e}",
+                state.epoch()
+            );
+        }
+
+        // Resume at the epoch of the stored system state, if it moved past the committee
+        // this authority was started with.
+        if store
+            .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)
+            .expect("Database read should not fail.")
+            .is_some()
+        {
+            if let Err(e) = state
+                .reconfigure_from_system_state(&SUI_SYSTEM_STATE_OBJECT_ID)
+                .await
+            {
+                log::error!("Failed to move to the epoch of the Sui system state: {e}");
+            }
+        }
+
         state
     }
 
@@ -809,56 +1311,13 @@ impl AuthorityState {
         ctx: &mut TxContext,
         modules: Vec<CompiledModule>,
     ) -> SuiResult {
-        let inputs = Transaction::input_objects_in_compiled_modules(&modules);
-        let ids: Vec<_> = inputs.iter().map(|kind| kind.object_id()).collect();
-        let input_objects = self.get_objects(&ids[..]).await?;
-        // When publishing genesis packages, since the std framework packages all have
-        // non-zero addresses, [`Transaction::input_objects_in_compiled_modules`] will consider
-        // them as dependencies even though they are not. Hence input_objects contain objects
-        // that don't exist on-chain because they are yet to be published.
-        #[cfg(debug_assertions)]
-        {
-            let to_be_published_addresses: HashSet<_> = modules
-                .iter()
-                .map(|module| *module.self_id().address())
-                .collect();
-            assert!(
-                // An object either exists on-chain, or is one of the packages to be published.
-                inputs
-                    .iter()
-                    .zip(input_objects.iter())
-                    .all(|(kind, obj_opt)| obj_opt.is_some()
-                        || to_be_published_addresses.contains(&kind.object_id()))
-            );
-        }
-        let filtered = inputs
-            .into_iter()
-            .zip(input_objects.into_iter())
-            .filter_map(|(input, object_opt)| object_opt.map(|object| (input, object)))
-            .collect::<Vec<_>>();
-
-        debug_assert!(ctx.digest() == TransactionDigest::genesis());
-        let mut temporary_store =
-            AuthorityTemporaryStore::new(self._database.clone(), filtered, ctx.digest());
-        let package_id = ObjectID::from(*modules[0].self_id().address());
-        let natives = self._native_functions.clone();
-        let mut gas_status = SuiGasStatus::new_unmetered();
-        let vm = adapter::verify_and_link(
-            &temporary_store,
-            &modules,
-            package_id,
-            natives,
-            &mut gas_status,
-        )?;
-        adapter::store_package_and_init_modules(
-            &mut temporary_store,
-            &vm,
-            modules,
+        store_package_and_init_modules_for_genesis(
+            &self._database,
+            &self._native_functions,
             ctx,
-            &mut gas_status,
-        )?;
-        self.db()
-            .update_objects_state_for_genesis(temporary_store, ctx.digest())
+            modules,
+        )
+        .await
     }
 
     /// Make an information response for a transaction
@@ -960,6 +1419,17 @@ impl AuthorityState {
     ) -> Result<Option<(ObjectRef, TransactionDigest)>, SuiError> {
         self._database.get_latest_parent_entry(object_id)
     }
+
+    /// Returns the root of the state commitment over the live objects as of the last batch.
+    pub fn state_root(&self) -> SuiResult<StateDigest> {
+        self._database.state_root()
+    }
+
+    /// Returns a proof that the object is, or is not, part of the state as of the last
+    /// batch. The proof is against the state root signed as part of that batch.
+    pub fn get_object_proof(&self, object_id: ObjectID) -> SuiResult<ObjectProof> {
+        self._database.get_object_proof(object_id)
+    }
 }
 
 impl ModuleResolver for AuthorityState {
@@ -980,30 +1450,43 @@ impl ExecutionState for AuthorityState {
         execution_indices: ExecutionIndices,
         transaction: Self::Transaction,
     ) -> Result<Vec<u8>, Self::Error> {
-        let ConsensusTransaction::UserTransaction(certificate) = transaction;
-
-        // Ensure an idempotent answer.
-        let digest = certificate.digest();
-        if self._database.effects_exists(digest)? {
-            let info = self.make_transaction_info(digest).await?;
-            debug!("Shared-object transaction {digest:?} already executed");
-            return Ok(bincode::serialize(&info).unwrap());
-        }
-
-        // Assign locks to shared objects.
-        self.handle_consensus_certificate(certificate.clone(), execution_indices)
-            .await?;
-        debug!("Shared objects locks successfully attributed to transaction {digest:?}");
-
-        // Attempt to execute the transaction. This will only succeed if the authority
-        // already executed all its dependencies.
-        let confirmation_transaction = ConfirmationTransaction {
-            certificate: certificate.clone(),
+        let info = match transaction {
+            ConsensusTransaction::UserTransaction(certificate) => {
+                self.handle_sequenced_certificate(certificate, execution_indices)
+                    .await?
+            }
+            ConsensusTransaction::EpochCertificates(certificates) => {
+                // Every authority executes the same sequence, a certificate failing here
+                // is skipped.
+                for certificate in certificates {
+                    let digest = *certificate.digest();
+                    let confirmation_transaction = ConfirmationTransaction { certificate };
+                    if let Err(e) = self
+                        .confirm_certificate(confirmation_transaction, true)
+                        .await
+                    {
+                        debug!("Failed to execute the sequenced transaction {digest:?}: {e}");
+                    }
+                }
+                TransactionInfoResponse {
+                    signed_transaction: None,
+                    certified_transaction: None,
+                    signed_effects: None,
+                }
+            }
+            ConsensusTransaction::EndOfEpoch(end_of_epoch) => {
+                end_of_epoch.verify(&self.committee())?;
+                let EndOfEpoch { epoch, authority } = end_of_epoch.end_of_epoch;
+                self._database
+                    .record_end_of_epoch(epoch, authority, execution_indices)?;
+                debug!("Authority {authority:?} sealed epoch {epoch}");
+                TransactionInfoResponse {
+                    signed_transaction: None,
+                    certified_transaction: None,
+                    signed_effects: None,
+                }
+            }
         };
-        let info = self
-            .handle_confirmation_transaction(confirmation_transaction.clone())
-            .await?;
-        debug!("Executed transaction {digest:?}");
 
         // Return a serialized transaction info response. This will be sent back to the client.
         Ok(bincode::serialize(&info).unwrap())
@@ -1021,3 +1504,64 @@ impl ExecutionState for AuthorityState {
         self._database.last_consensus_index()
     }
 }
+
+/// Persist the Genesis package to a store along with the side effects for module
+/// initialization. This is shared by authorities and replicas.
+pub async fn store_package_and_init_modules_for_genesis<const A: bool, S>(
+    store: &Arc<SuiDataStore<A, S>>,
+    native_functions: &NativeFunctionTable,
+    ctx: &mut TxContext,
+    modules: Vec<CompiledModule>,
+) -> SuiResult
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let inputs = Transaction::input_objects_in_compiled_modules(&modules);
+    let ids: Vec<_> = inputs.iter().map(|kind| kind.object_id()).collect();
+    let input_objects = store.get_objects(&ids[..])?;
+    // When publishing genesis packages, since the std framework packages all have
+    // non-zero addresses, [`Transaction::input_objects_in_compiled_modules`] will consider
+    // them as dependencies even though they are not. Hence input_objects contain objects
+    // that don't exist on-chain because they are yet to be published.
+    #[cfg(debug_assertions)]
+    {
+        let to_be_published_addresses: HashSet<_> = modules
+            .iter()
+            .map(|module| *module.self_id().address())
+            .collect();
+        assert!(
+            // An object either exists on-chain, or is one of the packages to be published.
+            inputs
+                .iter()
+                .zip(input_objects.iter())
+                .all(|(kind, obj_opt)| obj_opt.is_some()
+                    || to_be_published_addresses.contains(&kind.object_id()))
+        );
+    }
+    let filtered = inputs
+        .into_iter()
+        .zip(input_objects.into_iter())
+        .filter_map(|(input, object_opt)| object_opt.map(|object| (input, object)))
+        .collect::<Vec<_>>();
+
+    debug_assert!(ctx.digest() == TransactionDigest::genesis());
+    let mut temporary_store = AuthorityTemporaryStore::new(store.clone(), filtered, ctx.digest());
+    let package_id = ObjectID::from(*modules[0].self_id().address());
+    let natives = native_functions.clone();
+    let mut gas_status = SuiGasStatus::new_unmetered();
+    let vm = adapter::verify_and_link(
+        &temporary_store,
+        &modules,
+        package_id,
+        natives,
+        &mut gas_status,
+    )?;
+    adapter::store_package_and_init_modules(
+        &mut temporary_store,
+        &vm,
+        modules,
+        ctx,
+        &mut gas_status,
+    )?;
+    store.update_objects_state_for_genesis(temporary_store, ctx.digest())
+}
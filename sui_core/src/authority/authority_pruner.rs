@@ -0,0 +1,88 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::AuthorityState;
+use std::collections::VecDeque;
+use std::time::Duration;
+use sui_config::{PruningConfig, RetentionPolicy};
+use sui_types::{batch::TxSequenceNumber, error::SuiResult};
+use tokio::time::interval;
+use tracing::{debug, error, info};
+use typed_store::Map;
+
+#[cfg(test)]
+#[path = "../unit_tests/pruner_tests.rs"]
+mod pruner_tests;
+
+/*
+
+The authority store keeps certificates, effects and the transaction sequence of every
+executed transaction. The pruner is a background task that periodically deletes the
+data of transactions older than a watermark derived from the retention policy.
+
+The watermark is always a batch boundary: all batches and transactions before it are
+deleted, and the batch ending at it is kept, so batch streams (and followers) can still
+start at the watermark. Requests for sequences before it are rejected. Transactions
+that are still the latest parent of an object are kept, since object info requests
+serve their certificates. Only the digest of the effects of pruned transactions is kept,
+so that submitting their certificates again is answered without executing them twice.
+
+*/
+
+impl AuthorityState {
+    /// Returns the sequence number before which the retention policy allows data
+    /// to be pruned. The store rounds it down to a batch boundary.
+    pub fn pruning_watermark(&self, retention: &RetentionPolicy) -> SuiResult<TxSequenceNumber> {
+        let watermark = match retention {
+            RetentionPolicy::Transactions(num_transactions) => self
+                .last_batch()?
+                .map(|last_batch| {
+                    last_batch
+                        .batch
+                        .next_sequence_number
+                        .saturating_sub(*num_transactions)
+                })
+                .unwrap_or(0),
+            RetentionPolicy::Batches(num_batches) => {
+                // Only keep the ends of the last `num_batches + 1` batches: the first of
+                // them is the boundary of the retained batches.
+                let window = *num_batches as usize + 1;
+                let mut batch_keys = VecDeque::with_capacity(window);
+                for next_sequence_number in self.db().batches.keys() {
+                    if batch_keys.len() == window {
+                        batch_keys.pop_front();
+                    }
+                    batch_keys.push_back(next_sequence_number);
+                }
+                match batch_keys.front() {
+                    Some(watermark) if batch_keys.len() == window => *watermark,
+                    _ => 0,
+                }
+            }
+        };
+        Ok(watermark)
+    }
+
+    /// Prunes the store once according to the retention policy, and returns the
+    /// number of transactions pruned.
+    pub fn prune(&self, retention: &RetentionPolicy) -> SuiResult<usize> {
+        let watermark = self.pruning_watermark(retention)?;
+        let num_pruned = self.db().prune_transactions_before(watermark)?;
+        self.metrics.pruned_transactions.inc_by(num_pruned as u64);
+        Ok(num_pruned)
+    }
+
+    /// Prunes the store every pruning period. A failed pass is logged and retried at the
+    /// next period, so the service never ends.
+    pub async fn run_pruner_service(&self, config: PruningConfig) {
+        info!(retention = ?config.retention, "Starting the pruner service");
+        let mut interval = interval(Duration::from_millis(config.pruning_period_ms));
+        loop {
+            interval.tick().await;
+            match self.prune(&config.retention) {
+                Ok(num_pruned) => debug!(num_pruned, "Pruned old transactions from the store"),
+                Err(err) => error!("Failed to prune old transactions from the store: {err}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::AuthorityState;
+use futures::future::join_all;
+use std::{collections::HashSet, sync::Arc};
+use sui_types::{
+    base_types::ObjectID,
+    error::{SuiError, SuiResult},
+    messages::{
+        CertifiedTransaction, ConfirmationTransaction, InputObjectKind, TransactionInfoResponse,
+    },
+};
+use tracing::{debug, error};
+
+#[cfg(test)]
+#[path = "../unit_tests/scheduler_tests.rs"]
+mod scheduler_tests;
+
+/*
+
+Certificates that do not share any input object are independent: executing them in any
+order, or at the same time, gives the same effects. The scheduler takes a list of
+certificates and executes them in waves. Each wave holds certificates that do not
+conflict with each other, nor with an earlier certificate of the list that is not yet
+executed, so the effects are the same as executing the list in order. The certificates
+of a wave execute on separate tasks, and hence on separate cores of the runtime, then
+their effects are committed one by one in the order of the list. This is also the order
+of the executed sequence.
+
+Packages and immutable objects are read-only, so they are not considered as conflicts.
+An object only becomes immutable once it is frozen, so an object which is immutable in the
+store when the wave is scheduled stays so while it executes.
+
+A certificate may depend on an object created by an earlier certificate of the list,
+which the scheduler cannot see. Its execution then fails, without side effects, and the
+failure is returned: the client submits the certificate again once its inputs exist, as
+it would if the certificates had arrived in separate requests.
+
+*/
+
+impl AuthorityState {
+    /// Executes a list of certificates, running the independent ones in parallel. The
+    /// effects are the same as handling the certificates one after the other, and the
+    /// results are returned in the same order as the certificates.
+    pub async fn handle_confirmation_transactions(
+        self: &Arc<Self>,
+        confirmation_transactions: Vec<ConfirmationTransaction>,
+    ) -> Vec<SuiResult<TransactionInfoResponse>> {
+        let _direct_execution = self.direct_execution.read().await;
+        let mut results: Vec<Option<SuiResult<TransactionInfoResponse>>> =
+            confirmation_transactions.iter().map(|_| None).collect();
+        let mut pending: Vec<(usize, CertifiedTransaction)> = confirmation_transactions
+            .into_iter()
+            .map(|confirmation_transaction| confirmation_transaction.certificate)
+            .enumerate()
+            .collect();
+
+        while !pending.is_empty() {
+            let (wave, deferred) = self.schedule_wave(pending, &mut results).await;
+            debug!(
+                wave_size = wave.len(),
+                num_deferred = deferred.len(),
+                "Executing a wave of independent certificates"
+            );
+
+            let indexes: Vec<_> = wave.iter().map(|(index, _)| *index).collect();
+            let executed = join_all(wave.into_iter().map(|(_, certificate)| {
+                let state = self.clone();
+                tokio::task::spawn(async move {
+                    state.metrics.total_certs.inc();
+                    match certificate.verify(&state.committee()) {
+                        Ok(()) => state.execute_certificate(certificate).await,
+                        Err(err) => {
+                            state.metrics.signature_errors.inc();
+                            Err(err)
+                        }
+                    }
+                })
+            }))
+            .await;
+
+            // Commit in the order of the list. A task that panicked only fails its own
+            // certificate.
+            for (index, join_result) in indexes.into_iter().zip(executed) {
+                let result = match join_result {
+                    Ok(Ok(executed)) => self.commit_certificate(executed).await,
+                    Ok(Err(err)) => Err(err),
+                    Err(join_error) => {
+                        error!(?join_error, "Certificate execution task failed");
+                        Err(SuiError::GenericAuthorityError {
+                            error: format!("Certificate execution task failed: {}", join_error),
+                        })
+                    }
+                };
+                results[index] = Some(result);
+            }
+            pending = deferred;
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("All certificates are handled"))
+            .collect()
+    }
+
+    /// Splits the pending certificates into a wave of independent certificates, and the
+    /// ones deferred to a later wave. Certificates already executed, or whose inputs are
+    /// invalid, get their result right away.
+    async fn schedule_wave(
+        &self,
+        pending: Vec<(usize, CertifiedTransaction)>,
+        results: &mut [Option<SuiResult<TransactionInfoResponse>>],
+    ) -> (
+        Vec<(usize, CertifiedTransaction)>,
+        Vec<(usize, CertifiedTransaction)>,
+    ) {
+        let mut wave = Vec::new();
+        let mut deferred = Vec::new();
+        // The objects used by the certificates of the wave, or by deferred certificates,
+        // which must execute before any later certificate using the same objects.
+        let mut used_objects: HashSet<ObjectID> = HashSet::new();
+
+        for (index, certificate) in pending {
+            let digest = certificate.digest();
+            match self._database.effects_exists(digest) {
+                // As for a single certificate, a failed reconfiguration is retried.
+                Ok(true) => {
+                    let result = match self.reconfigure_after_epoch_change(&certificate).await {
+                        Ok(()) => self.make_transaction_info(digest).await,
+                        Err(err) => Err(err),
+                    };
+                    results[index] = Some(result);
+                    continue;
+                }
+                Ok(false) => (),
+                Err(err) => {
+                    results[index] = Some(Err(err));
+                    continue;
+                }
+            }
+
+            if let Err(err) = self.check_direct_execution(&certificate) {
+                results[index] = Some(Err(err));
+                continue;
+            }
+
+            let input_objects = match self.mutable_input_objects(&certificate) {
+                Ok(input_objects) => input_objects,
+                Err(err) => {
+                    results[index] = Some(Err(err));
+                    continue;
+                }
+            };
+
+            let conflicts = input_objects.iter().any(|id| used_objects.contains(id));
+            used_objects.extend(input_objects);
+            if conflicts {
+                deferred.push((index, certificate));
+            } else {
+                wave.push((index, certificate));
+            }
+        }
+
+        (wave, deferred)
+    }
+
+    /// Returns the input objects of the certificate which other certificates may conflict
+    /// on, leaving out packages and the objects which are immutable in the store.
+    fn mutable_input_objects(
+        &self,
+        certificate: &CertifiedTransaction,
+    ) -> SuiResult<Vec<ObjectID>> {
+        let mut input_objects = Vec::new();
+        for kind in certificate.data.input_objects()? {
+            match kind {
+                InputObjectKind::MovePackage(_) => (),
+                InputObjectKind::ImmOrOwnedMoveObject((object_id, _, _)) => {
+                    let immutable = self
+                        ._database
+                        .get_object(&object_id)?
+                        .map_or(false, |object| object.is_immutable());
+                    if !immutable {
+                        input_objects.push(object_id);
+                    }
+                }
+                InputObjectKind::SharedMoveObject(object_id) => input_objects.push(object_id),
+            }
+        }
+        Ok(input_objects)
+    }
+}
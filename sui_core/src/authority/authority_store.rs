@@ -5,20 +5,28 @@ use crate::gateway_state::GatewayTxSeqNumber;
 use narwhal_executor::ExecutionIndices;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::any::TypeId;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryInto;
 use std::path::Path;
 use sui_types::base_types::SequenceNumber;
 use sui_types::batch::{SignedBatch, TxSequenceNumber};
-use sui_types::crypto::{AuthoritySignInfo, EmptySignInfo};
-use sui_types::object::OBJECT_START_VERSION;
-use tracing::warn;
+use sui_types::crypto::{sha3_hash, AuthoritySignInfo, EmptySignInfo};
+use sui_types::event::{EventEnvelope, EventFilter, EventID};
+use sui_types::gas::{GasCostSummary, SuiCostSchedule, INITIAL_SUI_COST_SCHEDULE};
+use sui_types::object::{Owner, OBJECT_START_VERSION};
+use sui_types::snapshot::StateSnapshot;
+use sui_types::state_commitment::{
+    root_key, update_state_tree, ObjectProof, StateDigest, StateNode, StateNodeKey,
+    EMPTY_STATE_DIGEST,
+};
+use sui_types::sui_system_state::{GasSchedule, SUI_GAS_SCHEDULE_OBJECT_ID};
+use tracing::{debug, warn};
 use typed_store::rocks::{DBBatch, DBMap};
 
 use typed_store::{reopen, traits::Map};
 
 pub type AuthorityStore = SuiDataStore<false, AuthoritySignInfo>;
-#[allow(dead_code)]
 pub type ReplicaStore = SuiDataStore<true, EmptySignInfo>;
 pub type GatewayStore = SuiDataStore<false, EmptySignInfo>;
 
@@ -42,7 +50,7 @@ pub struct SuiDataStore<const ALL_OBJ_VER: bool, S> {
 
     /// Stores all history versions of all objects.
     /// This is not needed by an authority, but is needed by a replica.
-    #[allow(dead_code)]
+    /// It is only written to when ALL_OBJ_VER is true.
     all_object_versions: DBMap<(ObjectID, SequenceNumber), Object>,
 
     /// This is a map between object references of currently active objects that can be mutated,
@@ -100,6 +108,77 @@ pub struct SuiDataStore<const ALL_OBJ_VER: bool, S> {
     /// A sequence of batches indexing into the sequence of executed transactions.
     pub batches: DBMap<TxSequenceNumber, SignedBatch>,
 
+    /// The nodes of the sparse Merkle tree committing to the reference of every live object
+    /// (see `sui_types::state_commitment`). Only authorities maintain it, since they sign its
+    /// root in their batches. It is brought up to date when a batch is made, so it lags behind
+    /// `objects` by the transactions executed since the last batch.
+    state_tree: DBMap<StateNodeKey, StateNode>,
+
+    /// The changes to the live objects made by the transactions of the executed sequence
+    /// which are not yet applied to the state tree.
+    state_tree_updates: DBMap<TxSequenceNumber, Vec<(ObjectID, Option<ObjectRef>)>>,
+
+    /// The root of the state tree after the execution of the transactions sequenced up to
+    /// each batch end.
+    state_roots: DBMap<TxSequenceNumber, StateDigest>,
+
+    /// The digest of the effects of the transactions pruned from the executed sequence, so
+    /// that certificates submitted again still get an idempotent answer.
+    pruned_effects: DBMap<TransactionDigest, [u8; 32]>,
+
+    /// The events emitted by the transactions in the executed sequence, keyed by the
+    /// sequence number of the transaction and the index of the event in its effects.
+    events: DBMap<EventID, EventEnvelope>,
+
+    /// Indexes of `events` by Move event type, by sender of the transaction, by transaction
+    /// digest, and by the objects created, mutated, unwrapped, deleted or wrapped by the
+    /// transaction. Only the keys are meaningful.
+    events_by_type: DBMap<(StructTag, EventID), ()>,
+    events_by_sender: DBMap<(SuiAddress, EventID), ()>,
+    events_by_transaction: DBMap<(TransactionDigest, EventID), ()>,
+    events_by_object: DBMap<(ObjectID, EventID), ()>,
+
+    /// Indexes of the executed sequence by sender of the transaction, by recipient (the
+    /// owners of the objects created, mutated or unwrapped by the transaction, other than
+    /// the sender), and by the objects created, mutated, unwrapped, deleted or wrapped by
+    /// the transaction.
+    transactions_by_sender: DBMap<(SuiAddress, TxSequenceNumber), TransactionDigest>,
+    transactions_by_recipient: DBMap<(SuiAddress, TxSequenceNumber), TransactionDigest>,
+    transactions_by_object: DBMap<(ObjectID, TxSequenceNumber), TransactionDigest>,
+
+    /// The gas fees collected from each transaction, keyed by the epoch it was certified in.
+    /// The fees of an epoch are distributed as staking rewards when the epoch changes.
+    epoch_gas_charges: DBMap<(EpochId, TransactionDigest), GasCostSummary>,
+
+    /// The version and the cost schedule in effect at each epoch this authority went
+    /// through, recorded when it moves to the epoch. Certificates are metered with the
+    /// schedule of the epoch they were certified in.
+    cost_schedules: DBMap<EpochId, (u64, SuiCostSchedule)>,
+
+    /// The next sequence number to request from each authority a replica follows, so that
+    /// it resumes where it stopped.
+    follower_next_sequence_numbers: DBMap<AuthorityName, TxSequenceNumber>,
+
+    /// The certificates without shared objects executed by an authority, keyed by the epoch
+    /// they were certified in and their sequence number. They are sequenced through consensus
+    /// at the end of the epoch, so that every authority executes them.
+    epoch_certificates: DBMap<(EpochId, TxSequenceNumber), TransactionDigest>,
+
+    /// The authorities which sealed each epoch, in the order consensus sequenced their
+    /// announcements.
+    end_of_epoch: DBMap<(EpochId, AuthorityName), ()>,
+
+    /// Serializes the updates to the state tree, since each one reads the nodes
+    /// written by the previous ones.
+    state_tree_lock: parking_lot::Mutex<()>,
+
+    /// Whether the store maintains the state tree, which only authorities do.
+    state_commitment: bool,
+
+    /// Whether the store indexes the transactions and the events it executes. Only the
+    /// stores serving client queries do: gateways, and the replicas of full nodes.
+    indexes: bool,
+
     /// The following table is used to store a single value (the corresponding key is a constant). The value
     /// represents the index of the latest consensus message this authority processed. This field is written
     /// by a single process acting as consensus (light) client. It is used to ensure the authority processes
@@ -111,7 +190,10 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
     SuiDataStore<ALL_OBJ_VER, S>
 {
     /// Open an authority store by directory path
-    pub fn open<P: AsRef<Path>>(path: P, db_options: Option<Options>) -> Self {
+    pub fn open<P: AsRef<Path>>(path: P, db_options: Option<Options>) -> Self
+    where
+        S: 'static,
+    {
         let mut options = db_options.unwrap_or_default();
 
         // One common issue when running tests on Mac is that the default ulimit is too low,
@@ -150,6 +232,23 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
                 ("schedule", &options),
                 ("executed_sequence", &options),
                 ("batches", &options),
+                ("state_tree", &point_lookup),
+                ("state_tree_updates", &options),
+                ("state_roots", &options),
+                ("pruned_effects", &point_lookup),
+                ("events", &point_lookup),
+                ("events_by_type", &options),
+                ("events_by_sender", &options),
+                ("events_by_transaction", &options),
+                ("events_by_object", &options),
+                ("transactions_by_sender", &options),
+                ("transactions_by_recipient", &options),
+                ("transactions_by_object", &options),
+                ("epoch_gas_charges", &options),
+                ("cost_schedules", &options),
+                ("follower_next_sequence_numbers", &options),
+                ("epoch_certificates", &options),
+                ("end_of_epoch", &options),
                 ("last_consensus_index", &options),
             ];
             typed_store::rocks::open_cf_opts(path, db_options, opt_cfs)
@@ -171,6 +270,23 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
             sequenced,
             schedule,
             batches,
+            state_tree,
+            state_tree_updates,
+            state_roots,
+            pruned_effects,
+            events,
+            events_by_type,
+            events_by_sender,
+            events_by_transaction,
+            events_by_object,
+            transactions_by_sender,
+            transactions_by_recipient,
+            transactions_by_object,
+            epoch_gas_charges,
+            cost_schedules,
+            follower_next_sequence_numbers,
+            epoch_certificates,
+            end_of_epoch,
             last_consensus_index,
         ) = reopen! (
             &db,
@@ -185,6 +301,23 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
             "sequenced";<(TransactionDigest, ObjectID), SequenceNumber>,
             "schedule";<ObjectID, SequenceNumber>,
             "batches";<TxSequenceNumber, SignedBatch>,
+            "state_tree";<StateNodeKey, StateNode>,
+            "state_tree_updates";<TxSequenceNumber, Vec<(ObjectID, Option<ObjectRef>)>>,
+            "state_roots";<TxSequenceNumber, StateDigest>,
+            "pruned_effects";<TransactionDigest, [u8; 32]>,
+            "events";<EventID, EventEnvelope>,
+            "events_by_type";<(StructTag, EventID), ()>,
+            "events_by_sender";<(SuiAddress, EventID), ()>,
+            "events_by_transaction";<(TransactionDigest, EventID), ()>,
+            "events_by_object";<(ObjectID, EventID), ()>,
+            "transactions_by_sender";<(SuiAddress, TxSequenceNumber), TransactionDigest>,
+            "transactions_by_recipient";<(SuiAddress, TxSequenceNumber), TransactionDigest>,
+            "transactions_by_object";<(ObjectID, TxSequenceNumber), TransactionDigest>,
+            "epoch_gas_charges";<(EpochId, TransactionDigest), GasCostSummary>,
+            "cost_schedules";<EpochId, (u64, SuiCostSchedule)>,
+            "follower_next_sequence_numbers";<AuthorityName, TxSequenceNumber>,
+            "epoch_certificates";<(EpochId, TxSequenceNumber), TransactionDigest>,
+            "end_of_epoch";<(EpochId, AuthorityName), ()>,
             "last_consensus_index";<u64, ExecutionIndices>
         );
         Self {
@@ -204,6 +337,28 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
                 .collect(),
             executed_sequence,
             batches,
+            state_tree,
+            state_tree_updates,
+            state_roots,
+            pruned_effects,
+            events,
+            events_by_type,
+            events_by_sender,
+            events_by_transaction,
+            events_by_object,
+            transactions_by_sender,
+            transactions_by_recipient,
+            transactions_by_object,
+            epoch_gas_charges,
+            cost_schedules,
+            follower_next_sequence_numbers,
+            epoch_certificates,
+            end_of_epoch,
+            state_tree_lock: parking_lot::Mutex::new(()),
+            // Only authorities sign the state root, in their batches, and they do not serve
+            // queries by address, object or event.
+            state_commitment: TypeId::of::<S>() == TypeId::of::<AuthoritySignInfo>(),
+            indexes: TypeId::of::<S>() != TypeId::of::<AuthoritySignInfo>(),
             last_consensus_index,
         }
     }
@@ -221,11 +376,11 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
             })
     }
 
-    /// Returns true if we have an effects structure for this transaction digest
+    /// Returns true if we have an effects structure for this transaction digest, or if the
+    /// transaction was executed and its effects pruned since.
     pub fn effects_exists(&self, transaction_digest: &TransactionDigest) -> SuiResult<bool> {
-        self.effects
-            .contains_key(transaction_digest)
-            .map_err(|e| e.into())
+        Ok(self.effects.contains_key(transaction_digest)?
+            || self.pruned_effects.contains_key(transaction_digest)?)
     }
 
     /// Returns true if we have a transaction structure for this transaction digest
@@ -246,13 +401,23 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
     }
 
     pub fn next_sequence_number(&self) -> Result<TxSequenceNumber, SuiError> {
-        Ok(self
+        let next_executed = self
             .executed_sequence
             .iter()
             .skip_prior_to(&TxSequenceNumber::MAX)?
             .next()
             .map(|(v, _)| v + 1u64)
-            .unwrap_or(0))
+            .unwrap_or(0);
+        // The sequence may have been pruned, or the store bootstrapped from a snapshot,
+        // in which case the last batch tells us where the sequence continues.
+        let next_batched = self
+            .batches
+            .iter()
+            .skip_prior_to(&TxSequenceNumber::MAX)?
+            .next()
+            .map(|(next_sequence_number, _)| next_sequence_number)
+            .unwrap_or(0);
+        Ok(std::cmp::max(next_executed, next_batched))
     }
 
     #[cfg(test)]
@@ -293,11 +458,76 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
             .collect())
     }
 
+    /// Returns up to `limit` objects owned by `account`, in object ID order, starting at
+    /// `cursor` (included), along with the ID of the next object if there are more.
+    ///
+    /// If `object_type` is given, only the Move objects of this type are returned. A type
+    /// without type parameters, such as `0x2::Coin::Coin`, matches all its instantiations.
+    pub fn get_account_objects_page(
+        &self,
+        account: SuiAddress,
+        object_type: Option<&StructTag>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<(Vec<(ObjectRef, Object)>, Option<ObjectID>)> {
+        let mut objects = Vec::new();
+        for ((_owner, object_id), object_ref) in self
+            .owner_index
+            .iter()
+            .skip_to(&(account, cursor.unwrap_or(ObjectID::ZERO)))?
+            .take_while(|((owner, _id), _object_ref)| owner == &account)
+        {
+            let object = match self.objects.get(&object_id)? {
+                Some(object) => object,
+                None => continue,
+            };
+            let type_matches = match (object_type, object.type_()) {
+                (None, _) => true,
+                (Some(expected), Some(actual)) => {
+                    expected == actual
+                        || (expected.type_params.is_empty()
+                            && expected.address == actual.address
+                            && expected.module == actual.module
+                            && expected.name == actual.name)
+                }
+                (Some(_), None) => false,
+            };
+            if !type_matches {
+                continue;
+            }
+            if objects.len() == limit {
+                return Ok((objects, Some(object_id)));
+            }
+            objects.push((object_ref, object));
+        }
+        Ok((objects, None))
+    }
+
     /// Read an object and return it, or Err(ObjectNotFound) if the object was not found.
     pub fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
         self.objects.get(object_id).map_err(|e| e.into())
     }
 
+    /// Read an object at a specific version.
+    ///
+    /// Stores that keep all object versions (ALL_OBJ_VER) serve any version ever written,
+    /// while other stores can only serve the version currently in the `objects` table.
+    pub fn get_object_by_version(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, SuiError> {
+        if ALL_OBJ_VER {
+            return self
+                .all_object_versions
+                .get(&(*object_id, version))
+                .map_err(|e| e.into());
+        }
+        Ok(self
+            .get_object(object_id)?
+            .filter(|object| object.version() == version))
+    }
+
     /// Get many objects
     pub fn get_objects(&self, _objects: &[ObjectID]) -> Result<Vec<Option<Object>>, SuiError> {
         self.objects.multi_get(_objects).map_err(|e| e.into())
@@ -412,6 +642,10 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
     pub fn insert_object_direct(&self, object_ref: ObjectRef, object: &Object) -> SuiResult {
         // Insert object
         self.objects.insert(&object_ref.0, object)?;
+        if ALL_OBJ_VER {
+            self.all_object_versions
+                .insert(&(object_ref.0, object_ref.1), object)?;
+        }
 
         self.transaction_lock.get_or_insert(&object_ref, || None)?;
 
@@ -424,6 +658,16 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
         self.parent_sync
             .insert(&object_ref, &object.previous_transaction)?;
 
+        // Update the state commitment
+        if self.state_commitment {
+            let _tree_guard = self.state_tree_lock.lock();
+            let (write_batch, _) = self.batch_update_state_tree(
+                self.state_tree.batch(),
+                std::iter::once((object_ref.0, Some(object_ref))),
+            )?;
+            write_batch.write()?;
+        }
+
         Ok(())
     }
 
@@ -431,13 +675,33 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
     /// In particular it does not check the old locks before inserting new ones, so the objects
     /// must be new.
     pub fn bulk_object_insert(&self, objects: &[&Object]) -> SuiResult<()> {
-        let batch = self.objects.batch();
+        let batch = self.bulk_object_insert_batch(self.objects.batch(), objects)?;
+        if !self.state_commitment {
+            batch.write()?;
+            return Ok(());
+        }
+
+        let _tree_guard = self.state_tree_lock.lock();
+        let (batch, _) = self.batch_update_state_tree(
+            batch,
+            objects.iter().map(|o| {
+                let oref = o.compute_object_reference();
+                (oref.0, Some(oref))
+            }),
+        )?;
+        batch.write()?;
+
+        Ok(())
+    }
+
+    /// Adds to `batch` the writes inserting new objects, except for the state tree.
+    fn bulk_object_insert_batch(&self, batch: DBBatch, objects: &[&Object]) -> SuiResult<DBBatch> {
         let ref_and_objects: Vec<_> = objects
             .iter()
             .map(|o| (o.compute_object_reference(), o))
             .collect();
 
-        batch
+        let mut batch = batch
             .insert_batch(
                 &self.objects,
                 ref_and_objects.iter().map(|(oref, o)| (oref.0, **o)),
@@ -458,10 +722,18 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
                 ref_and_objects
                     .iter()
                     .map(|(oref, o)| (oref, o.previous_transaction)),
-            )?
-            .write()?;
+            )?;
 
-        Ok(())
+        if ALL_OBJ_VER {
+            batch = batch.insert_batch(
+                &self.all_object_versions,
+                ref_and_objects
+                    .iter()
+                    .map(|(oref, o)| ((oref.0, oref.1), **o)),
+            )?;
+        }
+
+        Ok(batch)
     }
 
     /// Set the transaction lock to a specific transaction
@@ -528,6 +800,31 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
         Ok(())
     }
 
+    /// Release the locks set to a transaction that will not be executed, and forget it.
+    ///
+    /// Only the locks still held by `transaction_digest` are reset to None, the locks of
+    /// objects that were since updated or locked to another transaction are left alone.
+    pub fn release_transaction_lock(
+        &self,
+        owned_input_objects: &[ObjectRef],
+        transaction_digest: &TransactionDigest,
+    ) -> SuiResult {
+        let _mutexes = self.acquire_locks(owned_input_objects.iter());
+
+        let locks = self.transaction_lock.multi_get(owned_input_objects)?;
+        let released = owned_input_objects
+            .iter()
+            .zip(locks)
+            .filter(|(_, lock)| *lock == Some(Some(*transaction_digest)))
+            .map(|(obj_ref, _)| (obj_ref, None::<TransactionDigest>));
+        self.transaction_lock
+            .batch()
+            .insert_batch(&self.transaction_lock, released)?
+            .delete_batch(&self.transactions, std::iter::once(transaction_digest))?
+            .write()?;
+        Ok(())
+    }
+
     /// Updates the state resulting from the execution of a certificate.
     ///
     /// Internally it checks that all locks for active inputs are at the correct
@@ -556,16 +853,41 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
             std::iter::once((transaction_digest, effects)),
         )?;
 
+        // Index the transaction, and the events it emitted.
+        if let Some(seq) = sequence_number {
+            if self.indexes {
+                write_batch =
+                    self.batch_index_transaction(write_batch, seq, certificate, &effects.effects)?;
+                write_batch =
+                    self.batch_index_events(write_batch, seq, certificate, &effects.effects)?;
+            }
+            if !certificate.contains_shared_object() {
+                write_batch = write_batch.insert_batch(
+                    &self.epoch_certificates,
+                    std::iter::once(((certificate.auth_sign_info.epoch, seq), *transaction_digest)),
+                )?;
+            }
+        }
+
         // Cleanup the lock of the shared objects.
         let write_batch =
             self.remove_shared_objects_locks(write_batch, transaction_digest, certificate)?;
 
+        // The fees of the transaction changing the epoch are only collected once it is
+        // executed, so they are distributed at the end of the next epoch.
+        let mut epoch = certificate.auth_sign_info.epoch;
+        if certificate.data.is_change_epoch() {
+            epoch += 1;
+        }
+        let gas_charges = (epoch, effects.effects.status.gas_cost_summary());
+
         // Safe to unwrap since the "true" flag ensures we get a sequence value back.
         self.batch_update_objects(
             write_batch,
             temporary_store,
             *transaction_digest,
             sequence_number,
+            Some(gas_charges),
         )
     }
 
@@ -577,7 +899,7 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
     ) -> Result<(), SuiError> {
         debug_assert_eq!(transaction_digest, TransactionDigest::genesis());
         let write_batch = self.transaction_lock.batch();
-        self.batch_update_objects(write_batch, temporary_store, transaction_digest, None)
+        self.batch_update_objects(write_batch, temporary_store, transaction_digest, None, None)
     }
 
     /// This is used by the Gateway to update its local store after a transaction succeeded
@@ -612,6 +934,16 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
             std::iter::once((transaction_digest, &certificate)),
         )?;
 
+        // Index the transaction, and the events it emitted.
+        write_batch = self.batch_index_transaction(
+            write_batch,
+            sequence_number,
+            &certificate,
+            &effects.effects,
+        )?;
+        write_batch =
+            self.batch_index_events(write_batch, sequence_number, &certificate, &effects.effects)?;
+
         // Store the unsigned effects of the transaction
         write_batch = write_batch.insert_batch(
             &self.effects,
@@ -628,9 +960,292 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
             temporary_store,
             *transaction_digest,
             Some(sequence_number),
+            None,
         )
     }
 
+    /// Adds to the write batch the indexes of a transaction executed at sequence number
+    /// `seq`, by sender, by recipient and by object.
+    fn batch_index_transaction(
+        &self,
+        write_batch: DBBatch,
+        seq: TxSequenceNumber,
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+    ) -> SuiResult<DBBatch> {
+        let digest = *certificate.digest();
+        let sender = certificate.data.signer();
+        Ok(write_batch
+            .insert_batch(
+                &self.transactions_by_sender,
+                std::iter::once(((sender, seq), digest)),
+            )?
+            .insert_batch(
+                &self.transactions_by_recipient,
+                Self::transaction_recipients(certificate, effects)
+                    .into_iter()
+                    .map(|recipient| ((recipient, seq), digest)),
+            )?
+            .insert_batch(
+                &self.transactions_by_object,
+                effects
+                    .touched_object_ids()
+                    .into_iter()
+                    .map(|object_id| ((object_id, seq), digest)),
+            )?)
+    }
+
+    /// The owners of the objects created, mutated or unwrapped by a transaction, other than
+    /// its sender.
+    fn transaction_recipients(
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+    ) -> BTreeSet<SuiAddress> {
+        let sender = certificate.data.signer();
+        effects
+            .mutated_and_created()
+            .chain(effects.unwrapped.iter())
+            .filter_map(|(_, owner)| match owner {
+                Owner::AddressOwner(address) if *address != sender => Some(*address),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Adds to the write batch the deletion of the index entries written by
+    /// `batch_index_transaction` and `batch_index_events` for a transaction executed at
+    /// sequence number `seq`. The events themselves are left to the caller.
+    fn batch_unindex_transaction(
+        &self,
+        write_batch: DBBatch,
+        seq: TxSequenceNumber,
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+    ) -> SuiResult<DBBatch> {
+        let object_ids = effects.touched_object_ids();
+        let events = EventEnvelope::from_transaction(
+            seq,
+            *certificate.digest(),
+            certificate.data.signer(),
+            &effects.events,
+        );
+        Ok(write_batch
+            .delete_batch(
+                &self.transactions_by_sender,
+                std::iter::once((certificate.data.signer(), seq)),
+            )?
+            .delete_batch(
+                &self.transactions_by_recipient,
+                Self::transaction_recipients(certificate, effects)
+                    .into_iter()
+                    .map(|recipient| (recipient, seq)),
+            )?
+            .delete_batch(
+                &self.transactions_by_object,
+                object_ids.iter().map(|object_id| (*object_id, seq)),
+            )?
+            .delete_batch(
+                &self.events_by_type,
+                events
+                    .iter()
+                    .map(|event| (event.event.type_.clone(), event.id)),
+            )?
+            .delete_batch(
+                &self.events_by_sender,
+                events.iter().map(|event| (event.sender, event.id)),
+            )?
+            .delete_batch(
+                &self.events_by_transaction,
+                events.iter().map(|event| (event.tx_digest, event.id)),
+            )?
+            .delete_batch(
+                &self.events_by_object,
+                events.iter().flat_map(|event| {
+                    object_ids
+                        .iter()
+                        .map(move |object_id| (*object_id, event.id))
+                }),
+            )?)
+    }
+
+    /// Adds to the write batch the events emitted by a transaction executed at sequence
+    /// number `seq`, along with their indexes.
+    fn batch_index_events(
+        &self,
+        write_batch: DBBatch,
+        seq: TxSequenceNumber,
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+    ) -> SuiResult<DBBatch> {
+        let events = EventEnvelope::from_transaction(
+            seq,
+            *certificate.digest(),
+            certificate.data.signer(),
+            &effects.events,
+        );
+        if events.is_empty() {
+            return Ok(write_batch);
+        }
+        let object_ids = effects.touched_object_ids();
+        Ok(write_batch
+            .insert_batch(&self.events, events.iter().map(|event| (event.id, event)))?
+            .insert_batch(
+                &self.events_by_type,
+                events
+                    .iter()
+                    .map(|event| ((event.event.type_.clone(), event.id), ())),
+            )?
+            .insert_batch(
+                &self.events_by_sender,
+                events.iter().map(|event| ((event.sender, event.id), ())),
+            )?
+            .insert_batch(
+                &self.events_by_transaction,
+                events.iter().map(|event| ((event.tx_digest, event.id), ())),
+            )?
+            .insert_batch(
+                &self.events_by_object,
+                events.iter().flat_map(|event| {
+                    object_ids
+                        .iter()
+                        .map(move |object_id| ((*object_id, event.id), ()))
+                }),
+            )?)
+    }
+
+    /// Returns up to `limit` transactions sent by or to `address`, in execution order,
+    /// starting at `cursor` (included), along with the sequence number of the next one if
+    /// there are more.
+    pub fn get_transactions_by_address(
+        &self,
+        address: SuiAddress,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+    ) -> SuiResult<(
+        Vec<(TxSequenceNumber, TransactionDigest)>,
+        Option<TxSequenceNumber>,
+    )> {
+        let start = (address, cursor.unwrap_or(0));
+        // The first `limit + 1` transactions of the union are among the first `limit + 1`
+        // transactions of each index.
+        let mut transactions = BTreeMap::new();
+        for index in [
+            &self.transactions_by_sender,
+            &self.transactions_by_recipient,
+        ] {
+            transactions.extend(
+                index
+                    .iter()
+                    .skip_to(&start)?
+                    .take_while(|((key, _), _)| *key == address)
+                    .take(limit.saturating_add(1))
+                    .map(|((_, seq), digest)| (seq, digest)),
+            );
+        }
+        Ok(Self::paginate(transactions.into_iter(), limit))
+    }
+
+    /// Returns up to `limit` transactions which created, mutated, unwrapped, deleted or
+    /// wrapped `object_id`, in execution order, starting at `cursor` (included), along with
+    /// the sequence number of the next one if there are more.
+    pub fn get_transactions_by_object(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+    ) -> SuiResult<(
+        Vec<(TxSequenceNumber, TransactionDigest)>,
+        Option<TxSequenceNumber>,
+    )> {
+        let transactions = self
+            .transactions_by_object
+            .iter()
+            .skip_to(&(object_id, cursor.unwrap_or(0)))?
+            .take_while(|((key, _), _)| *key == object_id)
+            .map(|((_, seq), digest)| (seq, digest));
+        Ok(Self::paginate(transactions, limit))
+    }
+
+    /// Splits the first `limit` transactions out of `transactions`, along with the sequence
+    /// number of the next one if there are more.
+    fn paginate(
+        mut transactions: impl Iterator<Item = (TxSequenceNumber, TransactionDigest)>,
+        limit: usize,
+    ) -> (
+        Vec<(TxSequenceNumber, TransactionDigest)>,
+        Option<TxSequenceNumber>,
+    ) {
+        let page = transactions.by_ref().take(limit).collect();
+        (page, transactions.next().map(|(seq, _)| seq))
+    }
+
+    /// Returns up to `limit` events matching `filter`, in execution order, starting at
+    /// `cursor` (included), along with the ID of the next matching event if there are more.
+    pub fn get_events(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<EventID>,
+        limit: usize,
+    ) -> SuiResult<(Vec<EventEnvelope>, Option<EventID>)> {
+        let start = cursor.unwrap_or((0, 0));
+        // Walk the index of the most selective criterion, and check the others on the way.
+        let ids: Box<dyn Iterator<Item = EventID> + '_> = if let Some(digest) = filter.transaction {
+            Box::new(
+                self.events_by_transaction
+                    .iter()
+                    .skip_to(&(digest, start))?
+                    .take_while(move |((key, _), _)| *key == digest)
+                    .map(|((_, id), _)| id),
+            )
+        } else if let Some(object_id) = filter.object {
+            Box::new(
+                self.events_by_object
+                    .iter()
+                    .skip_to(&(object_id, start))?
+                    .take_while(move |((key, _), _)| *key == object_id)
+                    .map(|((_, id), _)| id),
+            )
+        } else if let Some(sender) = filter.sender {
+            Box::new(
+                self.events_by_sender
+                    .iter()
+                    .skip_to(&(sender, start))?
+                    .take_while(move |((key, _), _)| *key == sender)
+                    .map(|((_, id), _)| id),
+            )
+        } else if let Some(event_type) = &filter.event_type {
+            Box::new(
+                self.events_by_type
+                    .iter()
+                    .skip_to(&(event_type.clone(), start))?
+                    .take_while(move |((key, _), _)| key == event_type)
+                    .map(|((_, id), _)| id),
+            )
+        } else {
+            Box::new(self.events.iter().skip_to(&start)?.map(|(id, _)| id))
+        };
+
+        let mut events = Vec::new();
+        for id in ids {
+            let event = match self.events.get(&id)? {
+                Some(event) => event,
+                None => continue,
+            };
+            let object_matches = match filter.object {
+                Some(object_id) => self.events_by_object.contains_key(&(object_id, id))?,
+                None => true,
+            };
+            if !object_matches || !filter.matches_event(&event) {
+                continue;
+            }
+            if events.len() == limit {
+                return Ok((events, Some(id)));
+            }
+            events.push(event);
+        }
+        Ok((events, None))
+    }
+
     /// Helper function for updating the objects in the state
     fn batch_update_objects<BackingPackageStore>(
         &self,
@@ -638,6 +1253,7 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
         temporary_store: AuthorityTemporaryStore<BackingPackageStore>,
         transaction_digest: TransactionDigest,
         seq_opt: Option<TxSequenceNumber>,
+        gas_charges: Option<(EpochId, &GasCostSummary)>,
     ) -> Result<(), SuiError> {
         let (objects, active_inputs, written, deleted, _events) = temporary_store.into_inner();
 
@@ -747,7 +1363,43 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
                 .map(|(object_id, (_, new_object))| (object_id, new_object)),
         )?;
 
-        // Update the indexes of the objects written
+        // Record the gas fees collected.
+        if let Some((epoch, charges)) = gas_charges {
+            write_batch = write_batch.insert_batch(
+                &self.epoch_gas_charges,
+                std::iter::once(((epoch, transaction_digest), charges)),
+            )?;
+        }
+
+        // Record the changes to the live objects, which are applied to the state tree when
+        // the next batch is made, outside of the critical region below.
+        let mut tree_guard = None;
+        if self.state_commitment {
+            let tree_updates: Vec<_> = deleted
+                .iter()
+                .map(|(id, _)| (*id, None))
+                .chain(
+                    written
+                        .iter()
+                        .map(|(id, (object_ref, _))| (*id, Some(*object_ref))),
+                )
+                .collect();
+            match seq_opt {
+                Some(next_seq) => {
+                    write_batch = write_batch.insert_batch(
+                        &self.state_tree_updates,
+                        std::iter::once((next_seq, tree_updates)),
+                    )?;
+                }
+                None => {
+                    // Genesis objects are not sequenced, so they are applied right away.
+                    tree_guard = Some(self.state_tree_lock.lock());
+                    let (new_write_batch, _) =
+                        self.batch_update_state_tree(write_batch, tree_updates)?;
+                    write_batch = new_write_batch;
+                }
+            }
+        }
 
         // This is the critical region: testing the locks and writing the
         // new locks must be atomic, and no writes should happen in between.
@@ -781,10 +1433,224 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
 
             // implicit: drop(_mutexes);
         } // End of critical region
+        drop(tree_guard);
 
         Ok(())
     }
 
+    /// Adds to the write batch the changes to the state tree resulting from the given
+    /// updates to the live objects (`None` for removed objects), and returns the new
+    /// state root. The caller must hold `state_tree_lock` until the batch is written.
+    fn batch_update_state_tree(
+        &self,
+        write_batch: DBBatch,
+        updates: impl IntoIterator<Item = (ObjectID, Option<ObjectRef>)>,
+    ) -> SuiResult<(DBBatch, StateDigest)> {
+        let (state_root, changes) =
+            update_state_tree(|key| Ok(self.state_tree.get(key)?), updates)?;
+        let (removed, updated): (Vec<_>, Vec<_>) =
+            changes.into_iter().partition(|(_, node)| node.is_none());
+        let write_batch = write_batch
+            .delete_batch(&self.state_tree, removed.into_iter().map(|(key, _)| key))?
+            .insert_batch(
+                &self.state_tree,
+                updated
+                    .into_iter()
+                    .filter_map(|(key, node)| node.map(|node| (key, node))),
+            )?;
+        Ok((write_batch, state_root))
+    }
+
+    /// Applies to the state tree the changes made by the transactions sequenced before
+    /// `next_sequence_number`, which must all be committed, and records the state root
+    /// reached at the last of them. Returns the state root.
+    pub fn commit_state_tree(
+        &self,
+        next_sequence_number: TxSequenceNumber,
+    ) -> SuiResult<StateDigest> {
+        let _tree_guard = self.state_tree_lock.lock();
+        let pending: Vec<_> = self
+            .state_tree_updates
+            .iter()
+            .take_while(|(seq, _)| *seq < next_sequence_number)
+            .collect();
+        let last_seq = match pending.last() {
+            Some((seq, _)) => *seq,
+            None => return self.state_root(),
+        };
+        // The changes of later transactions override those of earlier ones.
+        let updates: BTreeMap<_, _> = pending
+            .iter()
+            .flat_map(|(_, updates)| updates.iter().copied())
+            .collect();
+        let (write_batch, state_root) =
+            self.batch_update_state_tree(self.state_tree.batch(), updates)?;
+        write_batch
+            .delete_batch(
+                &self.state_tree_updates,
+                pending.iter().map(|(seq, _)| *seq),
+            )?
+            .insert_batch(&self.state_roots, std::iter::once((last_seq, state_root)))?
+            .write()?;
+        Ok(state_root)
+    }
+
+    /// Returns the gas fees collected from the transactions certified in `epoch`.
+    pub fn epoch_gas_charges(&self, epoch: EpochId) -> SuiResult<GasCostSummary> {
+        let mut total = GasCostSummary::default();
+        for (_, charges) in self
+            .epoch_gas_charges
+            .iter()
+            .skip_to(&(epoch, TransactionDigest::new([0; 32])))?
+            .take_while(|((charges_epoch, _), _)| *charges_epoch == epoch)
+        {
+            total.accumulate(&charges);
+        }
+        Ok(total)
+    }
+
+    /// Returns the version and the cost schedule in effect at `epoch`, read from the gas
+    /// schedule. Stores created without a gas schedule use the initial cost schedule.
+    pub fn get_cost_schedule(&self, epoch: EpochId) -> SuiResult<(u64, SuiCostSchedule)> {
+        match self.get_object(&SUI_GAS_SCHEDULE_OBJECT_ID)? {
+            Some(object) => GasSchedule::from_object(&object)?.cost_schedule_at(epoch),
+            None => Ok((0, INITIAL_SUI_COST_SCHEDULE.clone())),
+        }
+    }
+
+    /// Returns the version and the cost schedule in effect at `epoch`. The first time an
+    /// epoch is asked for, its schedule is read from the gas schedule and recorded, so that
+    /// it stays the same once the gas schedule moves on. A schedule that cannot be decoded
+    /// is ignored by all the authorities alike, and the one of the previous epoch stays in
+    /// effect.
+    pub fn epoch_cost_schedule(&self, epoch: EpochId) -> SuiResult<(u64, SuiCostSchedule)> {
+        if let Some(cost_schedule) = self.cost_schedules.get(&epoch)? {
+            return Ok(cost_schedule);
+        }
+        let cost_schedule = match self.get_cost_schedule(epoch) {
+            Ok(cost_schedule) => cost_schedule,
+            Err(e @ SuiError::InvalidCostSchedule { .. }) => {
+                match self.cost_schedules.iter().skip_prior_to(&epoch)?.next() {
+                    Some((_, previous)) => {
+                        warn!(epoch, "Keeping the previous cost schedule: {e}");
+                        previous
+                    }
+                    None => return Err(e),
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        self.cost_schedules.insert(&epoch, &cost_schedule)?;
+        Ok(cost_schedule)
+    }
+
+    /// Returns the cost schedules recorded for the epochs this authority went through.
+    pub fn cost_schedules(&self) -> BTreeMap<EpochId, (u64, SuiCostSchedule)> {
+        self.cost_schedules.iter().collect()
+    }
+
+    /// Returns the next sequence number to request from the authority `name`, which is 0 if
+    /// it was never followed.
+    pub fn follower_next_sequence_number(
+        &self,
+        name: &AuthorityName,
+    ) -> SuiResult<TxSequenceNumber> {
+        Ok(self
+            .follower_next_sequence_numbers
+            .get(name)?
+            .unwrap_or_default())
+    }
+
+    /// Records that the transactions of the authority `name` were replicated up to
+    /// `next_sequence_number` (excluded).
+    pub fn set_follower_next_sequence_number(
+        &self,
+        name: &AuthorityName,
+        next_sequence_number: TxSequenceNumber,
+    ) -> SuiResult {
+        self.follower_next_sequence_numbers
+            .insert(name, &next_sequence_number)?;
+        Ok(())
+    }
+
+    /// The certificates without shared objects of `epoch` executed by this authority, in
+    /// the order it executed them.
+    pub fn epoch_certificates(&self, epoch: EpochId) -> SuiResult<Vec<CertifiedTransaction>> {
+        let digests: Vec<_> = self
+            .epoch_certificates
+            .iter()
+            .skip_to(&(epoch, 0))?
+            .take_while(|((certificate_epoch, _), _)| *certificate_epoch == epoch)
+            .map(|(_, digest)| digest)
+            .collect();
+        Ok(self
+            .certificates
+            .multi_get(&digests)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Records that consensus sequenced the end of `epoch` announced by `authority`, along
+    /// with the last consensus index.
+    pub fn record_end_of_epoch(
+        &self,
+        epoch: EpochId,
+        authority: AuthorityName,
+        consensus_index: ExecutionIndices,
+    ) -> SuiResult {
+        self.end_of_epoch
+            .batch()
+            .insert_batch(
+                &self.end_of_epoch,
+                std::iter::once(((epoch, authority), ())),
+            )?
+            .insert_batch(
+                &self.last_consensus_index,
+                std::iter::once((LAST_CONSENSUS_INDEX_ADDR, consensus_index)),
+            )?
+            .write()?;
+        Ok(())
+    }
+
+    /// The authorities whose end of `epoch` was sequenced by consensus.
+    pub fn sealed_authorities(&self, epoch: EpochId) -> SuiResult<Vec<AuthorityName>> {
+        Ok(self
+            .end_of_epoch
+            .iter()
+            .skip_to(&(epoch, AuthorityName::default()))?
+            .take_while(|((sealed_epoch, _), _)| *sealed_epoch == epoch)
+            .map(|((_, authority), _)| authority)
+            .collect())
+    }
+
+    /// Returns the root of the state tree, over the live objects as of the last batch.
+    pub fn state_root(&self) -> SuiResult<StateDigest> {
+        Ok(self
+            .state_tree
+            .get(&root_key())?
+            .map(|node| node.digest())
+            .unwrap_or(EMPTY_STATE_DIGEST))
+    }
+
+    /// Returns the state root after the execution of the transactions sequenced up to the
+    /// last batch end at or before `seq`, or None if there is none.
+    pub fn state_root_at(&self, seq: TxSequenceNumber) -> SuiResult<Option<StateDigest>> {
+        Ok(self
+            .state_roots
+            .iter()
+            .skip_prior_to(&seq)?
+            .next()
+            .map(|(_, state_root)| state_root))
+    }
+
+    /// Returns a proof that the object is part of the state as of the last batch, with its
+    /// reference at that point, or that it is not part of the state if it did not exist.
+    pub fn get_object_proof(&self, object_id: ObjectID) -> SuiResult<ObjectProof> {
+        let _tree_guard = self.state_tree_lock.lock();
+        ObjectProof::new(|key| Ok(self.state_tree.get(key)?), object_id)
+    }
+
     /// Returns the last entry we have for this object in the parents_sync index used
     /// to facilitate client and authority sync. In turn the latest entry provides the
     /// latest object_reference, and also the latest transaction that has interacted with
@@ -994,6 +1860,259 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
         Ok((batches, transactions))
     }
 
+    /// Returns the sequence number at which the oldest batch still held by the store ends.
+    /// Transactions sequenced before it have been pruned, and the store can no longer
+    /// serve batches or transaction sequences starting before it.
+    pub fn lowest_retained_sequence(&self) -> SuiResult<TxSequenceNumber> {
+        Ok(self
+            .batches
+            .iter()
+            .next()
+            .map(|(next_sequence_number, _)| next_sequence_number)
+            .unwrap_or(0))
+    }
+
+    /// Deletes the data of executed transactions sequenced before the batch boundary at or
+    /// prior to `watermark`, and returns the number of transactions pruned.
+    ///
+    /// The batch ending at that boundary is kept, so that batch streams starting at the
+    /// boundary still begin with a signed batch. Transactions that are still the latest
+    /// parent of some object (including deletion markers) are retained, since their
+    /// certificates are served by object info requests. They stay in `executed_sequence`
+    /// so that a later pass can prune them once the objects move on. The digest of the
+    /// effects of pruned transactions is kept, so that their certificates still get an
+    /// idempotent answer. Events and the indexes of all transactions before the boundary
+    /// are dropped. Historical object versions kept by ALL_OBJ_VER stores are never pruned.
+    pub fn prune_transactions_before(&self, watermark: TxSequenceNumber) -> SuiResult<usize> {
+        let boundary = match self.batches.iter().skip_prior_to(&watermark)?.next() {
+            Some((next_sequence_number, _)) => next_sequence_number,
+            None => return Ok(0),
+        };
+
+        let mut write_batch = self.executed_sequence.batch();
+        let mut num_pruned = 0;
+        // The highest version of each object written by a pruned transaction. The parent
+        // entries of the object up to it are deleted.
+        let mut pruned_versions: BTreeMap<ObjectID, SequenceNumber> = BTreeMap::new();
+        for (seq, digest) in self
+            .executed_sequence
+            .iter()
+            .take_while(|(seq, _)| *seq < boundary)
+        {
+            let (certificate, effects) =
+                match (self.certificates.get(&digest)?, self.effects.get(&digest)?) {
+                    (Some(certificate), Some(effects)) => (certificate, effects.effects),
+                    _ => {
+                        write_batch = write_batch
+                            .delete_batch(&self.executed_sequence, std::iter::once(seq))?;
+                        continue;
+                    }
+                };
+
+            if self.indexes {
+                write_batch =
+                    self.batch_unindex_transaction(write_batch, seq, &certificate, &effects)?;
+            }
+
+            let mut is_latest_parent = false;
+            for object_id in effects.touched_object_ids() {
+                if let Some((_, parent_digest)) = self.get_latest_parent_entry(object_id)? {
+                    is_latest_parent |= parent_digest == digest;
+                }
+            }
+            if is_latest_parent {
+                continue;
+            }
+
+            let written = effects
+                .mutated_and_created()
+                .chain(effects.unwrapped.iter())
+                .map(|(object_ref, _)| object_ref)
+                .chain(effects.deleted.iter())
+                .chain(effects.wrapped.iter());
+            for (object_id, version, _) in written {
+                let pruned_version = pruned_versions.entry(*object_id).or_insert(*version);
+                *pruned_version = (*pruned_version).max(*version);
+            }
+
+            num_pruned += 1;
+            write_batch = write_batch
+                .delete_batch(&self.executed_sequence, std::iter::once(seq))?
+                .delete_batch(
+                    &self.sequenced,
+                    certificate
+                        .shared_input_objects()
+                        .map(|object_id| (digest, *object_id)),
+                )?
+                .delete_batch(&self.transactions, std::iter::once(digest))?
+                .delete_batch(&self.certificates, std::iter::once(digest))?
+                .delete_batch(&self.effects, std::iter::once(digest))?
+                .insert_batch(
+                    &self.pruned_effects,
+                    std::iter::once((digest, sha3_hash(&effects))),
+                )?;
+        }
+
+        // The latest parent entry of an object is always above the versions written by
+        // pruned transactions, since its transaction is retained.
+        for (object_id, version) in pruned_versions {
+            write_batch = write_batch.delete_range(
+                &self.parent_sync,
+                &(object_id, SequenceNumber::MIN, ObjectDigest::MIN),
+                &(object_id, version.increment(), ObjectDigest::MIN),
+            )?;
+        }
+
+        write_batch
+            .delete_range(&self.batches, &0, &boundary)?
+            .delete_range(&self.state_roots, &0, &boundary)?
+            .delete_range(&self.events, &(0, 0), &(boundary, 0))?
+            .write()?;
+
+        Ok(num_pruned)
+    }
+
+    /// Makes a snapshot of the live object set at the batch ending at `next_sequence_number`.
+    ///
+    /// The store only keeps the latest version of the objects, so the batch must include all
+    /// executed transactions: the store should not be executing certificates while the
+    /// snapshot is taken (e.g. the authority is stopped). The batch must be signed with its
+    /// state root, so that the snapshot can be verified.
+    pub fn export_snapshot(
+        &self,
+        next_sequence_number: TxSequenceNumber,
+    ) -> SuiResult<StateSnapshot> {
+        let batch = self.batches.get(&next_sequence_number)?.ok_or_else(|| {
+            SuiError::StateSnapshotError {
+                error: format!("No batch ends at sequence number {}", next_sequence_number),
+            }
+        })?;
+        let state_root = batch
+            .batch
+            .state_root
+            .ok_or_else(|| SuiError::StateSnapshotError {
+                error: "The batch is not signed with a state root".to_string(),
+            })?;
+        let next_executed = self
+            .executed_sequence
+            .iter()
+            .skip_prior_to(&TxSequenceNumber::MAX)?
+            .next()
+            .map(|(seq, _)| seq + 1);
+        fp_ensure!(
+            next_executed.unwrap_or(0) <= next_sequence_number,
+            SuiError::StateSnapshotError {
+                error: format!(
+                    "Transactions were executed after sequence number {}, which is not the last batch",
+                    next_sequence_number
+                ),
+            }
+        );
+        fp_ensure!(
+            self.state_root()? == state_root,
+            SuiError::StateSnapshotError {
+                error: "The live objects do not match the state root of the batch".to_string(),
+            }
+        );
+
+        let objects: Vec<Object> = self.objects.values().collect();
+        let parent_digests: BTreeSet<_> = objects
+            .iter()
+            .map(|object| object.previous_transaction)
+            .filter(|digest| *digest != TransactionDigest::genesis())
+            .collect();
+        let parent_certificates = self
+            .certificates
+            .multi_get(&parent_digests)?
+            .into_iter()
+            .zip(parent_digests.iter())
+            .map(|(certificate, digest)| {
+                certificate.ok_or(SuiError::CertificateNotfound {
+                    certificate_digest: *digest,
+                })
+            })
+            .collect::<SuiResult<_>>()?;
+
+        let schedule = self.schedule.iter().collect();
+        let last_consensus_index = self.last_consensus_index()?;
+
+        Ok(StateSnapshot::new(
+            batch,
+            objects,
+            parent_certificates,
+            schedule,
+            last_consensus_index,
+        ))
+    }
+
+    /// Returns the signed batch of the store carrying the state root `state_root`, if any.
+    /// Other authorities use it to attest the state root of a snapshot.
+    pub fn signed_batch_with_state_root(
+        &self,
+        state_root: StateDigest,
+    ) -> SuiResult<Option<SignedBatch>> {
+        Ok(self
+            .batches
+            .values()
+            .find(|batch| batch.batch.state_root == Some(state_root)))
+    }
+
+    /// Bootstraps an empty store from a snapshot. The snapshot batch becomes the last
+    /// batch of the store, the transaction sequence continues after it and the consensus
+    /// client resumes after the snapshot consensus index.
+    ///
+    /// The caller is responsible for verifying the snapshot against the committee. The
+    /// snapshot batch must carry a state root, which the imported objects must match:
+    /// nothing is written otherwise.
+    pub fn import_snapshot(&self, snapshot: &StateSnapshot) -> SuiResult {
+        let state_root = snapshot.signed_state_root()?;
+        fp_ensure!(
+            self.database_is_empty()?,
+            SuiError::StateSnapshotError {
+                error: "Snapshots can only be imported into an empty store".to_string(),
+            }
+        );
+
+        let objects: Vec<_> = snapshot.objects.iter().collect();
+        let batch = self
+            .bulk_object_insert_batch(self.objects.batch(), &objects)?
+            .insert_batch(
+                &self.certificates,
+                snapshot
+                    .parent_certificates
+                    .iter()
+                    .map(|certificate| (*certificate.digest(), certificate)),
+            )?
+            .insert_batch(
+                &self.batches,
+                std::iter::once((snapshot.batch.batch.next_sequence_number, &snapshot.batch)),
+            )?
+            .insert_batch(&self.schedule, snapshot.schedule.iter().copied())?
+            .insert_batch(
+                &self.last_consensus_index,
+                std::iter::once((LAST_CONSENSUS_INDEX_ADDR, &snapshot.last_consensus_index)),
+            )?;
+
+        // The whole snapshot is written in one batch, once the root it yields is checked.
+        let _tree_guard = self.state_tree_lock.lock();
+        let (batch, root) = self.batch_update_state_tree(
+            batch,
+            snapshot.objects.iter().map(|object| {
+                let object_ref = object.compute_object_reference();
+                (object_ref.0, Some(object_ref))
+            }),
+        )?;
+        fp_ensure!(
+            root == state_root,
+            SuiError::StateSnapshotError {
+                error: "Snapshot objects do not match the state root of the batch".to_string(),
+            }
+        );
+        batch.write()?;
+
+        Ok(())
+    }
+
     /// Return the latest consensus index. It is used to bootstrap the consensus client.
     pub fn last_consensus_index(&self) -> SuiResult<ExecutionIndices> {
         self.last_consensus_index
@@ -1026,14 +2145,84 @@ impl<const ALL_OBJ_VER: bool, S: Eq + Serialize + for<'de> Deserialize<'de>>
 }
 
 impl<const A: bool> SuiDataStore<A, AuthoritySignInfo> {
+    /// Once the authority moves to `epoch`, the certificates of the previous epochs are
+    /// rejected, so the transactions they signed which were not executed will never be:
+    /// release the locks they hold and forget them, along with the records of the end of
+    /// the previous epochs.
+    pub fn release_transaction_locks_before(&self, epoch: EpochId) -> SuiResult {
+        let mut released = Vec::new();
+        for (digest, transaction) in self.transactions.iter() {
+            if transaction.auth_sign_info.epoch < epoch && !self.effects.contains_key(&digest)? {
+                let owned_objects: Vec<_> = transaction
+                    .data
+                    .input_objects()?
+                    .into_iter()
+                    .filter_map(|kind| match kind {
+                        InputObjectKind::ImmOrOwnedMoveObject(object_ref) => Some(object_ref),
+                        _ => None,
+                    })
+                    .collect();
+                released.push((digest, owned_objects));
+            }
+        }
+        let mut write_batch = self.transaction_lock.batch();
+        let _mutexes = self.acquire_locks(released.iter().flat_map(|(_, objects)| objects));
+        for (digest, owned_objects) in &released {
+            let locks = self.transaction_lock.multi_get(owned_objects)?;
+            write_batch = write_batch.insert_batch(
+                &self.transaction_lock,
+                owned_objects
+                    .iter()
+                    .zip(locks)
+                    .filter(|(_, lock)| *lock == Some(Some(*digest)))
+                    .map(|(object_ref, _)| (object_ref, None::<TransactionDigest>)),
+            )?;
+        }
+        let past_certificates: Vec<_> = self
+            .epoch_certificates
+            .iter()
+            .take_while(|((certificate_epoch, _), _)| *certificate_epoch < epoch)
+            .map(|(key, _)| key)
+            .collect();
+        let past_ends: Vec<_> = self
+            .end_of_epoch
+            .iter()
+            .take_while(|((sealed_epoch, _), _)| *sealed_epoch < epoch)
+            .map(|(key, _)| key)
+            .collect();
+        write_batch
+            .delete_batch(
+                &self.transactions,
+                released.iter().map(|(digest, _)| digest),
+            )?
+            .delete_batch(&self.epoch_certificates, past_certificates)?
+            .delete_batch(&self.end_of_epoch, past_ends)?
+            .write()?;
+        debug!(
+            epoch,
+            num_released = released.len(),
+            "Released the locks of the transactions of past epochs"
+        );
+        Ok(())
+    }
+
     pub fn get_signed_transaction_info(
         &self,
         transaction_digest: &TransactionDigest,
     ) -> Result<TransactionInfoResponse, SuiError> {
+        let signed_effects = self.effects.get(transaction_digest)?;
+        if signed_effects.is_none() {
+            if let Some(effects_digest) = self.pruned_effects.get(transaction_digest)? {
+                return Err(SuiError::TransactionEffectsPruned {
+                    digest: *transaction_digest,
+                    effects_digest,
+                });
+            }
+        }
         Ok(TransactionInfoResponse {
             signed_transaction: self.transactions.get(transaction_digest)?,
             certified_transaction: self.certificates.get(transaction_digest)?,
-            signed_effects: self.effects.get(transaction_digest)?,
+            signed_effects,
         })
     }
 }
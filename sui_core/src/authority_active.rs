@@ -103,7 +103,7 @@ impl<A> ActiveAuthority<A> {
         authority: Arc<AuthorityState>,
         authority_clients: BTreeMap<AuthorityName, A>,
     ) -> SuiResult<Self> {
-        let committee = authority.committee.clone();
+        let committee = authority.committee().as_ref().clone();
 
         Ok(ActiveAuthority {
             health: Arc::new(Mutex::new(
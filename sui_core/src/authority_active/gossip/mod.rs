@@ -46,7 +46,7 @@ where
 
     // Number of tasks at most "degree" and no more than committee - 1
     let target_num_tasks: usize = usize::min(
-        active_authority.state.committee.voting_rights.len() - 1,
+        active_authority.state.committee().voting_rights.len() - 1,
         degree,
     );
 
@@ -78,7 +78,7 @@ where
 
         let mut k = 0;
         while gossip_tasks.len() < target_num_tasks {
-            let name = active_authority.state.committee.sample();
+            let name = committee.sample();
             if peer_names.contains(name)
                 || *name == active_authority.state.name
                 || !active_authority.can_contact(*name).await
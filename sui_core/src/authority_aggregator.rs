@@ -8,19 +8,27 @@ use crate::safe_client::SafeClient;
 
 use futures::{future, StreamExt};
 use move_core_types::value::MoveStructLayout;
+use multiaddr::Multiaddr;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use sui_types::crypto::{AuthoritySignature, PublicKeyBytes};
 use sui_types::object::{Object, ObjectFormatOptions, ObjectRead};
 use sui_types::{
     base_types::*,
-    committee::Committee,
+    committee::{Committee, EpochId},
     error::{SuiError, SuiResult},
+    fp_ensure,
     messages::*,
+    sui_system_state::{
+        GasSchedule, SuiSystemState, SUI_GAS_SCHEDULE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
+    },
 };
 use tracing::{debug, trace, Instrument};
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::string::ToString;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::Receiver;
 use tokio::time::timeout;
 
@@ -35,12 +43,63 @@ pub mod authority_aggregator_tests;
 
 pub type AsyncResult<'a, T, E> = future::BoxFuture<'a, Result<T, E>>;
 
+/// How the authorities are queried when driving a transaction or a certificate to a quorum.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuorumDriverConfig {
+    /// Driving a transaction to a certificate.
+    pub process_transaction: QuorumPhaseConfig,
+    /// Driving a certificate to effects.
+    pub process_certificate: QuorumPhaseConfig,
+    /// How long to wait for the remaining authorities once a quorum answered.
+    pub timeout_after_quorum: Duration,
+    /// Maximum number of authorities queried at once, all of them if None.
+    pub max_in_flight: Option<usize>,
+    /// With `max_in_flight`, how long to wait for an authority before querying the next
+    /// one as well.
+    pub request_timeout: Duration,
+    /// Query the authorities which answered the fastest so far first.
+    pub prefer_low_latency: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumPhaseConfig {
+    /// How long to wait for a quorum of authorities to answer.
+    pub timeout: Duration,
+    /// Keep waiting for the remaining authorities once a quorum answered, up to
+    /// `timeout_after_quorum`, rather than returning at once.
+    pub wait_for_stragglers: bool,
+}
+
+impl Default for QuorumDriverConfig {
+    fn default() -> Self {
+        Self {
+            process_transaction: QuorumPhaseConfig {
+                timeout: Duration::from_secs(60),
+                wait_for_stragglers: false,
+            },
+            process_certificate: QuorumPhaseConfig {
+                timeout: Duration::from_secs(60),
+                wait_for_stragglers: true,
+            },
+            timeout_after_quorum: Duration::from_secs(60),
+            max_in_flight: None,
+            request_timeout: Duration::from_secs(5),
+            prefer_low_latency: false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct AuthorityAggregator<A> {
     /// Our Sui committee.
     pub committee: Committee,
     /// How to talk to this committee.
     pub authority_clients: BTreeMap<AuthorityName, SafeClient<A>>,
+    /// How to drive transactions and certificates to a quorum.
+    pub config: QuorumDriverConfig,
+    /// Latency of the successful requests to each authority, smoothed over time.
+    latencies: Arc<Mutex<BTreeMap<AuthorityName, Duration>>>,
     // Metrics
     pub metrics: &'static GatewayMetrics,
 }
@@ -53,9 +112,131 @@ impl<A> AuthorityAggregator<A> {
                 .into_iter()
                 .map(|(name, api)| (name, SafeClient::new(api, committee.clone(), name)))
                 .collect(),
+            config: QuorumDriverConfig::default(),
+            latencies: Default::default(),
             metrics: &METRICS,
         }
     }
+
+    pub fn with_config(mut self, config: QuorumDriverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The aggregator of the committee of a later epoch. The clients of the authorities
+    /// staying in the committee are kept, and `new_clients` must provide the ones of the
+    /// authorities joining it.
+    pub fn reconfigure(
+        &self,
+        committee: Committee,
+        mut new_clients: BTreeMap<AuthorityName, A>,
+    ) -> SuiResult<Self>
+    where
+        A: Clone,
+    {
+        fp_ensure!(
+            committee.epoch > self.committee.epoch,
+            SuiError::InvalidCommittee {
+                error: format!(
+                    "Cannot move from epoch {} to epoch {}",
+                    self.committee.epoch, committee.epoch
+                ),
+            }
+        );
+        let mut authority_clients = BTreeMap::new();
+        for name in committee.voting_rights.keys() {
+            let client = match new_clients.remove(name) {
+                Some(client) => SafeClient::new(client, committee.clone(), *name),
+                None => self
+                    .authority_clients
+                    .get(name)
+                    .ok_or_else(|| SuiError::InvalidCommittee {
+                        error: format!("No client for authority {:?}", name),
+                    })?
+                    .clone()
+                    .with_committee(committee.clone()),
+            };
+            authority_clients.insert(*name, client);
+        }
+        Ok(Self {
+            committee,
+            authority_clients,
+            config: self.config.clone(),
+            latencies: self.latencies.clone(),
+            metrics: self.metrics,
+        })
+    }
+
+    /// The aggregator of the committee recorded in the Sui system state. The clients of
+    /// the validators joining the committee are made by `connect` from their network
+    /// address.
+    pub fn reconfigure_from_system_state(
+        &self,
+        system_state: &SuiSystemState,
+        connect: impl Fn(&Multiaddr) -> SuiResult<A>,
+    ) -> SuiResult<Self>
+    where
+        A: Clone,
+    {
+        let mut new_clients = BTreeMap::new();
+        for validator in &system_state.validators.active_validators {
+            let name = validator.authority_name()?;
+            if !self.authority_clients.contains_key(&name) {
+                let address = Multiaddr::try_from(validator.net_address.clone()).map_err(|e| {
+                    SuiError::InvalidCommittee {
+                        error: format!("Invalid network address of validator {:?}: {e}", name),
+                    }
+                })?;
+                new_clients.insert(name, connect(&address)?);
+            }
+        }
+        self.reconfigure(system_state.get_committee()?, new_clients)
+    }
+
+    fn record_latency(&self, name: AuthorityName, latency: Duration) {
+        let mut latencies = self.latencies.lock();
+        let smoothed = match latencies.get(&name) {
+            Some(previous) => (*previous * 3 + latency) / 4,
+            None => latency,
+        };
+        latencies.insert(name, smoothed);
+    }
+
+    /// The authorities in the order they should be queried: by increasing latency if
+    /// configured to prefer low latency authorities, the ones never heard from first.
+    fn authorities_in_query_order(&self) -> Vec<(&AuthorityName, &SafeClient<A>)> {
+        let mut authorities: Vec<_> = self.authority_clients.iter().collect();
+        if self.config.prefer_low_latency {
+            let latencies = self.latencies.lock();
+            authorities.sort_by_key(|(name, _)| latencies.get(*name).copied().unwrap_or_default());
+        }
+        authorities
+    }
+}
+
+/// The transactions an object is locked on, as reported by the authorities.
+#[derive(Debug, Default)]
+pub struct ObjectLocks {
+    /// The transactions the object is locked on, by digest.
+    pub locks: BTreeMap<TransactionDigest, LockedTransaction>,
+    /// Stake of the authorities holding the object at this version, without a lock.
+    pub unlocked_stake: usize,
+    /// Stake of the authorities holding a later version of the object.
+    pub outdated_stake: usize,
+    /// Stake of the authorities holding an earlier version of the object, or which did not
+    /// answer.
+    pub other_stake: usize,
+}
+
+/// A transaction an object is locked on, with the signatures of the authorities which
+/// locked it.
+#[derive(Debug)]
+pub struct LockedTransaction {
+    pub transaction: Transaction,
+    /// The epoch of the signatures.
+    pub epoch: EpochId,
+    pub signatures: Vec<(AuthorityName, AuthoritySignature)>,
+    pub stake: usize,
 }
 
 pub enum ReduceOutput<S> {
@@ -317,30 +498,82 @@ where
         ) -> AsyncResult<'a, ReduceOutput<S>, SuiError>,
     {
         // TODO: shuffle here according to stake
-        let authority_clients = &self.authority_clients;
-
-        // First, execute in parallel for each authority FMap.
-        let mut responses: futures::stream::FuturesUnordered<_> = authority_clients
-            .iter()
-            .map(|(name, client)| {
+        let max_in_flight = self.config.max_in_flight.unwrap_or(usize::MAX).max(1);
+        let request_timeout = self
+            .config
+            .max_in_flight
+            .map(|_| self.config.request_timeout);
+        let mut requests = self
+            .authorities_in_query_order()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, client))| {
                 let execute = map_each_authority.clone();
-                async move {
-                    (
-                        *name,
-                        execute(*name, client)
-                            .instrument(tracing::trace_span!("quorum_map_auth", authority =? name))
-                            .await,
-                    )
+                let request = async move {
+                    let start = Instant::now();
+                    let result = execute(*name, client)
+                        .instrument(tracing::trace_span!("quorum_map_auth", authority =? name))
+                        .await;
+                    if result.is_ok() {
+                        self.record_latency(*name, start.elapsed());
+                    }
+                    (index, Some((*name, result)))
+                };
+                (index, request)
+            });
+
+        // Execute FMap for as many authorities as allowed, and start the next one every time
+        // one of them answers, or takes longer than `request_timeout`. A late request keeps
+        // running, and its answer is still folded into the state.
+        let mut responses = futures::stream::FuturesUnordered::new();
+        // The requests counted towards `max_in_flight`.
+        let mut in_flight = HashSet::new();
+        // The requests which did not answer yet.
+        let mut num_pending = 0;
+
+        // The deadline of the current phase. It is moved once, when the reducer first asks to
+        // continue with a timeout, and later answers do not extend it.
+        let phase_deadline =
+            tokio::time::sleep_until(tokio::time::Instant::now() + initial_timeout);
+        tokio::pin!(phase_deadline);
+        let mut timeout_set = false;
+        let mut accumulated_state = initial_state;
+        loop {
+            while in_flight.len() < max_in_flight {
+                let (index, request) = match requests.next() {
+                    Some(request) => request,
+                    None => break,
+                };
+                in_flight.insert(index);
+                num_pending += 1;
+                responses.push(future::Either::Left(request));
+                if let Some(request_timeout) = request_timeout {
+                    let deadline = async move {
+                        tokio::time::sleep(request_timeout).await;
+                        (index, None::<(AuthorityName, Result<V, SuiError>)>)
+                    };
+                    responses.push(future::Either::Right(deadline));
                 }
-            })
-            .collect();
+            }
+            if num_pending == 0 {
+                break;
+            }
 
-        let mut current_timeout = initial_timeout;
-        let mut accumulated_state = initial_state;
-        // Then, as results become available fold them into the state using FReduce.
-        while let Ok(Some((authority_name, result))) =
-            timeout(current_timeout, responses.next()).await
-        {
+            // Then, as results become available fold them into the state using FReduce.
+            let (index, response) = tokio::select! {
+                response = responses.next() => match response {
+                    Some(response) => response,
+                    None => break,
+                },
+                _ = &mut phase_deadline => break,
+            };
+            in_flight.remove(&index);
+            let (authority_name, result) = match response {
+                Some(response) => response,
+                // The request is late, or it already answered.
+                None => continue,
+            };
+            num_pending -= 1;
             let authority_weight = self.committee.weight(&authority_name);
             accumulated_state =
                 match reduce_result(accumulated_state, authority_name, authority_weight, result)
@@ -349,8 +582,13 @@ where
                     // In the first two cases we are told to continue the iteration.
                     ReduceOutput::Continue(state) => state,
                     ReduceOutput::ContinueWithTimeout(state, duration) => {
-                        // Adjust the waiting timeout.
-                        current_timeout = duration;
+                        // Wait at most `duration` for the remaining authorities.
+                        if !timeout_set {
+                            timeout_set = true;
+                            phase_deadline
+                                .as_mut()
+                                .reset(tokio::time::Instant::now() + duration);
+                        }
                         state
                     }
                     ReduceOutput::End(state) => {
@@ -811,6 +1049,7 @@ where
             bad_stake: 0,
         };
 
+        let phase_config = &self.config.process_transaction;
         let transaction_ref = &transaction;
         let state = self
             .quorum_map_then_reduce_with_timeout(
@@ -846,7 +1085,7 @@ where
                                     inner_signed_transaction.auth_sign_info.signature,
                                 ));
                                 state.good_stake += weight;
-                                if state.good_stake >= threshold {
+                                if state.certificate.is_none() && state.good_stake >= threshold {
                                     self.metrics
                                         .num_signatures
                                         .observe(state.signatures.len() as f64);
@@ -878,7 +1117,7 @@ where
                             }
                         };
 
-                        if state.bad_stake > validity {
+                        if state.certificate.is_none() && state.bad_stake > validity {
                             // Too many errors
                             debug!(
                                 num_errors = state.errors.len(),
@@ -904,16 +1143,22 @@ where
                             }
                         }
 
-                        // If we have a certificate, then finish, otherwise continue.
-                        if state.certificate.is_some() {
-                            Ok(ReduceOutput::End(state))
-                        } else {
+                        // If we have a certificate, then finish (or wait for the other
+                        // authorities if configured to), otherwise continue.
+                        if state.certificate.is_none() {
                             Ok(ReduceOutput::Continue(state))
+                        } else if phase_config.wait_for_stragglers {
+                            Ok(ReduceOutput::ContinueWithTimeout(
+                                state,
+                                timeout_after_quorum,
+                            ))
+                        } else {
+                            Ok(ReduceOutput::End(state))
                         }
                     })
                 },
                 // A long timeout before we hear back from a quorum
-                Duration::from_secs(60),
+                phase_config.timeout,
             )
             .await?;
 
@@ -941,7 +1186,7 @@ where
     /// At that point (and after) enough authorities are up to date with all objects
     /// needed to process the certificate that a submission should succeed. However,
     /// in case an authority returns an error, we do try to bring it up to speed.
-    async fn process_certificate(
+    pub async fn process_certificate(
         &self,
         certificate: CertifiedTransaction,
         timeout_after_quorum: Duration,
@@ -959,6 +1204,7 @@ where
             bad_stake: 0,
         };
 
+        let phase_config = &self.config.process_certificate;
         let cert_ref = &certificate;
         let threshold = self.committee.quorum_threshold();
         let validity = self.committee.validity_threshold();
@@ -998,6 +1244,17 @@ where
                             return res;
                         }
 
+                        // Once an authority sealed the end of its epoch, it only executes
+                        // the certificates sequenced by consensus.
+                        if matches!(res, Err(SuiError::ValidatorHaltedAtEpochEnd { .. })) {
+                            return client
+                                .handle_consensus_transaction(ConsensusTransaction::UserTransaction(
+                                    cert_ref.clone(),
+                                ))
+                                .instrument(tracing::trace_span!("handle_cert", authority =? _name, sequenced = true))
+                                .await;
+                        }
+
                         // LockErrors indicate the authority may be out-of-date.
                         // We only attempt to update authority and retry if we are seeing LockErrors.
                         // For any other error, we stop here and return.
@@ -1043,7 +1300,11 @@ where
                             entry.0 += weight;
 
                             if entry.0 >= threshold {
-                                // It will set the timeout quite high.
+                                // Unless configured to return at once, wait for the other
+                                // authorities to be brought up to date, up to a timeout.
+                                if !phase_config.wait_for_stragglers {
+                                    return Ok(ReduceOutput::End(state));
+                                }
                                 return Ok(ReduceOutput::ContinueWithTimeout(
                                     state,
                                     timeout_after_quorum,
@@ -1062,7 +1323,7 @@ where
                     })
                 },
                 // A long timeout before we hear back from a quorum
-                Duration::from_secs(60),
+                phase_config.timeout,
             )
             .await?;
 
@@ -1105,18 +1366,242 @@ where
         transaction: &Transaction,
     ) -> Result<(CertifiedTransaction, TransactionEffects), anyhow::Error> {
         let new_certificate = self
-            .process_transaction(transaction.clone(), Duration::from_secs(60))
+            .process_transaction(transaction.clone(), self.config.timeout_after_quorum)
             .instrument(tracing::debug_span!("process_tx"))
             .await?;
         self.metrics.total_tx_certificates.inc();
         let response = self
-            .process_certificate(new_certificate.clone(), Duration::from_secs(60))
+            .process_certificate(new_certificate.clone(), self.config.timeout_after_quorum)
             .instrument(tracing::debug_span!("process_cert"))
             .await?;
 
         Ok((new_certificate, response))
     }
 
+    /// Ask every authority which transaction, if any, it locked `object_ref` on, and
+    /// gather the signed transactions they return. A lock only counts if it is signed by
+    /// the authority returning it, for the epoch of the committee.
+    pub async fn get_object_locks(
+        &self,
+        object_ref: ObjectRef,
+        timeout_after_quorum: Duration,
+    ) -> Result<ObjectLocks, SuiError> {
+        struct GetObjectLocksState {
+            object_locks: ObjectLocks,
+            // Stake of the authorities which answered so far.
+            responded_stake: usize,
+        }
+
+        let state = GetObjectLocksState {
+            object_locks: ObjectLocks::default(),
+            responded_stake: 0,
+        };
+        let threshold = self.committee.quorum_threshold();
+        let committee = &self.committee;
+        let state = self
+            .quorum_map_then_reduce_with_timeout(
+                state,
+                |_name, client| {
+                    Box::pin(async move {
+                        client
+                            .handle_object_info_request(
+                                ObjectInfoRequest::latest_object_info_request(object_ref.0, None),
+                            )
+                            .await
+                    })
+                },
+                |mut state, name, weight, result| {
+                    Box::pin(async move {
+                        let locks = &mut state.object_locks;
+                        match result {
+                            Ok(ObjectInfoResponse {
+                                requested_object_reference: Some(latest_ref),
+                                object_and_lock: Some(ObjectResponse { lock, .. }),
+                                ..
+                            }) if latest_ref == object_ref => match lock {
+                                Some(signed_transaction)
+                                    if signed_transaction.auth_sign_info.authority != name
+                                        || signed_transaction.auth_sign_info.epoch
+                                            != committee.epoch()
+                                        || signed_transaction.verify(committee).is_err() =>
+                                {
+                                    locks.other_stake += weight
+                                }
+                                Some(signed_transaction) => {
+                                    let locked = locks
+                                        .locks
+                                        .entry(*signed_transaction.digest())
+                                        .or_insert_with(|| LockedTransaction {
+                                            transaction: signed_transaction
+                                                .clone()
+                                                .to_transaction(),
+                                            epoch: signed_transaction.auth_sign_info.epoch,
+                                            signatures: vec![],
+                                            stake: 0,
+                                        });
+                                    locked
+                                        .signatures
+                                        .push((name, signed_transaction.auth_sign_info.signature));
+                                    locked.stake += weight;
+                                }
+                                None => locks.unlocked_stake += weight,
+                            },
+                            Ok(ObjectInfoResponse {
+                                requested_object_reference: Some(latest_ref),
+                                ..
+                            }) if latest_ref.1 > object_ref.1 => locks.outdated_stake += weight,
+                            _ => locks.other_stake += weight,
+                        }
+                        state.responded_stake += weight;
+                        if state.responded_stake >= threshold {
+                            Ok(ReduceOutput::ContinueWithTimeout(
+                                state,
+                                timeout_after_quorum,
+                            ))
+                        } else {
+                            Ok(ReduceOutput::Continue(state))
+                        }
+                    })
+                },
+                // A long timeout before we hear back from a quorum
+                Duration::from_secs(60),
+            )
+            .await?;
+
+        // The authorities which did not answer in time may hold any lock.
+        let mut object_locks = state.object_locks;
+        object_locks.other_stake += self.committee.total_votes - state.responded_stake;
+        debug!(
+            ?object_ref,
+            num_locks = object_locks.locks.len(),
+            unlocked_stake = object_locks.unlocked_stake,
+            outdated_stake = object_locks.outdated_stake,
+            other_stake = object_locks.other_stake,
+            "Fetched object locks from authorities"
+        );
+        Ok(object_locks)
+    }
+
+    /// Release an object left locked by a transaction which did not reach a quorum of
+    /// signatures, by driving the transaction it is locked on to a certificate, and
+    /// executing it.
+    ///
+    /// A transaction already signed by a quorum is certified from the existing signatures.
+    /// Otherwise it is submitted again to the authorities: the ones which signed it return
+    /// the same signature, and those which did not lock the object yet may sign it. The
+    /// transaction locked by the most stake is chosen, and an error is returned if the
+    /// authorities locked on conflicting transactions prevent it from ever being certified.
+    /// Returns None if no authority holds a lock on the object.
+    pub async fn retry_locked_object(
+        &self,
+        object_ref: ObjectRef,
+        timeout_after_quorum: Duration,
+    ) -> Result<Option<(CertifiedTransaction, TransactionEffects)>, SuiError> {
+        let object_locks = self
+            .get_object_locks(object_ref, timeout_after_quorum)
+            .await?;
+        let locked = match object_locks
+            .locks
+            .values()
+            .max_by_key(|locked| locked.stake)
+        {
+            Some(locked) => locked,
+            None => return Ok(None),
+        };
+
+        let threshold = self.committee.quorum_threshold();
+        let certificate = if locked.stake >= threshold {
+            CertifiedTransaction::new_with_signatures(
+                locked.epoch,
+                locked.transaction.clone(),
+                locked.signatures.clone(),
+            )
+        } else {
+            // The authorities locked on other transactions, or holding a later version of
+            // the object, will never sign it.
+            let conflicting_stake: usize = object_locks
+                .locks
+                .values()
+                .map(|other| other.stake)
+                .sum::<usize>()
+                - locked.stake;
+            fp_ensure!(
+                self.committee.total_votes - conflicting_stake - object_locks.outdated_stake
+                    >= threshold,
+                SuiError::ObjectLockConflict {
+                    obj_ref: object_ref,
+                    pending_transactions: object_locks.locks.keys().cloned().collect(),
+                }
+            );
+            self.process_transaction(locked.transaction.clone(), timeout_after_quorum)
+                .instrument(tracing::debug_span!("process_tx"))
+                .await?
+        };
+        self.metrics.total_tx_certificates.inc();
+        let effects = self
+            .process_certificate(certificate.clone(), timeout_after_quorum)
+            .instrument(tracing::debug_span!("process_cert"))
+            .await?;
+        Ok(Some((certificate, effects)))
+    }
+
+    /// Whether `transaction` can never be certified: for one of its owned input objects,
+    /// the authorities which locked the object on another transaction, or which hold a
+    /// later version of it, leave less than a quorum able to sign the transaction.
+    pub async fn is_transaction_rejected(
+        &self,
+        transaction: &Transaction,
+        timeout_after_quorum: Duration,
+    ) -> SuiResult<bool> {
+        let threshold = self.committee.quorum_threshold();
+        for kind in transaction.data.input_objects()? {
+            let object_ref = match kind {
+                InputObjectKind::ImmOrOwnedMoveObject(object_ref) => object_ref,
+                _ => continue,
+            };
+            let object_locks = self
+                .get_object_locks(object_ref, timeout_after_quorum)
+                .await?;
+            let conflicting_stake: usize = object_locks
+                .locks
+                .iter()
+                .filter(|(digest, _)| *digest != transaction.digest())
+                .map(|(_, locked)| locked.stake)
+                .sum();
+            if self.committee.total_votes - conflicting_stake - object_locks.outdated_stake
+                < threshold
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// The Sui system state, as known to the authorities.
+    pub async fn get_system_state(&self) -> SuiResult<SuiSystemState> {
+        match self
+            .get_object_info_execute(SUI_SYSTEM_STATE_OBJECT_ID)
+            .await?
+        {
+            ObjectRead::Exists(_, object, _) => SuiSystemState::from_object(&object),
+            _ => Err(SuiError::ObjectNotFound {
+                object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+            }),
+        }
+    }
+
+    pub async fn get_gas_schedule(&self) -> SuiResult<GasSchedule> {
+        match self
+            .get_object_info_execute(SUI_GAS_SCHEDULE_OBJECT_ID)
+            .await?
+        {
+            ObjectRead::Exists(_, object, _) => GasSchedule::from_object(&object),
+            _ => Err(SuiError::ObjectNotFound {
+                object_id: SUI_GAS_SCHEDULE_OBJECT_ID,
+            }),
+        }
+    }
+
     pub async fn get_object_info_execute(&self, object_id: ObjectID) -> SuiResult<ObjectRead> {
         let (object_map, cert_map) = self
             .get_object_by_id(object_id, AUTHORITY_REQUEST_TIMEOUT)
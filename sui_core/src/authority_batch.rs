@@ -99,12 +99,9 @@ impl crate::authority::AuthorityState {
 
         if !transactions.is_empty() {
             // Make a new batch, to put the old transactions not in a batch in.
-            let last_signed_batch = SignedBatch::new(
-                // Unwrap safe due to check not empty
-                AuthorityBatch::make_next(&last_batch, &transactions[..])?,
-                &*self.secret,
-                self.name,
-            );
+            let mut batch = AuthorityBatch::make_next(&last_batch, &transactions[..])?;
+            batch.state_root = Some(self.db().commit_state_tree(batch.next_sequence_number)?);
+            let last_signed_batch = SignedBatch::new(batch, &*self.secret, self.name);
             self.db().batches.insert(
                 &last_signed_batch.batch.next_sequence_number,
                 &last_signed_batch,
@@ -183,12 +180,12 @@ impl crate::authority::AuthorityState {
                 }
 
                 // Make and store a new batch.
-                let new_batch = SignedBatch::new(
-                    // Unwrap safe since we tested above it is not empty
-                    AuthorityBatch::make_next(&prev_batch, &current_batch).unwrap(),
-                    &*self.secret,
-                    self.name,
-                );
+                // Unwrap safe since we tested above it is not empty
+                let mut batch = AuthorityBatch::make_next(&prev_batch, &current_batch).unwrap();
+                // All the transactions of the batch are committed, so their changes can be
+                // applied to the state tree to get the state at the batch end.
+                batch.state_root = Some(self.db().commit_state_tree(batch.next_sequence_number)?);
+                let new_batch = SignedBatch::new(batch, &*self.secret, self.name);
                 self.db()
                     .batches
                     .insert(&new_batch.batch.next_sequence_number, &new_batch)?;
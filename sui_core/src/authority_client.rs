@@ -8,7 +8,10 @@ use futures::{stream::BoxStream, TryStreamExt};
 use multiaddr::Multiaddr;
 use std::sync::Arc;
 use sui_network::{api::ValidatorClient, tonic};
-use sui_types::{error::SuiError, messages::*};
+use sui_types::{
+    error::{SuiError, SuiResult},
+    messages::*,
+};
 
 #[cfg(test)]
 use sui_types::{
@@ -64,6 +67,9 @@ pub trait AuthorityAPI {
 
 pub type BatchInfoResponseItemStream = BoxStream<'static, Result<BatchInfoResponseItem, SuiError>>;
 
+/// Connects to an authority joining the committee, from its network address.
+pub type AuthorityConnector<A> = Box<dyn Fn(&Multiaddr) -> SuiResult<A> + Send + Sync>;
+
 #[derive(Clone)]
 pub struct NetworkAuthorityClient {
     client: ValidatorClient<tonic::transport::Channel>,
@@ -86,6 +92,19 @@ impl NetworkAuthorityClient {
         }
     }
 
+    /// Connects lazily to the authorities joining the committee, with the given network
+    /// config.
+    pub fn connector(config: mysten_network::config::Config) -> AuthorityConnector<Self> {
+        Box::new(move |address| {
+            let channel = config
+                .connect_lazy(address)
+                .map_err(|e| SuiError::InvalidCommittee {
+                    error: format!("Cannot connect to {address}: {e}"),
+                })?;
+            Ok(Self::new(channel))
+        })
+    }
+
     fn client(&self) -> ValidatorClient<tonic::transport::Channel> {
         self.client.clone()
     }
@@ -184,6 +203,8 @@ pub struct LocalAuthorityClientFaultConfig {
     pub fail_after_handle_transaction: bool,
     pub fail_before_handle_confirmation: bool,
     pub fail_after_handle_confirmation: bool,
+    /// Never answer transactions, as an authority which stalled.
+    pub stall_handle_transaction: bool,
 }
 
 impl LocalAuthorityClientFaultConfig {
@@ -209,6 +230,9 @@ impl AuthorityAPI for LocalAuthorityClient {
                 error: "Mock error before handle_transaction".to_owned(),
             });
         }
+        if self.fault_config.stall_handle_transaction {
+            return std::future::pending().await;
+        }
         let state = self.state.clone();
         let result = state.handle_transaction(transaction).await;
         if self.fault_config.fail_after_handle_transaction {
@@ -15,8 +15,11 @@ use sui_network::{
     tonic,
 };
 use sui_types::{crypto::VerificationObligation, error::*, messages::*};
-use tokio::sync::mpsc::Sender;
-use tracing::{info, Instrument};
+use tokio::sync::{
+    mpsc::{self, Receiver, Sender},
+    oneshot,
+};
+use tracing::{debug, info, Instrument};
 
 #[cfg(test)]
 #[path = "unit_tests/server_tests.rs"]
@@ -25,6 +28,15 @@ mod server_tests;
 const MIN_BATCH_SIZE: u64 = 1000;
 const MAX_DELAY_MILLIS: u64 = 5_000; // 5 sec
 
+/// The maximum number of certificates handed to the scheduler of the authority at once.
+const MAX_CERTIFICATE_BATCH_SIZE: usize = 1000;
+
+/// A certificate received by the server, with the channel its result is sent back on.
+type PendingCertificate = (
+    ConfirmationTransaction,
+    oneshot::Sender<SuiResult<TransactionInfoResponse>>,
+);
+
 pub struct AuthorityServerHandle {
     tx_cancellation: tokio::sync::oneshot::Sender<()>,
     local_addr: Multiaddr,
@@ -59,6 +71,9 @@ pub struct AuthorityServer {
     consensus_adapter: ConsensusAdapter,
     min_batch_size: u64,
     max_delay: Duration,
+    certificate_sender: Sender<PendingCertificate>,
+    /// Taken by the certificate batcher when the server is spawned.
+    certificate_receiver: Option<Receiver<PendingCertificate>>,
 }
 
 impl AuthorityServer {
@@ -70,10 +85,11 @@ impl AuthorityServer {
     ) -> Self {
         let consensus_adapter = ConsensusAdapter::new(
             consensus_address,
-            state.committee.clone(),
+            state.committee().as_ref().clone(),
             tx_consensus_listener,
             /* max_delay */ Duration::from_millis(5_000),
         );
+        let (certificate_sender, certificate_receiver) = mpsc::channel(MAX_CERTIFICATE_BATCH_SIZE);
 
         Self {
             address,
@@ -81,6 +97,8 @@ impl AuthorityServer {
             consensus_adapter,
             min_batch_size: MIN_BATCH_SIZE,
             max_delay: Duration::from_millis(MAX_DELAY_MILLIS),
+            certificate_sender,
+            certificate_receiver: Some(certificate_receiver),
         }
     }
 
@@ -107,7 +125,7 @@ impl AuthorityServer {
     }
 
     pub async fn spawn_with_bind_address(
-        self,
+        mut self,
         address: Multiaddr,
     ) -> Result<AuthorityServerHandle, io::Error> {
         // Start the batching subsystem
@@ -115,6 +133,11 @@ impl AuthorityServer {
             .spawn_batch_subsystem(self.min_batch_size, self.max_delay)
             .await;
 
+        // Start the certificate batcher
+        if let Some(receiver) = self.certificate_receiver.take() {
+            tokio::task::spawn(run_certificate_batcher(self.state.clone(), receiver));
+        }
+
         let mut server = mysten_network::config::Config::new()
             .server_builder()
             .add_service(ValidatorServer::new(self))
@@ -177,7 +200,7 @@ impl Validator for AuthorityServer {
 
         let mut obligation = VerificationObligation::default();
         transaction
-            .add_to_verification_obligation(&self.state.committee, &mut obligation)
+            .add_to_verification_obligation(&self.state.committee(), &mut obligation)
             .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
         obligation
             .verify_all()
@@ -196,11 +219,16 @@ impl Validator for AuthorityServer {
             certificate: transaction,
         };
 
-        let info = self
-            .state
-            .handle_confirmation_transaction(confirmation_transaction)
+        let (sender, receiver) = oneshot::channel();
+        let stopped = || tonic::Status::unavailable("The certificate batcher stopped");
+        self.certificate_sender
+            .send((confirmation_transaction, sender))
+            .await
+            .map_err(|_| stopped())?;
+        let info = receiver
             .instrument(span)
             .await
+            .map_err(|_| stopped())?
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
         Ok(tonic::Response::new(info))
@@ -212,17 +240,26 @@ impl Validator for AuthorityServer {
     ) -> Result<tonic::Response<TransactionInfoResponse>, tonic::Status> {
         let transaction = request.into_inner();
 
+        // Follow the authority when it moves to the committee of a new epoch.
+        self.consensus_adapter
+            .update_committee(&self.state.committee());
+
         // In some cases we can skip consensus for shared-object transactions: (i) we already executed
         // the transaction, (ii) we already assigned locks to the transaction but failed to execute it.
         // The later scenario happens when the authority missed some of the transaction's dependencies;
         // we can thus try to re-execute it now.
-        let ConsensusTransaction::UserTransaction(certificate) = transaction.clone();
-        let info = match self
-            .state
-            .try_skip_consensus(certificate)
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?
-        {
+        let skipped = match &transaction {
+            ConsensusTransaction::UserTransaction(certificate)
+                if certificate.contains_shared_object() =>
+            {
+                self.state
+                    .try_skip_consensus(certificate.clone())
+                    .await
+                    .map_err(|e| tonic::Status::internal(e.to_string()))?
+            }
+            _ => None,
+        };
+        let info = match skipped {
             Some(info) => info,
             None => self
                 .consensus_adapter
@@ -297,3 +334,32 @@ impl Validator for AuthorityServer {
         Ok(tonic::Response::new(Box::pin(response)))
     }
 }
+
+/// Executes the certificates received by the server in batches, with the scheduler of the
+/// authority running the independent ones of a batch in parallel. A batch holds the
+/// certificates received while the previous one executed, so a lone certificate does not
+/// wait for others.
+async fn run_certificate_batcher(
+    state: Arc<AuthorityState>,
+    mut receiver: Receiver<PendingCertificate>,
+) {
+    while let Some(pending) = receiver.recv().await {
+        let mut batch = vec![pending];
+        while batch.len() < MAX_CERTIFICATE_BATCH_SIZE {
+            match receiver.try_recv() {
+                Ok(pending) => batch.push(pending),
+                Err(_) => break,
+            }
+        }
+        debug!(
+            batch_size = batch.len(),
+            "Executing a batch of certificates"
+        );
+        let (certificates, senders): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+        let results = state.handle_confirmation_transactions(certificates).await;
+        for (sender, result) in senders.into_iter().zip(results) {
+            // The request is gone if the client disconnected.
+            let _ = sender.send(result);
+        }
+    }
+}
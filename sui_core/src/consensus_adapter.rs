@@ -5,6 +5,7 @@ use multiaddr::Multiaddr;
 use narwhal_executor::SubscriberResult;
 use narwhal_types::TransactionProto;
 use narwhal_types::TransactionsClient;
+use parking_lot::RwLock;
 use std::{
     collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
@@ -60,8 +61,8 @@ pub struct ConsensusAdapter {
     /// The network address of the consensus node.
     _consensus_address: Multiaddr,
     consensus_client: TransactionsClient<sui_network::tonic::transport::Channel>,
-    /// The Sui committee information.
-    committee: Committee,
+    /// The Sui committee information, updated when the epoch changes.
+    committee: RwLock<Committee>,
     /// A channel to notify the consensus listener to take action for a transactions.
     tx_consensus_listener: Sender<ConsensusListenerMessage>,
     /// The maximum duration to wait from consensus before aborting the transaction. After
@@ -84,12 +85,20 @@ impl ConsensusAdapter {
         Self {
             _consensus_address: consensus_address,
             consensus_client,
-            committee,
+            committee: RwLock::new(committee),
             tx_consensus_listener,
             max_delay,
         }
     }
 
+    /// Check the certificates against the committee of a later epoch.
+    pub fn update_committee(&self, committee: &Committee) {
+        let mut current = self.committee.write();
+        if committee.epoch > current.epoch {
+            *current = committee.clone();
+        }
+    }
+
     /// Check if this authority should submit the transaction to consensus.
     fn should_submit(_certificate: &ConsensusTransaction) -> bool {
         // TODO [issue #1647]: Right now every authority submits the transaction to consensus.
@@ -102,7 +111,7 @@ impl ConsensusAdapter {
         certificate: &ConsensusTransaction,
     ) -> SuiResult<TransactionInfoResponse> {
         // Check the Sui certificate (submitted by the user).
-        certificate.verify(&self.committee)?;
+        certificate.verify(&self.committee.read())?;
 
         // Serialize the certificate in a way that is understandable to consensus (i.e., using
         // bincode) and it certificate to consensus.
@@ -0,0 +1,367 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use parking_lot::RwLock;
+use sui_config::EpochChangeConfig;
+use sui_types::{
+    base_types::{AuthorityName, ObjectRef, SuiAddress},
+    committee::EpochId,
+    crypto::{KeyPair, Signature},
+    error::{SuiError, SuiResult},
+    gas_coin::GasCoin,
+    messages::{ConsensusTransaction, Transaction, TransactionData, TransactionEffects},
+    sui_system_state::{SuiSystemState, SUI_SYSTEM_STATE_OBJECT_ID},
+};
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+use crate::{
+    authority::AuthorityState,
+    authority_aggregator::AuthorityAggregator,
+    authority_client::{AuthorityAPI, AuthorityConnector},
+    safe_client::SafeClient,
+};
+
+/*
+
+At the end of each epoch, every validator halts, so that it only signs the transactions
+ending the epoch. Certificates without shared objects are executed by each authority as
+soon as it receives them, so the authorities may not have executed the same ones: each
+validator then stops executing them outside of consensus, sequences those it executed
+during the epoch, and announces the end of its epoch through consensus. Every authority
+thus executes the certificates executed by any of the validators which sealed the epoch.
+
+Once a quorum of the stake sealed the epoch, each validator reports on chain the gas fees
+it collected, since it only knows the fees of the certificates it executed. Once the
+validators holding a quorum of the stake reported, it submits the transaction changing
+the epoch, which distributes the median of the reports. Executing it moves every
+authority to the committee recorded in the Sui system state, after which the certificates
+of the previous epoch are rejected. The driver then reaches the authorities through the
+clients of the new committee.
+
+A validator that fails to end the epoch before announcing its end resumes, and tries
+again. Once announced, it stays halted until the epoch changes.
+
+*/
+
+/// How long to wait before submitting the epoch change again, for instance while the
+/// other validators have not halted yet.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The maximum number of certificates sequenced at once at the end of the epoch.
+const MAX_EPOCH_CERTIFICATES_CHUNK: usize = 100;
+
+pub struct EpochChangeDriver<A> {
+    state: Arc<AuthorityState>,
+    /// The authorities of the current epoch, swapped when the committee changes.
+    net: RwLock<Arc<AuthorityAggregator<A>>>,
+    /// The key of the validator, which sends the transaction changing the epoch.
+    key_pair: KeyPair,
+    connect: AuthorityConnector<A>,
+}
+
+/// Resumes a halted validator when dropped, unless the end of its epoch was announced.
+struct HaltGuard<'a> {
+    state: &'a AuthorityState,
+    announced: bool,
+}
+
+impl Drop for HaltGuard<'_> {
+    fn drop(&mut self) {
+        if !self.announced {
+            warn!(
+                epoch = self.state.epoch(),
+                "Resuming the validator, the end of the epoch was not announced"
+            );
+            self.state.resume_validator();
+        }
+    }
+}
+
+impl<A> EpochChangeDriver<A>
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    pub fn new(
+        state: Arc<AuthorityState>,
+        authority_clients: BTreeMap<AuthorityName, A>,
+        key_pair: KeyPair,
+        connect: AuthorityConnector<A>,
+    ) -> Self {
+        let committee = state.committee().as_ref().clone();
+        Self {
+            state,
+            net: RwLock::new(Arc::new(AuthorityAggregator::new(
+                committee,
+                authority_clients,
+            ))),
+            key_pair,
+            connect,
+        }
+    }
+
+    /// The authorities of the current epoch.
+    pub fn net(&self) -> Arc<AuthorityAggregator<A>> {
+        self.net.read().clone()
+    }
+
+    fn address(&self) -> SuiAddress {
+        SuiAddress::from(self.key_pair.public_key_bytes())
+    }
+
+    /// End an epoch every `epoch_duration_ms`. A failed attempt is retried until the
+    /// epoch changes.
+    pub async fn run(&self, config: EpochChangeConfig) {
+        info!(
+            epoch_duration_ms = config.epoch_duration_ms,
+            "Starting the epoch change driver"
+        );
+        loop {
+            sleep(Duration::from_millis(config.epoch_duration_ms)).await;
+            let epoch = self.state.epoch();
+            while self.state.epoch() == epoch {
+                if let Err(e) = self.change_epoch(config.gas_budget).await {
+                    error!(epoch, "Failed to change the epoch: {e}");
+                    sleep(RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+
+    /// Halt this validator, seal the epoch, report the gas fees it collected during the
+    /// epoch, and submit the transaction changing the epoch until the authority moved to
+    /// the next one. Then move to the authorities of its committee.
+    pub async fn change_epoch(&self, gas_budget: u64) -> SuiResult {
+        self.follow_committee()?;
+        let epoch = self.state.epoch();
+        let mut gas_payment = self.gas_payment(gas_budget)?;
+
+        info!(epoch, "Halting at the end of the epoch");
+        self.state.halt_validator();
+        let mut halt = HaltGuard {
+            state: &self.state,
+            announced: false,
+        };
+        self.seal_epoch(&mut halt).await?;
+        self.wait_for_sealed_epoch(epoch).await;
+
+        // Each transaction is built once and submitted again as is, so that the gas coin is
+        // never locked on two different transactions.
+        let report = loop {
+            match self
+                .state
+                .new_report_gas_charges_transaction(
+                    self.address(),
+                    SUI_SYSTEM_STATE_OBJECT_ID,
+                    gas_payment,
+                    gas_budget,
+                )
+                .await
+            {
+                Ok(data) => break self.sign(data),
+                Err(e) => {
+                    warn!(epoch, "Failed to build the gas charges report: {e}");
+                    sleep(RETRY_DELAY).await;
+                }
+            }
+        };
+        while self.state.epoch() == epoch {
+            match self.submit(&report).await {
+                Ok(effects) => {
+                    gas_payment = effects.gas_object.0;
+                    break;
+                }
+                Err(e) => {
+                    warn!(epoch, "Failed to report the gas charges: {e}");
+                    sleep(RETRY_DELAY).await;
+                }
+            }
+        }
+
+        // The epoch changes once the validators holding a quorum of the stake reported their
+        // fees. A change that failed, for instance because it raced with the last reports,
+        // is built again with the gas coin it was charged to.
+        let mut change_epoch = None;
+        while self.state.epoch() == epoch {
+            if change_epoch.is_none() {
+                change_epoch = match self
+                    .new_change_epoch_transaction(gas_payment, gas_budget)
+                    .await
+                {
+                    Ok(transaction) => transaction,
+                    Err(e) => {
+                        warn!(epoch, "Failed to build the epoch change: {e}");
+                        None
+                    }
+                };
+            }
+            if let Some(transaction) = &change_epoch {
+                match self.submit(transaction).await {
+                    Ok(effects) if !effects.status.is_ok() => {
+                        gas_payment = effects.gas_object.0;
+                        change_epoch = None;
+                    }
+                    Ok(_) => (),
+                    Err(e) => warn!(epoch, "Failed to change the epoch: {e}"),
+                }
+            }
+            if self.state.epoch() == epoch {
+                sleep(RETRY_DELAY).await;
+            }
+        }
+
+        self.follow_committee()
+    }
+
+    /// Stop executing certificates without shared objects outside of consensus, sequence
+    /// the ones this validator executed during the epoch, and announce the end of its
+    /// epoch. From the announcement on, the validator stays halted.
+    async fn seal_epoch(&self, halt: &mut HaltGuard<'_>) -> SuiResult {
+        let client = self.own_client()?;
+        let certificates = self.state.seal_direct_execution().await?;
+        info!(
+            epoch = self.state.epoch(),
+            num_certificates = certificates.len(),
+            "Sequencing the certificates executed during the epoch"
+        );
+        for chunk in certificates.chunks(MAX_EPOCH_CERTIFICATES_CHUNK) {
+            client
+                .handle_consensus_transaction(ConsensusTransaction::EpochCertificates(
+                    chunk.to_vec(),
+                ))
+                .await?;
+        }
+
+        // The announcement may be sequenced even if its submission fails, so the validator
+        // cannot resume once it is submitted.
+        halt.announced = true;
+        self.announce_end_of_epoch(&client).await;
+        Ok(())
+    }
+
+    async fn announce_end_of_epoch(&self, client: &SafeClient<A>) {
+        let end_of_epoch = ConsensusTransaction::EndOfEpoch(self.state.new_end_of_epoch());
+        if let Err(e) = client.handle_consensus_transaction(end_of_epoch).await {
+            warn!(
+                epoch = self.state.epoch(),
+                "Failed to announce the end of the epoch: {e}"
+            );
+        }
+    }
+
+    /// Wait until the validators holding a quorum of the stake sealed the epoch, announcing
+    /// the end of the epoch of this validator again until it is sequenced.
+    async fn wait_for_sealed_epoch(&self, epoch: EpochId) {
+        let quorum_threshold = self.state.committee().quorum_threshold();
+        while self.state.epoch() == epoch {
+            match self.state.db().sealed_authorities(epoch) {
+                Ok(sealed) if !sealed.contains(&self.state.name) => match self.own_client() {
+                    Ok(client) => self.announce_end_of_epoch(&client).await,
+                    Err(e) => warn!(epoch, "Failed to announce the end of the epoch: {e}"),
+                },
+                Ok(_) => match self.state.sealed_stake() {
+                    Ok(stake) if stake >= quorum_threshold => break,
+                    Ok(_) => (),
+                    Err(e) => warn!(epoch, "Failed to read the sealed stake: {e}"),
+                },
+                Err(e) => warn!(epoch, "Failed to read the sealed authorities: {e}"),
+            }
+            sleep(RETRY_DELAY).await;
+        }
+        info!(epoch, "A quorum sealed the epoch");
+    }
+
+    /// The transaction changing the epoch, once the validators holding a quorum of the
+    /// stake reported their gas fees.
+    async fn new_change_epoch_transaction(
+        &self,
+        gas_payment: ObjectRef,
+        gas_budget: u64,
+    ) -> SuiResult<Option<Transaction>> {
+        if !self.system_state()?.gas_charges_reported() {
+            return Ok(None);
+        }
+        let data = self
+            .state
+            .new_change_epoch_transaction(
+                self.address(),
+                SUI_SYSTEM_STATE_OBJECT_ID,
+                gas_payment,
+                gas_budget,
+            )
+            .await?;
+        Ok(Some(self.sign(data)))
+    }
+
+    /// The client of the authority of this validator, which sequences its certificates.
+    fn own_client(&self) -> SuiResult<SafeClient<A>> {
+        self.net()
+            .authority_clients
+            .get(&self.state.name)
+            .cloned()
+            .ok_or(SuiError::UnknownSigner)
+    }
+
+    /// Move to the authorities of the committee recorded in the Sui system state, if it
+    /// changed.
+    fn follow_committee(&self) -> SuiResult {
+        let system_state = self.system_state()?;
+        let net = self.net();
+        if system_state.epoch <= net.committee.epoch {
+            return Ok(());
+        }
+        let net = net.reconfigure_from_system_state(&system_state, &*self.connect)?;
+        *self.net.write() = Arc::new(net);
+        info!(epoch = system_state.epoch, "Moved to the next committee");
+        Ok(())
+    }
+
+    /// The Sui system state, as executed by this authority.
+    fn system_state(&self) -> SuiResult<SuiSystemState> {
+        let object = self
+            .state
+            .db()
+            .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)?
+            .ok_or(SuiError::ObjectNotFound {
+                object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+            })?;
+        SuiSystemState::from_object(&object)
+    }
+
+    fn sign(&self, data: TransactionData) -> Transaction {
+        let signature = Signature::new(&data, &self.key_pair);
+        Transaction::new(data, signature)
+    }
+
+    async fn submit(&self, transaction: &Transaction) -> SuiResult<TransactionEffects> {
+        let (_, effects) = self
+            .net()
+            .execute_transaction(transaction)
+            .await
+            .map_err(|e| SuiError::InvalidEpochChange {
+                error: e.to_string(),
+            })?;
+        Ok(effects)
+    }
+
+    /// A gas coin of this validator covering `gas_budget` at the reference gas price.
+    fn gas_payment(&self, gas_budget: u64) -> SuiResult<ObjectRef> {
+        let cost = gas_budget.saturating_mul(self.state.reference_gas_price());
+        let store = self.state.db();
+        for (object_id, _, _) in store.get_account_objects(self.address())? {
+            if let Some(object) = store.get_object(&object_id)? {
+                if matches!(GasCoin::try_from(&object), Ok(coin) if coin.value() >= cost) {
+                    return Ok(object.compute_object_reference());
+                }
+            }
+        }
+        Err(SuiError::InvalidEpochChange {
+            error: format!(
+                "Validator {} has no gas coin covering {cost}",
+                self.address()
+            ),
+        })
+    }
+}
@@ -3,17 +3,20 @@
 
 use std::{collections::BTreeSet, sync::Arc};
 
-use crate::authority::AuthorityTemporaryStore;
+use crate::authority::{AuthorityTemporaryStore, SuiDataStore};
+use crate::transaction_input_checker;
+use itertools::Itertools;
 use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
+use serde::{Deserialize, Serialize};
 use sui_adapter::adapter;
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest, TxContext},
     error::SuiResult,
-    gas::{self, SuiGasStatus},
+    gas::{self, SuiCostSchedule, SuiGasStatus},
     messages::{
-        ExecutionStatus, MoveCall, MoveModulePublish, SingleTransactionKind, TransactionData,
-        TransactionEffects, TransferCoin,
+        DryRunTransactionResponse, ExecutionStatus, InputObjectKind, MoveCall, MoveModulePublish,
+        SingleTransactionKind, TransactionData, TransactionEffects, TransferCoin,
     },
     object::Object,
     storage::{BackingPackageStore, Storage},
@@ -65,6 +68,58 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore>(
     Ok(effects)
 }
 
+/// Executes a transaction against the current state of `store`, and returns the effects it
+/// would have. Nothing is written to the store: no lock is taken, and the transaction does
+/// not need to be signed. Shared objects are read at their current version, as if the
+/// transaction was sequenced next.
+pub async fn dry_run_transaction<const A: bool, S>(
+    store: &Arc<SuiDataStore<A, S>>,
+    transaction_data: TransactionData,
+    cost_schedule: &SuiCostSchedule,
+    move_vm: &Arc<MoveVM>,
+    native_functions: &NativeFunctionTable,
+) -> SuiResult<DryRunTransactionResponse>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let transaction_digest = transaction_data.digest();
+    let (gas_status, objects_by_kind) = transaction_input_checker::check_transaction_data_input(
+        store,
+        &transaction_data,
+        cost_schedule,
+    )
+    .await?;
+
+    let shared_object_refs: Vec<_> = objects_by_kind
+        .iter()
+        .filter(|(kind, _)| matches!(kind, InputObjectKind::SharedMoveObject(_)))
+        .map(|(_, obj)| obj.compute_object_reference())
+        .sorted()
+        .collect();
+    let transaction_dependencies = objects_by_kind
+        .iter()
+        .map(|(_, obj)| obj.previous_transaction)
+        .collect();
+    let mut temporary_store =
+        AuthorityTemporaryStore::new(store.clone(), objects_by_kind, transaction_digest);
+    let effects = execute_transaction_to_effects(
+        shared_object_refs,
+        &mut temporary_store,
+        transaction_data,
+        transaction_digest,
+        transaction_dependencies,
+        move_vm,
+        native_functions,
+        gas_status,
+    )?;
+
+    Ok(DryRunTransactionResponse {
+        events: effects.events.clone(),
+        gas_cost_summary: effects.status.gas_cost_summary().clone(),
+        effects,
+    })
+}
+
 fn charge_gas_for_object_read<S>(
     temporary_store: &AuthorityTemporaryStore<S>,
     gas_status: &mut SuiGasStatus,
@@ -0,0 +1,423 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+use tracing::error;
+
+use sui_config::genesis::Genesis;
+use sui_types::{
+    base_types::{AuthorityName, ObjectID, ObjectRef, SuiAddress, TransactionDigest},
+    committee::Committee,
+    error::{SuiError, SuiResult},
+    event::{EventEnvelope, EventFilter, EventID},
+    messages::{DryRunTransactionResponse, Transaction, TransactionData},
+    object::ObjectRead,
+    sui_system_state::{SuiSystemState, SUI_SYSTEM_STATE_OBJECT_ID},
+};
+
+use crate::{
+    authority::ReplicaStore,
+    authority_client::AuthorityAPI,
+    gateway_state::{
+        gateway_requests::SingleTransactionParams, gateway_responses::*, notification_stream,
+        store_reads, GatewayAPI, GatewayState, GatewayTxSeqNumber,
+    },
+    replica::ReplicaNode,
+    sui_json::SuiJsonValue,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/full_node_tests.rs"]
+mod full_node_tests;
+
+/*
+
+A full node follows the batch streams of the authorities like a replica, and serves the
+reads of the gateway API from its own store, which holds the whole state of the network.
+It does not take part in the protocol: the transactions it is asked to build are built
+from its store, and executed by a gateway forwarding them to the committee. They become
+visible to its reads, and to its subscribers, once the replica has re-executed them. The
+gateway moves to the committee of each epoch the replica moves to.
+
+*/
+
+const REPLICA_DB_NAME: &str = "replica_db";
+const GATEWAY_DB_NAME: &str = "gateway_db";
+
+pub struct FullNodeState<A> {
+    replica: Arc<ReplicaNode<A>>,
+    store: Arc<ReplicaStore>,
+    gateway: Arc<GatewayState<A>>,
+    /// The clients of the authorities the full node knows of, handed to the gateway when
+    /// they join the committee.
+    authority_clients: BTreeMap<AuthorityName, A>,
+}
+
+impl<A> FullNodeState<A>
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    /// Create a full node following the given authorities, which stores its state under
+    /// `path`. An empty store is first initialized with the genesis state.
+    pub async fn new(
+        path: PathBuf,
+        committee: Committee,
+        authority_clients: BTreeMap<AuthorityName, A>,
+        genesis: &Genesis,
+    ) -> SuiResult<Self> {
+        let store = Arc::new(ReplicaStore::open(path.join(REPLICA_DB_NAME), None));
+        let replica = ReplicaNode::new(
+            committee.clone(),
+            store.clone(),
+            authority_clients.clone(),
+            genesis,
+        )
+        .await?;
+        let gateway = GatewayState::new(
+            path.join(GATEWAY_DB_NAME),
+            committee,
+            authority_clients.clone(),
+        )?
+        .with_replica_store(store.clone());
+        gateway.recover_pending_transactions().await?;
+        Ok(Self {
+            replica: Arc::new(replica),
+            store,
+            gateway: Arc::new(gateway),
+            authority_clients,
+        })
+    }
+
+    /// Start following the authorities in the background, and moving the gateway to the
+    /// committee of each epoch the replica moves to.
+    pub fn spawn_replica(&self) -> JoinHandle<()> {
+        let mut receiver = self.replica.subscribe();
+        let replica = self.replica.clone();
+        let store = self.store.clone();
+        let gateway = self.gateway.clone();
+        let authority_clients = self.authority_clients.clone();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(_) | Err(RecvError::Lagged(_)) => (),
+                    Err(RecvError::Closed) => return,
+                }
+                if let Err(e) =
+                    reconfigure_gateway(&replica, &store, &gateway, authority_clients.clone())
+                {
+                    error!("Failed to move the gateway to the next committee: {e}");
+                }
+            }
+        });
+        tokio::spawn(self.replica.clone().run())
+    }
+
+    pub fn store(&self) -> Arc<ReplicaStore> {
+        self.store.clone()
+    }
+}
+
+/// Move the gateway to the committee, reference gas price and cost schedule of the epoch of
+/// the replica, if the replica moved past the epoch of the gateway.
+fn reconfigure_gateway<A>(
+    replica: &ReplicaNode<A>,
+    store: &ReplicaStore,
+    gateway: &GatewayState<A>,
+    authority_clients: BTreeMap<AuthorityName, A>,
+) -> SuiResult
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    let committee = replica.committee();
+    if committee.epoch <= gateway.authorities().committee.epoch {
+        return Ok(());
+    }
+    let system_state =
+        store
+            .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)?
+            .ok_or(SuiError::ObjectNotFound {
+                object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+            })?;
+    let system_state = SuiSystemState::from_object(&system_state)?;
+    let (_, cost_schedule) = store.epoch_cost_schedule(committee.epoch)?;
+    gateway.reconfigure(
+        committee.as_ref().clone(),
+        system_state.reference_gas_price,
+        authority_clients,
+    )?;
+    gateway.set_cost_schedule(cost_schedule);
+    Ok(())
+}
+
+#[async_trait]
+impl<A> GatewayAPI for FullNodeState<A>
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    async fn execute_transaction(
+        &self,
+        tx: Transaction,
+    ) -> Result<TransactionResponse, anyhow::Error> {
+        self.gateway.execute_transaction(tx).await
+    }
+
+    async fn dry_run_transaction(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionResponse, anyhow::Error> {
+        Ok(self.replica.dry_run_transaction(tx_data).await?)
+    }
+
+    async fn transfer_coin(
+        &self,
+        signer: SuiAddress,
+        object_id: ObjectID,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+        recipient: SuiAddress,
+    ) -> Result<TransactionData, anyhow::Error> {
+        self.gateway
+            .transfer_coin(signer, object_id, gas, gas_budget, recipient)
+            .await
+    }
+
+    async fn sync_account_state(&self, account_addr: SuiAddress) -> Result<(), anyhow::Error> {
+        self.gateway.sync_account_state(account_addr).await
+    }
+
+    async fn move_call(
+        &self,
+        signer: SuiAddress,
+        package_object_id: ObjectID,
+        module: Identifier,
+        function: Identifier,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<SuiJsonValue>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        self.gateway
+            .move_call(
+                signer,
+                package_object_id,
+                module,
+                function,
+                type_arguments,
+                arguments,
+                gas,
+                gas_budget,
+            )
+            .await
+    }
+
+    async fn publish(
+        &self,
+        signer: SuiAddress,
+        package_bytes: Vec<Vec<u8>>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        self.gateway
+            .publish(signer, package_bytes, gas, gas_budget)
+            .await
+    }
+
+    async fn split_coin(
+        &self,
+        signer: SuiAddress,
+        coin_object_id: ObjectID,
+        split_amounts: Vec<u64>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        self.gateway
+            .split_coin(signer, coin_object_id, split_amounts, gas, gas_budget)
+            .await
+    }
+
+    async fn merge_coins(
+        &self,
+        signer: SuiAddress,
+        primary_coin: ObjectID,
+        coins_to_merge: Vec<ObjectID>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        self.gateway
+            .merge_coins(signer, primary_coin, coins_to_merge, gas, gas_budget)
+            .await
+    }
+
+    async fn merge_all_coins(
+        &self,
+        signer: SuiAddress,
+        coin_type: TypeTag,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        self.gateway
+            .merge_all_coins(signer, coin_type, gas, gas_budget)
+            .await
+    }
+
+    async fn batch_transaction(
+        &self,
+        signer: SuiAddress,
+        single_transaction_params: Vec<SingleTransactionParams>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        self.gateway
+            .batch_transaction(signer, single_transaction_params, gas, gas_budget)
+            .await
+    }
+
+    async fn merge_gas_coins(
+        &self,
+        signer: SuiAddress,
+        gas_budget: u64,
+        excluded_coins: Vec<ObjectID>,
+    ) -> Result<Option<TransactionData>, anyhow::Error> {
+        self.gateway
+            .merge_gas_coins(signer, gas_budget, excluded_coins)
+            .await
+    }
+
+    async fn get_object_info(&self, object_id: ObjectID) -> Result<ObjectRead, anyhow::Error> {
+        Ok(store_reads::get_object_read(&self.store, object_id)?)
+    }
+
+    async fn get_owned_objects(
+        &self,
+        account_addr: SuiAddress,
+    ) -> Result<Vec<ObjectRef>, anyhow::Error> {
+        Ok(self.store.get_account_objects(account_addr)?)
+    }
+
+    async fn get_owned_objects_page(
+        &self,
+        account_addr: SuiAddress,
+        object_type: Option<StructTag>,
+        cursor: Option<ObjectID>,
+        limit: u64,
+        show_details: bool,
+    ) -> Result<OwnedObjectPage, anyhow::Error> {
+        store_reads::get_owned_objects_page(
+            &self.store,
+            account_addr,
+            object_type,
+            cursor,
+            limit,
+            show_details,
+        )
+    }
+
+    async fn get_balance(
+        &self,
+        address: SuiAddress,
+        coin_type: TypeTag,
+    ) -> Result<Balance, anyhow::Error> {
+        store_reads::get_balance(&self.store, address, coin_type)
+    }
+
+    async fn get_all_balances(&self, address: SuiAddress) -> Result<Vec<Balance>, anyhow::Error> {
+        store_reads::get_all_balances(&self.store, address)
+    }
+
+    fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error> {
+        Ok(self.store.next_sequence_number()?)
+    }
+
+    fn get_transactions_in_range(
+        &self,
+        start: GatewayTxSeqNumber,
+        end: GatewayTxSeqNumber,
+    ) -> Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error> {
+        store_reads::get_transactions_in_range(&self.store, start, end)
+    }
+
+    fn get_recent_transactions(
+        &self,
+        count: u64,
+    ) -> Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error> {
+        store_reads::get_recent_transactions(&self.store, count)
+    }
+
+    async fn get_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<TransactionEffectsResponse, anyhow::Error> {
+        store_reads::get_transaction(&self.store, digest)
+    }
+
+    async fn get_transactions_by_address(
+        &self,
+        address: SuiAddress,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> Result<TransactionPage, anyhow::Error> {
+        store_reads::get_transactions_by_address(&self.store, address, cursor, limit)
+    }
+
+    async fn get_transactions_by_object(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> Result<TransactionPage, anyhow::Error> {
+        store_reads::get_transactions_by_object(&self.store, object_id, cursor, limit)
+    }
+
+    async fn get_events(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+        limit: u64,
+    ) -> Result<EventPage, anyhow::Error> {
+        store_reads::get_events(&self.store, filter, cursor, limit)
+    }
+
+    fn subscribe_events(
+        &self,
+        filter: EventFilter,
+    ) -> Result<BoxStream<'static, EventEnvelope>, anyhow::Error> {
+        Ok(notification_stream(
+            self.replica.subscribe(),
+            move |transaction| {
+                transaction
+                    .events
+                    .iter()
+                    .filter(|event| filter.matches(event, &transaction.object_ids))
+                    .cloned()
+                    .collect()
+            },
+        ))
+    }
+
+    fn subscribe_transactions(
+        &self,
+    ) -> Result<BoxStream<'static, (GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error> {
+        Ok(notification_stream(
+            self.replica.subscribe(),
+            |transaction| vec![(transaction.sequence_number, transaction.digest)],
+        ))
+    }
+
+    fn subscribe_object_changes(
+        &self,
+        address: SuiAddress,
+    ) -> Result<BoxStream<'static, ObjectChangeNotification>, anyhow::Error> {
+        Ok(notification_stream(
+            self.replica.subscribe(),
+            move |transaction| transaction.object_changes(address),
+        ))
+    }
+}
@@ -10,42 +10,57 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
-use futures::future;
+use futures::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use move_binary_format::file_format::CompiledModule;
 use move_core_types::identifier::Identifier;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use prometheus_exporter::prometheus::{
     register_histogram, register_int_counter, Histogram, IntCounter,
 };
-use tracing::{debug, error, Instrument};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tracing::{debug, error, info, warn, Instrument};
 
-use sui_adapter::adapter::resolve_and_type_check;
+use sui_adapter::adapter::{self, resolve_and_type_check};
 use sui_types::gas_coin::GasCoin;
 use sui_types::{
     base_types::*,
-    coin,
+    coin::{self, Coin},
     committee::Committee,
     error::{SuiError, SuiResult},
-    fp_ensure,
+    event::{EventEnvelope, EventFilter, EventID},
+    fp_bail, fp_ensure,
+    gas::{self, SuiCostSchedule},
     messages::*,
-    object::{Object, ObjectRead},
-    SUI_FRAMEWORK_ADDRESS,
+    object::{Object, ObjectRead, Owner},
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
 };
 
 use crate::sui_json::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
-use crate::transaction_input_checker;
 use crate::{
-    authority::GatewayStore, authority_aggregator::AuthorityAggregator,
-    authority_client::AuthorityAPI,
+    authority::{GatewayStore, ReplicaStore},
+    authority_aggregator::{AuthorityAggregator, QuorumDriverConfig},
+    authority_client::{AuthorityAPI, AuthorityConnector},
 };
+use crate::{execution_engine, transaction_input_checker};
 
+use self::gateway_requests::*;
 use self::gateway_responses::*;
 
 #[cfg(test)]
 #[path = "unit_tests/gateway_state_tests.rs"]
 mod gateway_state_tests;
 
+pub mod gateway_requests;
 pub mod gateway_responses;
+pub mod store_reads;
 
 pub type AsyncResult<'a, T, E> = future::BoxFuture<'a, Result<T, E>>;
 
@@ -53,9 +68,15 @@ pub type GatewayClient = Box<dyn GatewayAPI + Sync + Send>;
 
 pub type GatewayTxSeqNumber = u64;
 
-const MAX_TX_RANGE_SIZE: u64 = 4096;
+pub const MAX_TX_RANGE_SIZE: u64 = 4096;
+const MAX_EVENT_PAGE_SIZE: u64 = 4096;
+pub const MAX_OBJECT_PAGE_SIZE: u64 = 4096;
+/// Number of executed transactions buffered for slow subscribers
+pub(crate) const NOTIFICATION_CHANNEL_CAPACITY: usize = 10_000;
 /// Number of times to retry failed TX
 const MAX_NUM_TX_RETRIES: usize = 5;
+/// Margin added to estimated gas budgets, in percent of the gas used by the simulation.
+pub const DEFAULT_GAS_ESTIMATION_MARGIN: u64 = 20;
 
 /// Prometheus metrics which can be displayed in Grafana, queried and alerted on
 pub struct GatewayMetrics {
@@ -67,6 +88,8 @@ pub struct GatewayMetrics {
     num_tx_mergecoin: IntCounter,
     total_tx_retries: IntCounter,
     shared_obj_tx: IntCounter,
+    total_tx_recovered: IntCounter,
+    total_tx_released: IntCounter,
     pub total_tx_certificates: IntCounter,
     pub num_signatures: Histogram,
     pub num_good_stake: Histogram,
@@ -128,6 +151,16 @@ impl GatewayMetrics {
                 "Number of transactions involving shared objects"
             )
             .unwrap(),
+            total_tx_recovered: register_int_counter!(
+                "gateway_pending_tx_recovered",
+                "Number of pending transactions executed when recovering at startup"
+            )
+            .unwrap(),
+            total_tx_released: register_int_counter!(
+                "gateway_pending_tx_released",
+                "Number of pending transactions released when recovering at startup"
+            )
+            .unwrap(),
             // It's really important to use the right histogram buckets for accurate histogram collection.
             // Otherwise values get clipped
             num_signatures: register_histogram!(
@@ -168,7 +201,8 @@ impl Default for GatewayMetrics {
 pub static METRICS: Lazy<GatewayMetrics> = Lazy::new(GatewayMetrics::new);
 
 pub struct GatewayState<A> {
-    authorities: AuthorityAggregator<A>,
+    /// The authorities of the current epoch, swapped when the committee changes.
+    authorities: RwLock<Arc<AuthorityAggregator<A>>>,
     store: Arc<GatewayStore>,
     /// Every transaction committed in authorities (and hence also committed in the Gateway)
     /// will have a unique sequence number. This number is specific to this gateway,
@@ -176,9 +210,206 @@ pub struct GatewayState<A> {
     /// It's useful if we need some kind of ordering for transactions
     /// from a gateway.
     next_tx_seq_number: AtomicU64,
+    /// The reference gas price of the current epoch, at which the gateway prices the
+    /// transactions it builds.
+    reference_gas_price: AtomicU64,
+    /// The cost schedule of the current epoch, with which the gateway checks that the gas
+    /// coins of the transactions cover their budget.
+    cost_schedule: RwLock<Arc<SuiCostSchedule>>,
+    /// When a transaction is built without a gas budget, the budget is the gas used
+    /// by a simulation of the transaction, plus this margin (in percent).
+    gas_estimation_margin: u64,
+    /// Executes the simulated transactions against the objects of the store, so that
+    /// validators never execute transactions which are not signed.
+    move_vm: Arc<MoveVM>,
+    native_functions: NativeFunctionTable,
+    /// Broadcasts the transactions executed by this gateway to subscribers.
+    notification_sender: broadcast::Sender<Arc<ExecutedTransaction>>,
+    /// Connects to the authorities joining the committee, when the gateway follows the
+    /// epoch changes.
+    connect_authority: Option<AuthorityConnector<A>>,
+    /// The store of the full node embedding the gateway, which holds the whole state: the
+    /// transactions are built from its objects rather than from the gateway store.
+    replica_store: Option<Arc<ReplicaStore>>,
     metrics: &'static GatewayMetrics,
 }
 
+/// A transaction executed by this gateway (or by the replica of a full node), as seen by
+/// subscribers.
+pub(crate) struct ExecutedTransaction {
+    pub(crate) sequence_number: GatewayTxSeqNumber,
+    pub(crate) digest: TransactionDigest,
+    effects: TransactionEffects,
+    pub(crate) events: Vec<EventEnvelope>,
+    /// The objects created, mutated, unwrapped, deleted or wrapped by the transaction
+    pub(crate) object_ids: BTreeSet<ObjectID>,
+    /// The owners of the input objects, before the transaction
+    input_owners: BTreeMap<ObjectID, Owner>,
+}
+
+impl ExecutedTransaction {
+    pub(crate) fn new(
+        sequence_number: GatewayTxSeqNumber,
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+        input_owners: BTreeMap<ObjectID, Owner>,
+    ) -> Self {
+        Self {
+            sequence_number,
+            digest: *certificate.digest(),
+            effects: effects.clone(),
+            events: EventEnvelope::from_transaction(
+                sequence_number,
+                *certificate.digest(),
+                certificate.data.signer(),
+                &effects.events,
+            ),
+            object_ids: effects.touched_object_ids(),
+            input_owners,
+        }
+    }
+
+    /// Returns the changes made by the transaction to the objects owned by `address`,
+    /// before or after it.
+    pub(crate) fn object_changes(&self, address: SuiAddress) -> Vec<ObjectChangeNotification> {
+        let owner = Owner::AddressOwner(address);
+        let was_owned = |id: &ObjectID| self.input_owners.get(id) == Some(&owner);
+        let notification = |object_ref: &ObjectRef, kind| ObjectChangeNotification {
+            tx_seq_number: self.sequence_number,
+            tx_digest: self.digest,
+            object_ref: *object_ref,
+            kind,
+        };
+        let effects = &self.effects;
+        let created = effects
+            .created
+            .iter()
+            .filter(|(_, new_owner)| *new_owner == owner)
+            .map(|(object_ref, _)| notification(object_ref, ObjectChangeKind::Created));
+        let unwrapped = effects
+            .unwrapped
+            .iter()
+            .filter(|(_, new_owner)| *new_owner == owner)
+            .map(|(object_ref, _)| notification(object_ref, ObjectChangeKind::Unwrapped));
+        let mutated = effects
+            .mutated
+            .iter()
+            .filter_map(|(object_ref, new_owner)| {
+                let kind = match (was_owned(&object_ref.0), *new_owner == owner) {
+                    (true, true) => ObjectChangeKind::Mutated,
+                    (false, true) => ObjectChangeKind::Received,
+                    (true, false) => ObjectChangeKind::Sent,
+                    (false, false) => return None,
+                };
+                Some(notification(object_ref, kind))
+            });
+        let deleted = effects
+            .deleted
+            .iter()
+            .filter(|object_ref| was_owned(&object_ref.0))
+            .map(|object_ref| notification(object_ref, ObjectChangeKind::Deleted));
+        let wrapped = effects
+            .wrapped
+            .iter()
+            .filter(|object_ref| was_owned(&object_ref.0))
+            .map(|object_ref| notification(object_ref, ObjectChangeKind::Wrapped));
+        created
+            .chain(unwrapped)
+            .chain(mutated)
+            .chain(deleted)
+            .chain(wrapped)
+            .collect()
+    }
+}
+
+/// Turns a receiver of executed transactions into a stream of the items `f` extracts from
+/// each of them, logging the transactions dropped because the subscriber lagged behind.
+pub(crate) fn notification_stream<T, F>(
+    receiver: broadcast::Receiver<Arc<ExecutedTransaction>>,
+    f: F,
+) -> BoxStream<'static, T>
+where
+    T: Send + 'static,
+    F: Fn(&ExecutedTransaction) -> Vec<T> + Send + 'static,
+{
+    let stream = BroadcastStream::new(receiver).flat_map(move |transaction| {
+        let items = match transaction {
+            Ok(transaction) => f(&transaction),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                error!(?skipped, "Subscriber lagged, notifications were dropped");
+                vec![]
+            }
+        };
+        stream::iter(items)
+    });
+    Box::pin(stream)
+}
+
+/// Whether the gateway store lacks an input object of a transaction, or holds an earlier
+/// version of it than the transaction uses.
+fn is_missing_or_outdated(object: &Option<Object>, kind: &InputObjectKind) -> bool {
+    match (object, kind) {
+        (None, _) => true,
+        (Some(object), InputObjectKind::ImmOrOwnedMoveObject((_, version, _))) => {
+            object.version() < *version
+        }
+        _ => false,
+    }
+}
+
+/// Whether a transaction input check failed because the gateway holds a later version
+/// of an input object than the transaction, or the object was deleted.
+fn is_outdated_input_error(err: &SuiError) -> bool {
+    match err {
+        SuiError::LockErrors { errors } => errors.iter().any(is_outdated_input_error),
+        SuiError::UnexpectedSequenceNumber {
+            expected_sequence,
+            given_sequence,
+            ..
+        } => expected_sequence > given_sequence,
+        SuiError::ObjectNotFound { .. } | SuiError::ObjectDeleted { .. } => true,
+        _ => false,
+    }
+}
+
+/// The total balance of `coins`, which may not fit in a u64.
+fn total_balance(coins: &[(ObjectRef, u64)]) -> u128 {
+    coins.iter().map(|(_, balance)| *balance as u128).sum()
+}
+
+/// A merge of gas coins leaving a coin which covers `gas_cost`, as (primary coin, coins to
+/// merge into it, gas coin of the merge). The smallest coin covering `merge_gas_cost` pays
+/// for the merge, and the fewest of the largest other coins are merged. Returns None if no
+/// merge covers `gas_cost`.
+fn plan_gas_coin_merge(
+    coins: &[(ObjectRef, u64)],
+    gas_cost: u64,
+    merge_gas_cost: u64,
+) -> Option<(ObjectID, Vec<ObjectID>, ObjectID)> {
+    let mut coins = coins.to_vec();
+    coins.sort_by_key(|(_, balance)| std::cmp::Reverse(*balance));
+    let gas_index = coins
+        .iter()
+        .rposition(|(_, balance)| *balance >= merge_gas_cost)?;
+    let (gas, _) = coins.remove(gas_index);
+
+    let mut merged_balance = 0u128;
+    let mut merged_count = 0;
+    while merged_balance < gas_cost as u128 {
+        merged_balance += coins.get(merged_count)?.1 as u128;
+        merged_count += 1;
+    }
+    // A single coin covering the budget needs no merge.
+    if merged_count < 2 {
+        return None;
+    }
+    let coins_to_merge = coins[1..merged_count]
+        .iter()
+        .map(|((id, _, _), _)| *id)
+        .collect();
+    Some((coins[0].0 .0, coins_to_merge, gas.0))
+}
+
 impl<A> GatewayState<A> {
     /// Create a new manager which stores its managed addresses at `path`
     pub fn new(
@@ -195,14 +426,109 @@ impl<A> GatewayState<A> {
     ) -> SuiResult<Self> {
         let store = Arc::new(GatewayStore::open(path, None));
         let next_tx_seq_number = AtomicU64::new(store.next_sequence_number()?);
+        let (notification_sender, _) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+        let native_functions =
+            sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+        let move_vm = Arc::new(
+            adapter::new_move_vm(native_functions.clone())
+                .expect("We defined natives to not fail here"),
+        );
         Ok(Self {
             store,
-            authorities,
+            authorities: RwLock::new(Arc::new(authorities)),
             next_tx_seq_number,
+            reference_gas_price: AtomicU64::new(gas::INITIAL_REFERENCE_GAS_PRICE),
+            cost_schedule: RwLock::new(Arc::new(gas::INITIAL_SUI_COST_SCHEDULE.clone())),
+            gas_estimation_margin: DEFAULT_GAS_ESTIMATION_MARGIN,
+            move_vm,
+            native_functions,
+            notification_sender,
+            connect_authority: None,
+            replica_store: None,
             metrics: &METRICS,
         })
     }
 
+    /// Set the margin (in percent) added to the simulated gas cost of transactions
+    /// built without a gas budget.
+    pub fn with_gas_estimation_margin(mut self, gas_estimation_margin: u64) -> Self {
+        self.gas_estimation_margin = gas_estimation_margin;
+        self
+    }
+
+    /// Set how transactions and certificates are driven to a quorum of authorities.
+    pub fn with_quorum_driver_config(mut self, config: QuorumDriverConfig) -> Self {
+        self.authorities_mut().config = config;
+        self
+    }
+
+    /// Follow the epoch changes, connecting to the authorities joining the committee
+    /// with `connect`.
+    pub fn with_authority_connector(mut self, connect: AuthorityConnector<A>) -> Self {
+        self.connect_authority = Some(connect);
+        self
+    }
+
+    /// Build transactions from the objects of the store of a full node, instead of the
+    /// objects known to the gateway.
+    pub fn with_replica_store(mut self, store: Arc<ReplicaStore>) -> Self {
+        self.replica_store = Some(store);
+        self
+    }
+
+    /// The authorities of the current epoch.
+    pub fn authorities(&self) -> Arc<AuthorityAggregator<A>> {
+        self.authorities.read().clone()
+    }
+
+    /// Only the requests in flight share the authorities, and they borrow the gateway.
+    fn authorities_mut(&mut self) -> &mut AuthorityAggregator<A> {
+        Arc::get_mut(self.authorities.get_mut()).expect("No request is in flight")
+    }
+
+    /// The reference gas price of the current epoch.
+    pub fn reference_gas_price(&self) -> u64 {
+        self.reference_gas_price
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Move to the committee and reference gas price of a later epoch. The authorities
+    /// staying in the committee are reached through the same clients, and `new_clients`
+    /// provides the ones joining it.
+    pub fn reconfigure(
+        &self,
+        committee: Committee,
+        reference_gas_price: u64,
+        new_clients: BTreeMap<AuthorityName, A>,
+    ) -> SuiResult
+    where
+        A: Clone,
+    {
+        fp_ensure!(
+            reference_gas_price > 0,
+            SuiError::InvalidEpochChange {
+                error: "The reference gas price must be positive".to_owned(),
+            }
+        );
+        let mut authorities = self.authorities.write();
+        let epoch = committee.epoch;
+        *authorities = Arc::new(authorities.reconfigure(committee, new_clients)?);
+        self.reference_gas_price
+            .store(reference_gas_price, std::sync::atomic::Ordering::SeqCst);
+        info!(?epoch, reference_gas_price, "Moved to a new committee");
+        Ok(())
+    }
+
+    /// Check the gas of transactions with the cost schedule of a new epoch.
+    pub fn set_cost_schedule(&self, cost_schedule: SuiCostSchedule) {
+        *self.cost_schedule.write() = Arc::new(cost_schedule);
+    }
+
+    /// The balance a gas coin needs to cover `gas_budget` at the reference gas price.
+    fn gas_cost(&self, gas_budget: u64) -> u64 {
+        gas_budget.saturating_mul(self.reference_gas_price())
+    }
+
     // Given a list of inputs from a transaction, fetch the objects
     // from the db.
     async fn read_objects_from_store(
@@ -215,8 +541,8 @@ impl<A> GatewayState<A> {
     }
 
     #[cfg(test)]
-    pub fn get_authorities(&self) -> &AuthorityAggregator<A> {
-        &self.authorities
+    pub fn get_authorities(&self) -> Arc<AuthorityAggregator<A>> {
+        self.authorities()
     }
 
     #[cfg(test)]
@@ -226,6 +552,9 @@ impl<A> GatewayState<A> {
 }
 
 // Operations are considered successful when they successfully reach a quorum of authorities.
+//
+// The transaction builders take an optional gas budget. Without one, the transaction is
+// simulated locally and the budget is derived from the gas it used.
 #[async_trait]
 pub trait GatewayAPI {
     async fn execute_transaction(
@@ -233,13 +562,20 @@ pub trait GatewayAPI {
         tx: Transaction,
     ) -> Result<TransactionResponse, anyhow::Error>;
 
+    /// Execute a transaction against the current state, without committing it. The
+    /// transaction does not need to be signed: it is executed locally, not by validators.
+    async fn dry_run_transaction(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionResponse, anyhow::Error>;
+
     /// Send coin object to a Sui address.
     async fn transfer_coin(
         &self,
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> Result<TransactionData, anyhow::Error>;
 
@@ -258,7 +594,7 @@ pub trait GatewayAPI {
         type_arguments: Vec<TypeTag>,
         arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
     /// Publish Move modules
@@ -267,7 +603,7 @@ pub trait GatewayAPI {
         signer: SuiAddress,
         package_bytes: Vec<Vec<u8>>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
     /// Split the coin object (identified by `coin_object_ref`) into
@@ -282,26 +618,58 @@ pub trait GatewayAPI {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
-    /// Merge the `coin_to_merge` coin object into `primary_coin`.
+    /// Merge the `coins_to_merge` coin objects into `primary_coin`, in a single transaction.
     /// After this merge, the balance of `primary_coin` will become the
-    /// sum of the two, while `coin_to_merge` will be deleted.
+    /// sum of all of them, while the `coins_to_merge` will be deleted.
     ///
     /// Returns a pair:
     ///  (update primary coin object reference, updated gas payment object reference)
-    ///
-    /// TODO: Support merging a vector of coins.
     async fn merge_coins(
         &self,
         signer: SuiAddress,
         primary_coin: ObjectID,
-        coin_to_merge: ObjectID,
+        coins_to_merge: Vec<ObjectID>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error>;
+
+    /// Merge all coins of `signer` with type `Coin<coin_type>` into the one with the
+    /// largest balance, in a single transaction. When merging SUI coins, the gas payment
+    /// coin is left out of the merge.
+    async fn merge_all_coins(
+        &self,
+        signer: SuiAddress,
+        coin_type: TypeTag,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error>;
+
+    /// Create a batch transaction, executing the transfers and Move calls of
+    /// `single_transaction_params` in order, in a single certificate. Either all of
+    /// them take effect, or none does.
+    async fn batch_transaction(
+        &self,
+        signer: SuiAddress,
+        single_transaction_params: Vec<SingleTransactionParams>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error>;
 
+    /// Create a transaction merging gas coins of `signer`, for when no single coin
+    /// covers `gas_budget`. The largest coins not in `excluded_coins` are merged until
+    /// they cover the budget, and the smallest remaining coin which can pay for the merge
+    /// does. Returns None if a coin already covers the budget, and an error if no merge
+    /// leaves a coin covering it.
+    async fn merge_gas_coins(
+        &self,
+        signer: SuiAddress,
+        gas_budget: u64,
+        excluded_coins: Vec<ObjectID>,
+    ) -> Result<Option<TransactionData>, anyhow::Error>;
+
     /// Get the object information
     async fn get_object_info(&self, object_id: ObjectID) -> Result<ObjectRead, anyhow::Error>;
 
@@ -311,6 +679,30 @@ pub trait GatewayAPI {
         account_addr: SuiAddress,
     ) -> Result<Vec<ObjectRef>, anyhow::Error>;
 
+    /// Return up to `limit` objects owned by `account_addr` from local cache, in object ID
+    /// order and starting at the object `cursor`. If `object_type` is given, only the Move
+    /// objects of this type are returned, and a type without type parameters matches all
+    /// its instantiations. The type and owner of the objects are returned if `show_details` is set.
+    async fn get_owned_objects_page(
+        &self,
+        account_addr: SuiAddress,
+        object_type: Option<StructTag>,
+        cursor: Option<ObjectID>,
+        limit: u64,
+        show_details: bool,
+    ) -> Result<OwnedObjectPage, anyhow::Error>;
+
+    /// Return the number and total value of the coins of type `Coin<coin_type>` owned
+    /// by `address`, from local cache.
+    async fn get_balance(
+        &self,
+        address: SuiAddress,
+        coin_type: TypeTag,
+    ) -> Result<Balance, anyhow::Error>;
+
+    /// Return the balances of `address` in every coin type it owns, from local cache.
+    async fn get_all_balances(&self, address: SuiAddress) -> Result<Vec<Balance>, anyhow::Error>;
+
     /// Get the total number of transactions ever happened in history.
     fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error>;
 
@@ -333,12 +725,101 @@ pub trait GatewayAPI {
         &self,
         digest: TransactionDigest,
     ) -> Result<TransactionEffectsResponse, anyhow::Error>;
+
+    /// Return up to `limit` transactions sent by or to `address`, in execution order
+    /// and starting at the transaction sequence number `cursor`.
+    async fn get_transactions_by_address(
+        &self,
+        address: SuiAddress,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> Result<TransactionPage, anyhow::Error>;
+
+    /// Return up to `limit` transactions that touched `object_id`, in execution order
+    /// and starting at the transaction sequence number `cursor`.
+    async fn get_transactions_by_object(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> Result<TransactionPage, anyhow::Error>;
+
+    /// Return up to `limit` events matching `filter`, emitted by the transactions executed
+    /// by this gateway, in execution order and starting at the event `cursor`.
+    async fn get_events(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+        limit: u64,
+    ) -> Result<EventPage, anyhow::Error>;
+
+    /// Subscribe to the events matching `filter`, emitted by the transactions executed
+    /// by this gateway from now on.
+    fn subscribe_events(
+        &self,
+        filter: EventFilter,
+    ) -> Result<BoxStream<'static, EventEnvelope>, anyhow::Error>;
+
+    /// Subscribe to the transactions executed by this gateway from now on, along with
+    /// their sequence numbers.
+    fn subscribe_transactions(
+        &self,
+    ) -> Result<BoxStream<'static, (GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error>;
+
+    /// Subscribe to the changes made to the objects owned by `address`, before or after
+    /// each transaction executed by this gateway from now on.
+    fn subscribe_object_changes(
+        &self,
+        address: SuiAddress,
+    ) -> Result<BoxStream<'static, ObjectChangeNotification>, anyhow::Error>;
 }
 
 impl<A> GatewayState<A>
 where
     A: AuthorityAPI + Send + Sync + 'static + Clone,
 {
+    /// Read the Sui system state from the authorities, and move to its committee and
+    /// reference gas price if it is at a later epoch, along with the cost schedule of the
+    /// epoch. Returns whether the gateway moved, which it only does when it follows the
+    /// epoch changes.
+    pub async fn reconfigure_from_system_state(&self) -> SuiResult<bool> {
+        let connect = match &self.connect_authority {
+            Some(connect) => connect,
+            None => return Ok(false),
+        };
+        let authorities = self.authorities();
+        let system_state = authorities.get_system_state().await?;
+        if system_state.epoch <= authorities.committee.epoch {
+            return Ok(false);
+        }
+        let new_authorities =
+            authorities.reconfigure_from_system_state(&system_state, &**connect)?;
+        // A schedule that cannot be decoded is ignored by the authorities, which keep the
+        // one of the previous epoch.
+        match new_authorities
+            .get_gas_schedule()
+            .await?
+            .cost_schedule_at(system_state.epoch)
+        {
+            Ok((_, cost_schedule)) => *self.cost_schedule.write() = Arc::new(cost_schedule),
+            Err(e) => warn!(
+                epoch = system_state.epoch,
+                "Keeping the previous cost schedule: {e}"
+            ),
+        }
+        *self.authorities.write() = Arc::new(new_authorities);
+        self.reference_gas_price.store(
+            system_state.reference_gas_price,
+            std::sync::atomic::Ordering::SeqCst,
+        );
+        info!(
+            epoch = system_state.epoch,
+            reference_gas_price = system_state.reference_gas_price,
+            "Moved to a new committee"
+        );
+        Ok(true)
+    }
+
     // TODO: This is expensive and unnecessary.
     // We should make sure that the framework package exists in the gateway store and read it.
     // Or even better, we should cache the reference in GatewayState struct.
@@ -350,6 +831,13 @@ where
     }
 
     async fn get_object(&self, object_id: &ObjectID) -> SuiResult<Object> {
+        if let Some(store) = &self.replica_store {
+            return store
+                .get_object(object_id)?
+                .ok_or(SuiError::ObjectNotFound {
+                    object_id: *object_id,
+                });
+        }
         Ok(if let Some(object) = self.store.get_object(object_id)? {
             debug!(?object_id, ?object, "Fetched object from local store");
             object
@@ -381,8 +869,9 @@ where
     }
 
     /// Make sure all objects in the input exist in the gateway store.
-    /// If any object does not exist in the store, give it a chance
-    /// to download from authorities.
+    /// If any object does not exist in the store, or is older than the version used by
+    /// the transaction (which can be built from the store of a full node), give it a
+    /// chance to download from authorities.
     async fn sync_input_objects_with_authorities(
         &self,
         transaction: &Transaction,
@@ -390,7 +879,7 @@ where
         let input_objects = transaction.data.input_objects()?;
         let mut objects = self.read_objects_from_store(&input_objects).await?;
         for (object_opt, kind) in objects.iter_mut().zip(&input_objects) {
-            if object_opt.is_none() {
+            if is_missing_or_outdated(object_opt, kind) {
                 if let ObjectRead::Exists(_, object, _) =
                     self.get_object_info(kind.object_id()).await?
                 {
@@ -413,9 +902,11 @@ where
         self.sync_input_objects_with_authorities(&transaction)
             .await?;
 
+        let cost_schedule = self.cost_schedule.read().clone();
         let (_gas_status, all_objects) = transaction_input_checker::check_transaction_input(
             &self.store,
             &transaction,
+            &cost_schedule,
             &self.metrics.shared_obj_tx,
         )
         .await?;
@@ -440,7 +931,7 @@ where
             tx_kind = transaction.data.kind_as_str()
         );
         let exec_result = self
-            .authorities
+            .authorities()
             .execute_transaction(&transaction)
             .instrument(span)
             .await;
@@ -449,6 +940,11 @@ where
         if exec_result.is_err() {
             self.metrics.total_tx_errored.inc();
             error!("{:?}", exec_result);
+            // The authorities may have moved to a new epoch, so that the transaction
+            // succeeds when it is retried with the new committee.
+            if let Err(e) = self.reconfigure_from_system_state().await {
+                warn!("Failed to follow the epoch change: {e}");
+            }
         }
         let (new_certificate, effects) = exec_result?;
 
@@ -458,6 +954,20 @@ where
             "Transaction completed successfully"
         );
 
+        self.update_executed_transaction(all_objects, &new_certificate, &effects)
+            .await?;
+
+        Ok((new_certificate, effects))
+    }
+
+    /// Update the local object states with the effects of a transaction executed by the
+    /// authorities, and notify the subscribers.
+    async fn update_executed_transaction(
+        &self,
+        all_objects: Vec<(InputObjectKind, Object)>,
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+    ) -> Result<(), anyhow::Error> {
         // Download the latest content of every mutated object from the authorities.
         let mutated_object_refs: BTreeSet<_> = effects
             .mutated_and_created()
@@ -466,20 +976,252 @@ where
         let mutated_objects = self
             .download_objects_from_authorities(mutated_object_refs)
             .await?;
+        let input_owners = all_objects
+            .iter()
+            .map(|(_, object)| (object.id(), object.owner))
+            .collect();
+        let sequence_number = self
+            .next_tx_seq_number
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         self.store.update_gateway_state(
             all_objects,
             mutated_objects,
-            new_certificate.clone(),
+            certificate.clone(),
             effects.clone().to_unsigned_effects(),
-            self.next_tx_seq_number
-                .fetch_add(1, std::sync::atomic::Ordering::SeqCst),
+            sequence_number,
         )?;
+        self.notify_subscribers(sequence_number, certificate, effects, input_owners);
 
-        Ok((new_certificate, effects))
+        Ok(())
+    }
+
+    /// Finish or release the transactions left pending in the store, e.g. by a gateway
+    /// which crashed while executing them, and whose objects are still locked to them.
+    ///
+    /// Each pending transaction is submitted again to the authorities, which return its
+    /// certificate if one was already formed, or finish certifying it. Once executed, the
+    /// local state is updated as for any other transaction. The pending transaction is
+    /// released, unlocking its objects on this gateway, only if it can never be certified:
+    /// its inputs are outdated, or its objects are locked on conflicting transactions.
+    /// After any other failure it stays pending.
+    pub async fn recover_pending_transactions(&self) -> SuiResult {
+        let pending_transactions: Vec<_> = self.store.pending_transactions().iter().collect();
+        if pending_transactions.is_empty() {
+            return Ok(());
+        }
+        info!(
+            num_pending = pending_transactions.len(),
+            "Recovering pending transactions"
+        );
+        let cost_schedule = self.cost_schedule.read().clone();
+        for (tx_digest, transaction) in pending_transactions {
+            // The inputs are still at the versions the transaction was locked on, unless
+            // the objects were since synced from the authorities.
+            let all_objects = match transaction_input_checker::check_transaction_input(
+                &self.store,
+                &transaction,
+                &cost_schedule,
+                &self.metrics.shared_obj_tx,
+            )
+            .await
+            {
+                Ok((_gas_status, all_objects)) => all_objects,
+                Err(err) if is_outdated_input_error(&err) => {
+                    warn!(?tx_digest, ?err, "Pending transaction inputs are outdated");
+                    self.release_pending_transaction(&transaction)?;
+                    continue;
+                }
+                Err(err) => {
+                    error!(
+                        ?tx_digest,
+                        ?err,
+                        "Failed to check pending transaction inputs"
+                    );
+                    continue;
+                }
+            };
+
+            // The transaction is only released once the authorities rejected it for good:
+            // after a failure to reach them, it stays pending to be recovered again.
+            let span = tracing::debug_span!("recover_transaction", ?tx_digest);
+            let authorities = self.authorities();
+            let timeout_after_quorum = authorities.config.timeout_after_quorum;
+            let certificate = match authorities
+                .process_transaction(transaction.clone(), timeout_after_quorum)
+                .instrument(span.clone())
+                .await
+            {
+                Ok(certificate) => certificate,
+                Err(err) => {
+                    match authorities
+                        .is_transaction_rejected(&transaction, timeout_after_quorum)
+                        .instrument(span)
+                        .await
+                    {
+                        Ok(true) => {
+                            warn!(?tx_digest, ?err, "Pending transaction was rejected");
+                            self.release_pending_transaction(&transaction)?;
+                        }
+                        Ok(false) => {
+                            warn!(
+                                ?tx_digest,
+                                ?err,
+                                "Pending transaction could not be certified"
+                            )
+                        }
+                        Err(lock_err) => error!(
+                            ?tx_digest,
+                            ?err,
+                            ?lock_err,
+                            "Failed to fetch the locks of a pending transaction"
+                        ),
+                    }
+                    continue;
+                }
+            };
+            self.metrics.total_tx_certificates.inc();
+            let effects = match authorities
+                .process_certificate(certificate.clone(), timeout_after_quorum)
+                .instrument(span)
+                .await
+            {
+                Ok(effects) => effects,
+                Err(err) => {
+                    warn!(
+                        ?tx_digest,
+                        ?err,
+                        "Pending transaction could not be executed"
+                    );
+                    continue;
+                }
+            };
+            // If the local state cannot be updated the transaction stays pending, to
+            // be recovered again on the next start.
+            match self
+                .update_executed_transaction(all_objects, &certificate, &effects)
+                .await
+            {
+                Ok(()) => {
+                    self.metrics.total_tx_recovered.inc();
+                    info!(?tx_digest, "Recovered pending transaction");
+                }
+                Err(err) => error!(?tx_digest, ?err, "Failed to recover pending transaction"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Release the locks held by a pending transaction on this gateway, and forget it.
+    fn release_pending_transaction(&self, transaction: &Transaction) -> SuiResult {
+        let owned_objects: Vec<_> = transaction
+            .data
+            .input_objects()?
+            .into_iter()
+            .filter_map(|kind| match kind {
+                InputObjectKind::ImmOrOwnedMoveObject(object_ref) => Some(object_ref),
+                _ => None,
+            })
+            .collect();
+        self.store
+            .release_transaction_lock(&owned_objects, transaction.digest())?;
+        self.metrics.total_tx_released.inc();
+        info!(tx_digest = ?transaction.digest(), "Released pending transaction");
+        Ok(())
+    }
+
+    /// Broadcast a transaction, executed at `sequence_number`, to the subscribers.
+    fn notify_subscribers(
+        &self,
+        sequence_number: GatewayTxSeqNumber,
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+        input_owners: BTreeMap<ObjectID, Owner>,
+    ) {
+        if self.notification_sender.receiver_count() == 0 {
+            return;
+        }
+        // Sending only fails when the last subscriber just went away.
+        let _ = self
+            .notification_sender
+            .send(Arc::new(ExecutedTransaction::new(
+                sequence_number,
+                certificate,
+                effects,
+                input_owners,
+            )));
+    }
+
+    /// Executes a transaction against the objects of the store, without committing it. Its
+    /// input objects (shared ones at their latest version), and the packages they depend
+    /// on, are first downloaded from the authorities if needed.
+    async fn simulate_transaction(
+        &self,
+        data: TransactionData,
+    ) -> SuiResult<DryRunTransactionResponse> {
+        let input_objects = data.input_objects()?;
+        let objects = self.read_objects_from_store(&input_objects).await?;
+        for (object_opt, kind) in objects.iter().zip(&input_objects) {
+            if matches!(kind, InputObjectKind::SharedMoveObject(_))
+                || is_missing_or_outdated(object_opt, kind)
+            {
+                self.download_object_from_authorities(kind.object_id())
+                    .await?;
+            }
+        }
+
+        // The packages called or published against can only be linked with all of the
+        // packages they depend on in the store.
+        let mut pending: Vec<_> = input_objects
+            .iter()
+            .filter_map(|kind| match kind {
+                InputObjectKind::MovePackage(package_id) => Some(*package_id),
+                _ => None,
+            })
+            .collect();
+        let mut visited = BTreeSet::new();
+        while let Some(package_id) = pending.pop() {
+            if !visited.insert(package_id) {
+                continue;
+            }
+            let package = match self.store.get_object(&package_id)? {
+                Some(package) => package,
+                None => self
+                    .download_object_from_authorities(package_id)
+                    .await?
+                    .into_object()?,
+            };
+            let modules = package
+                .data
+                .try_as_package()
+                .into_iter()
+                .flat_map(|package| package.serialized_module_map().values())
+                .filter_map(|bytes| CompiledModule::deserialize(bytes).ok());
+            for module in modules {
+                pending.extend(
+                    module
+                        .immediate_dependencies()
+                        .iter()
+                        .map(|module_id| ObjectID::from(*module_id.address())),
+                );
+            }
+        }
+
+        let cost_schedule = self.cost_schedule.read().clone();
+        execution_engine::dry_run_transaction(
+            &self.store,
+            data,
+            &cost_schedule,
+            &self.move_vm,
+            &self.native_functions,
+        )
+        .await
     }
 
     async fn download_object_from_authorities(&self, object_id: ObjectID) -> SuiResult<ObjectRead> {
-        let result = self.authorities.get_object_info_execute(object_id).await?;
+        let result = self
+            .authorities()
+            .get_object_info_execute(object_id)
+            .await?;
         if let ObjectRead::Exists(obj_ref, object, _) = &result {
             let local_object = self.store.get_object(&object_id)?;
             if local_object.is_none()
@@ -499,7 +1241,7 @@ where
         object_refs: BTreeSet<ObjectRef>,
     ) -> Result<HashMap<ObjectRef, Object>, SuiError> {
         let mut receiver = self
-            .authorities
+            .authorities()
             .fetch_objects_from_authorities(object_refs.clone());
 
         let mut objects = HashMap::new();
@@ -713,39 +1455,259 @@ where
         }
     }
 
+    /// Create the transfer of a batch transaction. The transferred object is added to
+    /// `used_object_ids`, so that it is not picked to pay for gas.
+    async fn create_transfer_coin_transaction_kind(
+        &self,
+        params: TransferCoinParams,
+        used_object_ids: &mut BTreeSet<ObjectID>,
+    ) -> Result<SingleTransactionKind, anyhow::Error> {
+        let object = self.get_object(&params.object_id).await?;
+        used_object_ids.insert(params.object_id);
+        Ok(SingleTransactionKind::TransferCoin(TransferCoin {
+            recipient: params.recipient,
+            object_ref: object.compute_object_reference(),
+        }))
+    }
+
+    /// Create a Move call, resolving its SuiJSON arguments against the called function.
+    /// The objects passed as arguments are added to `used_object_ids`, so that they are
+    /// not picked to pay for gas.
+    async fn create_move_call_transaction_kind(
+        &self,
+        params: MoveCallParams,
+        used_object_ids: &mut BTreeSet<ObjectID>,
+    ) -> Result<SingleTransactionKind, anyhow::Error> {
+        let MoveCallParams {
+            package_object_id,
+            module,
+            function,
+            type_arguments,
+            arguments,
+        } = params;
+        let package_obj = self.get_object(&package_object_id).await?;
+        let package_obj_ref = package_obj.compute_object_reference();
+        let json_args =
+            resolve_move_function_args(&package_obj, module.clone(), function.clone(), arguments)?;
+
+        // Fetch all the objects needed for this call
+        let mut objects = BTreeMap::new();
+        let mut args = Vec::with_capacity(json_args.len());
+
+        for json_arg in json_args {
+            args.push(match json_arg {
+                SuiJsonCallArg::Object(id) => {
+                    let obj = self.get_object(&id).await?;
+                    let arg = if obj.is_shared() {
+                        CallArg::SharedObject(id)
+                    } else {
+                        CallArg::ImmOrOwnedObject(obj.compute_object_reference())
+                    };
+                    objects.insert(id, obj);
+                    arg
+                }
+                SuiJsonCallArg::Pure(bytes) => CallArg::Pure(bytes),
+            })
+        }
+
+        // Pass in the objects for a deeper check
+        let compiled_module = package_obj
+            .data
+            .try_as_package()
+            .ok_or_else(|| anyhow!("Cannot get package from object"))?
+            .deserialize_module(&module)?;
+        resolve_and_type_check(
+            &objects,
+            &compiled_module,
+            &function,
+            &type_arguments,
+            args.clone(),
+        )?;
+
+        used_object_ids.extend(objects.into_keys());
+        Ok(SingleTransactionKind::Call(MoveCall {
+            package: package_obj_ref,
+            module,
+            function,
+            type_arguments,
+            arguments: args,
+        }))
+    }
+
+    /// Pick the gas coin of a transaction, among the coins of `address` that are not
+    /// inputs of the transaction. The smallest coin covering the budget is picked, so that
+    /// larger coins remain available for larger budgets. Without a budget, the richest coin
+    /// is picked, since the budget will be estimated later.
     async fn choose_gas_for_address(
         &self,
         address: SuiAddress,
-        budget: u64,
+        budget: Option<u64>,
         gas: Option<ObjectID>,
         used_coins: Vec<ObjectID>,
     ) -> Result<ObjectRef, anyhow::Error> {
         if let Some(id) = gas {
-            Ok(self.get_object(&id).await?.compute_object_reference())
-        } else {
-            let used_coins = used_coins.into_iter().collect::<BTreeSet<_>>();
-            for (id, balance) in self.get_owned_coins(address).await.unwrap() {
-                if balance >= budget && !used_coins.contains(&id.0) {
-                    return Ok(id);
+            fp_ensure!(
+                !used_coins.contains(&id),
+                anyhow!("Gas object {} is also an input of the transaction", id)
+            );
+            return Ok(self.get_object(&id).await?.compute_object_reference());
+        }
+
+        let coins = self.get_gas_coins(address, used_coins).await?;
+        match budget {
+            Some(budget) => {
+                let gas_cost = self.gas_cost(budget);
+                if let Some((gas, _)) = coins
+                    .iter()
+                    .filter(|(_, balance)| *balance >= gas_cost)
+                    .min_by_key(|(_, balance)| *balance)
+                {
+                    return Ok(*gas);
+                }
+                let merge_gas_cost = self.gas_cost(*gas::MIN_GAS_BUDGET);
+                if plan_gas_coin_merge(&coins, gas_cost, merge_gas_cost).is_some() {
+                    Err(SuiError::GasCoinMergeRequired {
+                        address,
+                        gas_budget: budget,
+                    }
+                    .into())
+                } else {
+                    let total_balance = total_balance(&coins);
+                    Err(SuiError::InsufficientGasCoins {
+                        address,
+                        gas_budget: budget,
+                        total_balance: u64::try_from(total_balance).unwrap_or(u64::MAX),
+                    }
+                    .into())
                 }
             }
-            return Err(anyhow!(
-                "No non-argument gas objects found with value >= budget {}",
-                budget
-            ));
+            None => coins
+                .into_iter()
+                .max_by_key(|(_, balance)| *balance)
+                .map(|(gas, _)| gas)
+                .ok_or_else(|| {
+                    SuiError::InsufficientGasCoins {
+                        address,
+                        gas_budget: *gas::MIN_GAS_BUDGET,
+                        total_balance: 0,
+                    }
+                    .into()
+                }),
         }
     }
 
+    /// The gas coins of `address`, excluding the ones in `used_coins`.
+    async fn get_gas_coins(
+        &self,
+        address: SuiAddress,
+        used_coins: Vec<ObjectID>,
+    ) -> Result<Vec<(ObjectRef, u64)>, anyhow::Error> {
+        let used_coins = used_coins.into_iter().collect::<BTreeSet<_>>();
+        Ok(self
+            .get_owned_coins(address)
+            .await?
+            .into_iter()
+            .filter(|(gas, _)| !used_coins.contains(&gas.0))
+            .collect())
+    }
+
+    /// Set the gas price of a transaction built by the gateway to the reference gas price,
+    /// and its gas budget. Without an explicit budget, the transaction is simulated with
+    /// the whole balance of its gas coin as budget, and the budget is set to the gas used
+    /// plus `gas_estimation_margin` percent.
+    async fn set_gas_budget(
+        &self,
+        mut data: TransactionData,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        data.gas_price = self.reference_gas_price();
+        if let Some(gas_budget) = gas_budget {
+            data.gas_budget = gas_budget;
+            return Ok(data);
+        }
+
+        let gas_object_id = data.gas_payment_object_ref().0;
+        let gas_object = self.get_object(&gas_object_id).await?;
+        let balance = gas::get_gas_balance(&gas_object)?;
+        fp_ensure!(
+            balance >= self.gas_cost(*gas::MIN_GAS_BUDGET),
+            SuiError::InsufficientGasForEstimatedBudget {
+                gas_object_id,
+                balance,
+                gas_budget: *gas::MIN_GAS_BUDGET,
+            }
+            .into()
+        );
+
+        data.gas_budget = (balance / data.gas_price).min(*gas::MAX_GAS_BUDGET);
+        let response = self.simulate_transaction(data.clone()).await?;
+        // Both computation and storage are charged at the gas price, so the gas used
+        // is a whole number of gas units.
+        let gas_used = response.gas_cost_summary.gas_used() / data.gas_price;
+        let gas_budget = gas_used
+            .saturating_add(gas_used.saturating_mul(self.gas_estimation_margin) / 100)
+            .max(*gas::MIN_GAS_BUDGET);
+        debug!(
+            tx_digest = ?data.digest(),
+            gas_used,
+            gas_budget,
+            "Estimated gas budget"
+        );
+
+        match response.effects.status {
+            ExecutionStatus::Success { .. } => (),
+            // The simulation ran out of the whole balance of the gas coin.
+            ExecutionStatus::Failure { error, .. }
+                if matches!(*error, SuiError::InsufficientGas { .. }) =>
+            {
+                fp_bail!(SuiError::InsufficientGasForEstimatedBudget {
+                    gas_object_id,
+                    balance,
+                    gas_budget,
+                }
+                .into())
+            }
+            ExecutionStatus::Failure { error, .. } => fp_bail!((*error).into()),
+        }
+        fp_ensure!(
+            balance >= self.gas_cost(gas_budget),
+            SuiError::InsufficientGasForEstimatedBudget {
+                gas_object_id,
+                balance,
+                gas_budget,
+            }
+            .into()
+        );
+        gas::check_gas_balance(&gas_object, gas_budget, data.gas_price)?;
+
+        data.gas_budget = gas_budget;
+        Ok(data)
+    }
+
     async fn get_owned_coins(
         &self,
         address: SuiAddress,
     ) -> Result<Vec<(ObjectRef, u64)>, anyhow::Error> {
+        self.get_owned_coins_of_type(address, &GasCoin::type_())
+            .await
+    }
+
+    /// Return the reference and balance of every coin of type `coin_type` owned by `address`.
+    async fn get_owned_coins_of_type(
+        &self,
+        address: SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<Vec<(ObjectRef, u64)>, anyhow::Error> {
+        let owned_objects = match &self.replica_store {
+            Some(store) => store.get_account_objects(address)?,
+            None => self.store.get_account_objects(address)?,
+        };
         let mut coins = Vec::new();
-        for (id, _, _) in self.store.get_account_objects(address)? {
+        for (id, _, _) in owned_objects {
             let object = self.get_object(&id).await?;
-            if matches!(object.data.type_(), Some(ty)  if *ty == GasCoin::type_()) {
-                let gas_coin = GasCoin::try_from(object.data.try_as_move().unwrap())?;
-                coins.push((object.compute_object_reference(), gas_coin.value()));
+            if matches!(object.data.type_(), Some(ty)  if ty == coin_type) {
+                let coin: Coin = bcs::from_bytes(object.data.try_as_move().unwrap().contents())?;
+                coins.push((object.compute_object_reference(), coin.value()));
             }
         }
         Ok(coins)
@@ -845,7 +1807,9 @@ where
                         if move_call.function.as_ref() == coin::COIN_SPLIT_VEC_FUNC_NAME {
                             self.metrics.num_tx_splitcoin.inc();
                             return self.create_split_coin_response(certificate, effects).await;
-                        } else if move_call.function.as_ref() == coin::COIN_JOIN_FUNC_NAME {
+                        } else if move_call.function.as_ref() == coin::COIN_JOIN_FUNC_NAME
+                            || move_call.function.as_ref() == coin::COIN_JOIN_VEC_FUNC_NAME
+                        {
                             self.metrics.num_tx_mergecoin.inc();
                             return self.create_merge_coin_response(certificate, effects).await;
                         }
@@ -862,12 +1826,21 @@ where
         ));
     }
 
+    async fn dry_run_transaction(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionResponse, anyhow::Error> {
+        let tx_digest = tx_data.digest();
+        debug!(?tx_digest, "Received dry_run_transaction request");
+        Ok(self.simulate_transaction(tx_data).await?)
+    }
+
     async fn transfer_coin(
         &self,
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
     ) -> Result<TransactionData, anyhow::Error> {
         let gas_payment = self
@@ -875,16 +1848,21 @@ where
             .await?;
         let object = self.get_object(&object_id).await?;
         let object_ref = object.compute_object_reference();
-        let data =
-            TransactionData::new_transfer(recipient, object_ref, signer, gas_payment, gas_budget);
-        Ok(data)
+        let data = TransactionData::new_transfer(
+            recipient,
+            object_ref,
+            signer,
+            gas_payment,
+            gas_budget.unwrap_or_default(),
+        );
+        self.set_gas_budget(data, gas_budget).await
     }
 
     // TODO: Get rid of the sync API.
     // https://github.com/MystenLabs/sui/issues/1045
     async fn sync_account_state(&self, account_addr: SuiAddress) -> Result<(), anyhow::Error> {
         let (active_object_certs, _deleted_refs_certs) = self
-            .authorities
+            .authorities()
             .sync_all_owned_objects(account_addr, Duration::from_secs(60))
             .await?;
 
@@ -912,62 +1890,36 @@ where
         type_arguments: Vec<TypeTag>,
         arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
-        let package_obj = self.get_object(&package_object_id).await?;
-        let package_obj_ref = package_obj.compute_object_reference();
-        let json_args =
-            resolve_move_function_args(&package_obj, module.clone(), function.clone(), arguments)?;
-
-        // Fetch all the objects needed for this call
-        let mut objects = BTreeMap::new();
-        let mut args = Vec::with_capacity(json_args.len());
-
-        for json_arg in json_args {
-            args.push(match json_arg {
-                SuiJsonCallArg::Object(id) => {
-                    let obj = self.get_object(&id).await?;
-                    let arg = if obj.is_shared() {
-                        CallArg::SharedObject(id)
-                    } else {
-                        CallArg::ImmOrOwnedObject(obj.compute_object_reference())
-                    };
-                    objects.insert(id, obj);
-                    arg
-                }
-                SuiJsonCallArg::Pure(bytes) => CallArg::Pure(bytes),
-            })
-        }
-
-        let forbidden_gas_objects = objects.keys().copied().collect();
+        let mut used_object_ids = BTreeSet::new();
+        let call = self
+            .create_move_call_transaction_kind(
+                MoveCallParams {
+                    package_object_id,
+                    module,
+                    function,
+                    type_arguments,
+                    arguments,
+                },
+                &mut used_object_ids,
+            )
+            .await?;
         let gas = self
-            .choose_gas_for_address(signer, gas_budget, gas, forbidden_gas_objects)
+            .choose_gas_for_address(
+                signer,
+                gas_budget,
+                gas,
+                used_object_ids.into_iter().collect(),
+            )
             .await?;
-
-        // Pass in the objects for a deeper check
-        let compiled_module = package_obj
-            .data
-            .try_as_package()
-            .ok_or_else(|| anyhow!("Cannot get package from object"))?
-            .deserialize_module(&module)?;
-        resolve_and_type_check(
-            &objects,
-            &compiled_module,
-            &function,
-            &type_arguments,
-            args.clone(),
-        )?;
-
-        let data = TransactionData::new_move_call(
+        let data = TransactionData::new(
+            TransactionKind::Single(call),
             signer,
-            package_obj_ref,
-            module,
-            function,
-            type_arguments,
             gas,
-            args,
-            gas_budget,
+            gas_budget.unwrap_or_default(),
         );
+        let data = self.set_gas_budget(data, gas_budget).await?;
 
         debug!(?data, "Created Move Call transaction data");
         Ok(data)
@@ -978,13 +1930,14 @@ where
         signer: SuiAddress,
         package_bytes: Vec<Vec<u8>>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
         let gas = self
             .choose_gas_for_address(signer, gas_budget, gas, vec![])
             .await?;
-        let data = TransactionData::new_module(signer, gas, package_bytes, gas_budget);
-        Ok(data)
+        let data =
+            TransactionData::new_module(signer, gas, package_bytes, gas_budget.unwrap_or_default());
+        self.set_gas_budget(data, gas_budget).await
     }
 
     async fn split_coin(
@@ -993,7 +1946,7 @@ where
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
         let gas = self
             .choose_gas_for_address(signer, gas_budget, gas, vec![coin_object_id])
@@ -1012,8 +1965,9 @@ where
                 CallArg::ImmOrOwnedObject(coin_object_ref),
                 CallArg::Pure(bcs::to_bytes(&split_amounts)?),
             ],
-            gas_budget,
+            gas_budget.unwrap_or_default(),
         );
+        let data = self.set_gas_budget(data, gas_budget).await?;
         debug!(?data, "Created Split Coin transaction data");
         Ok(data)
     }
@@ -1022,36 +1976,178 @@ where
         &self,
         signer: SuiAddress,
         primary_coin: ObjectID,
-        coin_to_merge: ObjectID,
+        coins_to_merge: Vec<ObjectID>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     ) -> Result<TransactionData, anyhow::Error> {
+        fp_ensure!(
+            !coins_to_merge.is_empty(),
+            anyhow!(
+                "At least one coin to merge into {} must be given",
+                primary_coin
+            )
+        );
+        let mut used_coins = coins_to_merge.clone();
+        used_coins.push(primary_coin);
         let gas = self
-            .choose_gas_for_address(signer, gas_budget, gas, vec![coin_to_merge, primary_coin])
+            .choose_gas_for_address(signer, gas_budget, gas, used_coins)
             .await?;
         let primary_coin = self.get_object(&primary_coin).await?;
         let primary_coin_ref = primary_coin.compute_object_reference();
-        let coin_to_merge = self.get_object(&coin_to_merge).await?;
-        let coin_to_merge_ref = coin_to_merge.compute_object_reference();
+        let mut coins_to_merge_refs = Vec::with_capacity(coins_to_merge.len());
+        for coin_to_merge in coins_to_merge {
+            let coin_to_merge = self.get_object(&coin_to_merge).await?;
+            coins_to_merge_refs.push(coin_to_merge.compute_object_reference());
+        }
 
-        let coin_type = coin_to_merge.get_move_template_type()?;
+        let coin_type = primary_coin.get_move_template_type()?;
+        // A single coin is joined directly, several are joined as a vector of coins.
+        let (function, coins_to_merge_arg) = if coins_to_merge_refs.len() == 1 {
+            (
+                coin::COIN_JOIN_FUNC_NAME,
+                CallArg::ImmOrOwnedObject(coins_to_merge_refs[0]),
+            )
+        } else {
+            (
+                coin::COIN_JOIN_VEC_FUNC_NAME,
+                CallArg::ObjVec(coins_to_merge_refs),
+            )
+        };
         let data = TransactionData::new_move_call(
             signer,
             self.get_framework_object_ref().await?,
             coin::COIN_MODULE_NAME.to_owned(),
-            coin::COIN_JOIN_FUNC_NAME.to_owned(),
+            function.to_owned(),
             vec![coin_type],
             gas,
             vec![
                 CallArg::ImmOrOwnedObject(primary_coin_ref),
-                CallArg::ImmOrOwnedObject(coin_to_merge_ref),
+                coins_to_merge_arg,
             ],
-            gas_budget,
+            gas_budget.unwrap_or_default(),
         );
+        let data = self.set_gas_budget(data, gas_budget).await?;
         debug!(?data, "Created Merge Coin transaction data");
         Ok(data)
     }
 
+    async fn merge_all_coins(
+        &self,
+        signer: SuiAddress,
+        coin_type: TypeTag,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        let coin_struct = match coin_type {
+            TypeTag::Struct(type_param) => Coin::type_(type_param),
+            _ => fp_bail!(anyhow!(
+                "Coin type must be a struct type, found {}",
+                coin_type
+            )),
+        };
+        let mut coins = self.get_owned_coins_of_type(signer, &coin_struct).await?;
+        // SUI coins also pay for gas, so the gas payment is kept out of the merge.
+        let gas = if coin_struct == GasCoin::type_() {
+            let (gas, _, _) = self
+                .choose_gas_for_address(signer, gas_budget, gas, vec![])
+                .await?;
+            coins.retain(|((id, _, _), _)| *id != gas);
+            Some(gas)
+        } else {
+            gas
+        };
+        fp_ensure!(
+            coins.len() >= 2,
+            anyhow!(
+                "Address {} needs at least two coins of type {} to merge, found {}",
+                signer,
+                coin_struct,
+                coins.len()
+            )
+        );
+
+        coins.sort_by_key(|(_, balance)| std::cmp::Reverse(*balance));
+        let primary_coin = coins[0].0 .0;
+        let coins_to_merge = coins[1..].iter().map(|((id, _, _), _)| *id).collect();
+        self.merge_coins(signer, primary_coin, coins_to_merge, gas, gas_budget)
+            .await
+    }
+
+    async fn batch_transaction(
+        &self,
+        signer: SuiAddress,
+        single_transaction_params: Vec<SingleTransactionParams>,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> Result<TransactionData, anyhow::Error> {
+        fp_ensure!(
+            !single_transaction_params.is_empty(),
+            SuiError::InvalidBatchTransaction {
+                error: "A batch transaction must contain at least one transaction".to_owned(),
+            }
+            .into()
+        );
+        let mut used_object_ids = BTreeSet::new();
+        let mut kinds = Vec::with_capacity(single_transaction_params.len());
+        for params in single_transaction_params {
+            kinds.push(match params {
+                SingleTransactionParams::TransferCoin(params) => {
+                    self.create_transfer_coin_transaction_kind(params, &mut used_object_ids)
+                        .await?
+                }
+                SingleTransactionParams::MoveCall(params) => {
+                    self.create_move_call_transaction_kind(params, &mut used_object_ids)
+                        .await?
+                }
+            });
+        }
+        let gas = self
+            .choose_gas_for_address(
+                signer,
+                gas_budget,
+                gas,
+                used_object_ids.into_iter().collect(),
+            )
+            .await?;
+        let data = TransactionData::new(
+            TransactionKind::Batch(kinds),
+            signer,
+            gas,
+            gas_budget.unwrap_or_default(),
+        );
+        let data = self.set_gas_budget(data, gas_budget).await?;
+
+        debug!(?data, "Created Batch transaction data");
+        Ok(data)
+    }
+
+    async fn merge_gas_coins(
+        &self,
+        signer: SuiAddress,
+        gas_budget: u64,
+        excluded_coins: Vec<ObjectID>,
+    ) -> Result<Option<TransactionData>, anyhow::Error> {
+        let coins = self.get_gas_coins(signer, excluded_coins).await?;
+        let gas_cost = self.gas_cost(gas_budget);
+        if coins.iter().any(|(_, balance)| *balance >= gas_cost) {
+            return Ok(None);
+        }
+        let merge_gas_cost = self.gas_cost(*gas::MIN_GAS_BUDGET);
+        let (primary_coin, coins_to_merge, gas) =
+            plan_gas_coin_merge(&coins, gas_cost, merge_gas_cost).ok_or_else(|| {
+                SuiError::InsufficientGasCoins {
+                    address: signer,
+                    gas_budget,
+                    total_balance: u64::try_from(total_balance(&coins)).unwrap_or(u64::MAX),
+                }
+            })?;
+        // The budget of the merge is estimated, and checked against the balance of `gas`.
+        let data = self
+            .merge_coins(signer, primary_coin, coins_to_merge, Some(gas), None)
+            .await?;
+        Ok(Some(data))
+    }
+
     async fn get_object_info(&self, object_id: ObjectID) -> Result<ObjectRead, anyhow::Error> {
         let result = self.download_object_from_authorities(object_id).await?;
         Ok(result)
@@ -1064,6 +2160,36 @@ where
         Ok(self.store.get_account_objects(account_addr)?)
     }
 
+    async fn get_owned_objects_page(
+        &self,
+        account_addr: SuiAddress,
+        object_type: Option<StructTag>,
+        cursor: Option<ObjectID>,
+        limit: u64,
+        show_details: bool,
+    ) -> Result<OwnedObjectPage, anyhow::Error> {
+        store_reads::get_owned_objects_page(
+            &self.store,
+            account_addr,
+            object_type,
+            cursor,
+            limit,
+            show_details,
+        )
+    }
+
+    async fn get_balance(
+        &self,
+        address: SuiAddress,
+        coin_type: TypeTag,
+    ) -> Result<Balance, anyhow::Error> {
+        store_reads::get_balance(&self.store, address, coin_type)
+    }
+
+    async fn get_all_balances(&self, address: SuiAddress) -> Result<Vec<Balance>, anyhow::Error> {
+        store_reads::get_all_balances(&self.store, address)
+    }
+
     fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error> {
         Ok(self.store.next_sequence_number()?)
     }
@@ -1073,62 +2199,83 @@ where
         start: GatewayTxSeqNumber,
         end: GatewayTxSeqNumber,
     ) -> Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error> {
-        fp_ensure!(
-            start <= end,
-            SuiError::GatewayInvalidTxRangeQuery {
-                error: format!(
-                    "start must not exceed end, (start={}, end={}) given",
-                    start, end
-                ),
-            }
-            .into()
-        );
-        fp_ensure!(
-            end - start <= MAX_TX_RANGE_SIZE,
-            SuiError::GatewayInvalidTxRangeQuery {
-                error: format!(
-                    "Number of transactions queried must not exceed {}, {} queried",
-                    MAX_TX_RANGE_SIZE,
-                    end - start
-                ),
-            }
-            .into()
-        );
-        let res = self.store.transactions_in_seq_range(start, end)?;
-        debug!(?start, ?end, ?res, "Fetched transactions");
-        Ok(res)
+        store_reads::get_transactions_in_range(&self.store, start, end)
     }
 
     fn get_recent_transactions(
         &self,
         count: u64,
     ) -> Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error> {
-        fp_ensure!(
-            count <= MAX_TX_RANGE_SIZE,
-            SuiError::GatewayInvalidTxRangeQuery {
-                error: format!(
-                    "Number of transactions queried must not exceed {}, {} queried",
-                    MAX_TX_RANGE_SIZE, count
-                ),
-            }
-            .into()
-        );
-        let end = self.get_total_transaction_number()?;
-        let start = if end >= count { end - count } else { 0 };
-        self.get_transactions_in_range(start, end)
+        store_reads::get_recent_transactions(&self.store, count)
     }
 
     async fn get_transaction(
         &self,
         digest: TransactionDigest,
     ) -> Result<TransactionEffectsResponse, anyhow::Error> {
-        let opt = self.store.get_certified_transaction(&digest)?;
-        match opt {
-            Some(certificate) => Ok(TransactionEffectsResponse {
-                certificate,
-                effects: self.store.get_effects(&digest)?,
-            }),
-            None => Err(anyhow!(SuiError::TransactionNotFound { digest })),
-        }
+        store_reads::get_transaction(&self.store, digest)
+    }
+
+    async fn get_transactions_by_address(
+        &self,
+        address: SuiAddress,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> Result<TransactionPage, anyhow::Error> {
+        store_reads::get_transactions_by_address(&self.store, address, cursor, limit)
+    }
+
+    async fn get_transactions_by_object(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: u64,
+    ) -> Result<TransactionPage, anyhow::Error> {
+        store_reads::get_transactions_by_object(&self.store, object_id, cursor, limit)
+    }
+
+    async fn get_events(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+        limit: u64,
+    ) -> Result<EventPage, anyhow::Error> {
+        store_reads::get_events(&self.store, filter, cursor, limit)
+    }
+
+    fn subscribe_events(
+        &self,
+        filter: EventFilter,
+    ) -> Result<BoxStream<'static, EventEnvelope>, anyhow::Error> {
+        Ok(notification_stream(
+            self.notification_sender.subscribe(),
+            move |transaction| {
+                transaction
+                    .events
+                    .iter()
+                    .filter(|event| filter.matches(event, &transaction.object_ids))
+                    .cloned()
+                    .collect()
+            },
+        ))
+    }
+
+    fn subscribe_transactions(
+        &self,
+    ) -> Result<BoxStream<'static, (GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error> {
+        Ok(notification_stream(
+            self.notification_sender.subscribe(),
+            |transaction| vec![(transaction.sequence_number, transaction.digest)],
+        ))
+    }
+
+    fn subscribe_object_changes(
+        &self,
+        address: SuiAddress,
+    ) -> Result<BoxStream<'static, ObjectChangeNotification>, anyhow::Error> {
+        Ok(notification_stream(
+            self.notification_sender.subscribe(),
+            move |transaction| transaction.object_changes(address),
+        ))
     }
 }
@@ -0,0 +1,53 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::object::Object;
+
+use crate::sui_json::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
+
+/// A single transaction of a batch transaction.
+#[derive(Debug, Clone)]
+pub enum SingleTransactionParams {
+    TransferCoin(TransferCoinParams),
+    MoveCall(MoveCallParams),
+}
+
+/// Transfer the object `object_id` to `recipient`.
+#[derive(Debug, Clone)]
+pub struct TransferCoinParams {
+    pub recipient: SuiAddress,
+    pub object_id: ObjectID,
+}
+
+/// Call a Move function, with arguments in SuiJSON, as in `GatewayAPI::move_call`.
+#[derive(Debug, Clone)]
+pub struct MoveCallParams {
+    pub package_object_id: ObjectID,
+    pub module: Identifier,
+    pub function: Identifier,
+    pub type_arguments: Vec<TypeTag>,
+    pub arguments: Vec<SuiJsonValue>,
+}
+
+impl MoveCallParams {
+    /// The arguments which are objects in the signature of the called function, from
+    /// `package`, the object of `package_object_id`.
+    pub fn object_ids(&self, package: &Object) -> Result<Vec<ObjectID>, anyhow::Error> {
+        let args = resolve_move_function_args(
+            package,
+            self.module.clone(),
+            self.function.clone(),
+            self.arguments.clone(),
+        )?;
+        Ok(args
+            .into_iter()
+            .filter_map(|arg| match arg {
+                SuiJsonCallArg::Object(id) => Some(id),
+                SuiJsonCallArg::Pure(_) => None,
+            })
+            .collect())
+    }
+}
@@ -8,13 +8,18 @@ use std::fmt::{Display, Formatter};
 use serde::ser::Error;
 use serde::Serialize;
 
+use move_core_types::language_storage::{StructTag, TypeTag};
 use schemars::JsonSchema;
 use serde::Deserialize;
-use sui_types::base_types::{ObjectRef, SuiAddress};
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest};
 use sui_types::error::SuiError;
+use sui_types::event::{EventEnvelope, EventID};
 use sui_types::gas_coin::GasCoin;
+use sui_types::json_schema;
 use sui_types::messages::{CertifiedTransaction, TransactionEffects};
-use sui_types::object::Object;
+use sui_types::object::{Object, Owner};
+
+use crate::gateway_state::GatewayTxSeqNumber;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct TransactionEffectsResponse {
@@ -22,6 +27,82 @@ pub struct TransactionEffectsResponse {
     pub effects: TransactionEffects,
 }
 
+/// A page of events, in execution order.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct EventPage {
+    pub data: Vec<EventEnvelope>,
+    /// The ID of the first event of the next page, if there are more events
+    pub next_cursor: Option<EventID>,
+}
+
+/// A page of transaction digests, in execution order.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct TransactionPage {
+    pub data: Vec<(GatewayTxSeqNumber, TransactionDigest)>,
+    /// The sequence number of the first transaction of the next page, if there are more transactions
+    pub next_cursor: Option<GatewayTxSeqNumber>,
+}
+
+/// A page of the objects owned by an address, in object ID order.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct OwnedObjectPage {
+    pub data: Vec<OwnedObjectInfo>,
+    /// The ID of the first object of the next page, if there are more objects
+    pub next_cursor: Option<ObjectID>,
+}
+
+/// An object owned by an address, along with its type and owner if they were requested.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct OwnedObjectInfo {
+    pub object_ref: ObjectRef,
+    /// The Move type of the object, which packages do not have
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<json_schema::StructTag>")]
+    pub object_type: Option<StructTag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Owner>,
+}
+
+/// The balance of an address in the coins of one type.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct Balance {
+    /// The type parameter of the coins, e.g. `0x2::SUI::SUI`
+    #[schemars(with = "json_schema::TypeTag")]
+    pub coin_type: TypeTag,
+    /// The number of coins of this type owned by the address
+    pub coin_object_count: u64,
+    /// The sum of the values of these coins
+    pub total_balance: u128,
+}
+
+/// A change made by a transaction to an object owned by an address, before or after it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct ObjectChangeNotification {
+    pub tx_seq_number: GatewayTxSeqNumber,
+    pub tx_digest: TransactionDigest,
+    /// The reference of the object after the transaction
+    pub object_ref: ObjectRef,
+    pub kind: ObjectChangeKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+pub enum ObjectChangeKind {
+    /// The object was created, owned by the address
+    Created,
+    /// The object was unwrapped, owned by the address
+    Unwrapped,
+    /// The object was mutated, and is still owned by the address
+    Mutated,
+    /// The object was transferred to the address
+    Received,
+    /// The object is no longer owned by the address: it was transferred, shared or frozen
+    Sent,
+    /// The object, owned by the address, was deleted
+    Deleted,
+    /// The object, owned by the address, was wrapped into another object
+    Wrapped,
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub enum TransactionResponse {
     EffectResponse(TransactionEffectsResponse),
@@ -0,0 +1,284 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reads served from a local store. The gateway and the full node keep the same indexes
+//! in their stores, so they answer these queries the same way.
+
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use move_bytecode_utils::module_cache::ModuleCache;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use sui_types::{
+    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    coin::{self, Coin},
+    error::{SuiError, SuiResult},
+    event::{EventFilter, EventID},
+    fp_bail, fp_ensure,
+    object::{ObjectFormatOptions, ObjectRead},
+    SUI_FRAMEWORK_ADDRESS,
+};
+
+use crate::authority::SuiDataStore;
+
+use super::gateway_responses::*;
+use super::{GatewayTxSeqNumber, MAX_EVENT_PAGE_SIZE, MAX_OBJECT_PAGE_SIZE, MAX_TX_RANGE_SIZE};
+
+/// Returns the latest version of an object in the store, with its layout.
+pub fn get_object_read<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    object_id: ObjectID,
+) -> SuiResult<ObjectRead>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    match store.get_object(&object_id)? {
+        Some(object) => {
+            let layout =
+                object.get_layout(ObjectFormatOptions::default(), &ModuleCache::new(store))?;
+            Ok(ObjectRead::Exists(
+                object.compute_object_reference(),
+                object,
+                layout,
+            ))
+        }
+        None => match store.get_latest_parent_entry(object_id)? {
+            Some((object_ref, _)) => Ok(ObjectRead::Deleted(object_ref)),
+            None => Ok(ObjectRead::NotExists(object_id)),
+        },
+    }
+}
+
+pub fn get_owned_objects_page<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    account_addr: SuiAddress,
+    object_type: Option<StructTag>,
+    cursor: Option<ObjectID>,
+    limit: u64,
+    show_details: bool,
+) -> Result<OwnedObjectPage, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    fp_ensure!(
+        limit <= MAX_OBJECT_PAGE_SIZE,
+        anyhow!(
+            "Number of objects queried must not exceed {}, {} queried",
+            MAX_OBJECT_PAGE_SIZE,
+            limit
+        )
+    );
+    let (objects, next_cursor) = store.get_account_objects_page(
+        account_addr,
+        object_type.as_ref(),
+        cursor,
+        limit as usize,
+    )?;
+    let data = objects
+        .into_iter()
+        .map(|(object_ref, object)| OwnedObjectInfo {
+            object_ref,
+            object_type: show_details.then(|| object.type_().cloned()).flatten(),
+            owner: show_details.then(|| object.owner),
+        })
+        .collect();
+    Ok(OwnedObjectPage { data, next_cursor })
+}
+
+pub fn get_balance<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    address: SuiAddress,
+    coin_type: TypeTag,
+) -> Result<Balance, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let coin_struct = match &coin_type {
+        TypeTag::Struct(type_param) => Coin::type_(type_param.clone()),
+        _ => fp_bail!(anyhow!(
+            "Coin type must be a struct type, found {}",
+            coin_type
+        )),
+    };
+    let balance = get_balances(store, address, &coin_struct)?.remove(&coin_type);
+    Ok(balance.unwrap_or(Balance {
+        coin_type,
+        coin_object_count: 0,
+        total_balance: 0,
+    }))
+}
+
+pub fn get_all_balances<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    address: SuiAddress,
+) -> Result<Vec<Balance>, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let coin_struct = StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: coin::COIN_MODULE_NAME.to_owned(),
+        name: coin::COIN_STRUCT_NAME.to_owned(),
+        type_params: vec![],
+    };
+    Ok(get_balances(store, address, &coin_struct)?
+        .into_values()
+        .collect())
+}
+
+/// Returns the balances of `address` in the coins matching `coin_struct`, by coin type.
+/// A `Coin` struct without type parameters matches the coins of every type.
+fn get_balances<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    address: SuiAddress,
+    coin_struct: &StructTag,
+) -> Result<BTreeMap<TypeTag, Balance>, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let (coins, _) =
+        store.get_account_objects_page(address, Some(coin_struct), None, usize::MAX)?;
+    let mut balances = BTreeMap::new();
+    for (_, object) in coins {
+        let (type_, move_object) = match (object.type_(), object.data.try_as_move()) {
+            (Some(type_), Some(move_object)) => (type_, move_object),
+            _ => continue,
+        };
+        let coin_type = match type_.type_params.first() {
+            Some(coin_type) => coin_type.clone(),
+            None => continue,
+        };
+        let coin: Coin = bcs::from_bytes(move_object.contents())?;
+        let balance = balances
+            .entry(coin_type.clone())
+            .or_insert_with(|| Balance {
+                coin_type,
+                coin_object_count: 0,
+                total_balance: 0,
+            });
+        balance.coin_object_count += 1;
+        balance.total_balance += coin.value() as u128;
+    }
+    Ok(balances)
+}
+
+pub fn get_transactions_in_range<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    start: GatewayTxSeqNumber,
+    end: GatewayTxSeqNumber,
+) -> Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    fp_ensure!(
+        start <= end,
+        SuiError::GatewayInvalidTxRangeQuery {
+            error: format!(
+                "start must not exceed end, (start={}, end={}) given",
+                start, end
+            ),
+        }
+        .into()
+    );
+    check_tx_page_size(end - start)?;
+    let res = store.transactions_in_seq_range(start, end)?;
+    debug!(?start, ?end, ?res, "Fetched transactions");
+    Ok(res)
+}
+
+pub fn get_recent_transactions<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    count: u64,
+) -> Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    check_tx_page_size(count)?;
+    let end = store.next_sequence_number()?;
+    let start = if end >= count { end - count } else { 0 };
+    get_transactions_in_range(store, start, end)
+}
+
+pub fn get_transaction<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    digest: TransactionDigest,
+) -> Result<TransactionEffectsResponse, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let opt = store.get_certified_transaction(&digest)?;
+    match opt {
+        Some(certificate) => Ok(TransactionEffectsResponse {
+            certificate,
+            effects: store.get_effects(&digest)?,
+        }),
+        None => Err(anyhow!(SuiError::TransactionNotFound { digest })),
+    }
+}
+
+pub fn get_transactions_by_address<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    address: SuiAddress,
+    cursor: Option<GatewayTxSeqNumber>,
+    limit: u64,
+) -> Result<TransactionPage, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    check_tx_page_size(limit)?;
+    let (data, next_cursor) = store.get_transactions_by_address(address, cursor, limit as usize)?;
+    Ok(TransactionPage { data, next_cursor })
+}
+
+pub fn get_transactions_by_object<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    object_id: ObjectID,
+    cursor: Option<GatewayTxSeqNumber>,
+    limit: u64,
+) -> Result<TransactionPage, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    check_tx_page_size(limit)?;
+    let (data, next_cursor) =
+        store.get_transactions_by_object(object_id, cursor, limit as usize)?;
+    Ok(TransactionPage { data, next_cursor })
+}
+
+pub fn get_events<const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    filter: EventFilter,
+    cursor: Option<EventID>,
+    limit: u64,
+) -> Result<EventPage, anyhow::Error>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    fp_ensure!(
+        limit <= MAX_EVENT_PAGE_SIZE,
+        anyhow!(
+            "Number of events queried must not exceed {}, {} queried",
+            MAX_EVENT_PAGE_SIZE,
+            limit
+        )
+    );
+    let (data, next_cursor) = store.get_events(&filter, cursor, limit as usize)?;
+    Ok(EventPage { data, next_cursor })
+}
+
+fn check_tx_page_size(count: u64) -> Result<(), anyhow::Error> {
+    fp_ensure!(
+        count <= MAX_TX_RANGE_SIZE,
+        SuiError::GatewayInvalidTxRangeQuery {
+            error: format!(
+                "Number of transactions queried must not exceed {}, {} queried",
+                MAX_TX_RANGE_SIZE, count
+            ),
+        }
+        .into()
+    );
+    Ok(())
+}
@@ -8,8 +8,11 @@ pub mod authority_batch;
 pub mod authority_client;
 pub mod authority_server;
 pub mod consensus_adapter;
+pub mod epoch_change;
 pub mod execution_engine;
+pub mod full_node;
 pub mod gateway_state;
+pub mod replica;
 pub mod safe_client;
 pub mod sui_json;
 pub mod transaction_input_checker;
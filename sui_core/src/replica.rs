@@ -0,0 +1,456 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    authority::{
+        store_package_and_init_modules_for_genesis, AuthorityTemporaryStore, ReplicaStore,
+    },
+    authority_client::AuthorityAPI,
+    execution_engine,
+    gateway_state::{ExecutedTransaction, NOTIFICATION_CHANNEL_CAPACITY},
+    safe_client::SafeClient,
+    transaction_input_checker,
+};
+use futures::{future::join_all, StreamExt};
+use itertools::Itertools;
+use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use prometheus_exporter::prometheus::{register_int_counter, IntCounter};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use sui_adapter::adapter;
+use sui_config::genesis::Genesis;
+use sui_types::{
+    base_types::{AuthorityName, ObjectID, TransactionDigest},
+    batch::{TxSequenceNumber, UpdateItem},
+    committee::{Committee, EpochId},
+    error::{SuiError, SuiResult},
+    fp_ensure,
+    gas::SuiCostSchedule,
+    messages::{
+        BatchInfoRequest, BatchInfoResponseItem, CertifiedTransaction, DryRunTransactionResponse,
+        InputObjectKind, SignedTransactionEffects, TransactionData, TransactionEffects,
+        TransactionInfoRequest,
+    },
+    sui_system_state::{SuiSystemState, SUI_SYSTEM_STATE_OBJECT_ID},
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
+};
+use tokio::sync::{broadcast, Mutex};
+use tracing::{debug, error, info};
+
+#[cfg(test)]
+#[path = "unit_tests/replica_tests.rs"]
+mod replica_tests;
+
+/*
+
+A replica is a node that follows the batch streams of one or more authorities, without
+taking part in the protocol. For each transaction sequenced by an authority it downloads
+the certificate and the signed effects, re-executes the certificate against its own store,
+and checks the resulting effects are the ones the authority signed. The store of a replica
+keeps all object versions, so it can serve historical reads.
+
+Each authority sequences transactions in an order compatible with their dependencies, so
+following any single authority is enough to replicate the state. Following several allows
+to make progress when some of them are slow or unavailable; a transaction sequenced by more
+than one of them is executed once. The replica records how far it followed each authority,
+and resumes from there when it restarts.
+
+Once it executed the transaction changing the epoch, the replica moves to the committee
+and the cost schedule of the next epoch. Like the authorities, it then only accepts the
+certificates of the new epoch.
+
+*/
+
+const REQUEST_FOLLOW_NUM_DIGESTS: u64 = 100_000;
+const FOLLOWER_RETRY_DELAY_SECS: u64 = 5;
+
+pub struct ReplicaMetrics {
+    shared_obj_tx: IntCounter,
+    replicated_transactions: IntCounter,
+}
+
+impl ReplicaMetrics {
+    pub fn new() -> ReplicaMetrics {
+        Self {
+            shared_obj_tx: register_int_counter!(
+                "replica_num_shared_obj_tx",
+                "Number of replicated transactions involving shared objects"
+            )
+            .unwrap(),
+            replicated_transactions: register_int_counter!(
+                "replica_total_transactions",
+                "Total number of certificates re-executed by the replica"
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl Default for ReplicaMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// One static metrics instance, shared by all replicas of the process.
+static METRICS: Lazy<ReplicaMetrics> = Lazy::new(ReplicaMetrics::new);
+
+pub struct ReplicaNode<A> {
+    store: Arc<ReplicaStore>,
+    /// The committee of the current epoch, swapped when the epoch changes.
+    committee: RwLock<Arc<Committee>>,
+    /// The authorities the replica follows, checking the responses against the committee
+    /// of the current epoch.
+    clients: RwLock<BTreeMap<AuthorityName, SafeClient<A>>>,
+    move_vm: Arc<MoveVM>,
+    native_functions: NativeFunctionTable,
+    /// The cost schedules of the epochs the replica went through, recorded in the store.
+    /// Certificates are metered with the schedule of the epoch they were certified in.
+    cost_schedules: RwLock<BTreeMap<EpochId, Arc<SuiCostSchedule>>>,
+    /// Serializes the execution of certificates downloaded from different authorities.
+    execution_lock: Mutex<()>,
+    /// Broadcasts the transactions replicated by this replica to subscribers.
+    notification_sender: broadcast::Sender<Arc<ExecutedTransaction>>,
+    metrics: &'static ReplicaMetrics,
+}
+
+impl<A> ReplicaNode<A>
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    /// Creates a replica following the given authorities. An empty store is first
+    /// initialized with the genesis state.
+    pub async fn new(
+        committee: Committee,
+        store: Arc<ReplicaStore>,
+        authority_clients: BTreeMap<AuthorityName, A>,
+        genesis: &Genesis,
+    ) -> SuiResult<Self> {
+        let native_functions =
+            sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+
+        if store.database_is_empty()? {
+            let mut genesis_ctx = genesis.genesis_ctx().to_owned();
+            for genesis_modules in genesis.modules() {
+                store_package_and_init_modules_for_genesis(
+                    &store,
+                    &native_functions,
+                    &mut genesis_ctx,
+                    genesis_modules.to_owned(),
+                )
+                .await?;
+            }
+            store.bulk_object_insert(&genesis.objects().iter().collect::<Vec<_>>())?;
+        }
+
+        let clients = authority_clients
+            .into_iter()
+            .map(|(name, client)| (name, SafeClient::new(client, committee.clone(), name)))
+            .collect();
+        let mut cost_schedules: BTreeMap<_, _> = store
+            .cost_schedules()
+            .into_iter()
+            .map(|(epoch, (_, cost_schedule))| (epoch, Arc::new(cost_schedule)))
+            .collect();
+        let (_, cost_schedule) = store.epoch_cost_schedule(committee.epoch)?;
+        cost_schedules.insert(committee.epoch, Arc::new(cost_schedule));
+
+        let replica = Self {
+            store,
+            committee: RwLock::new(Arc::new(committee)),
+            clients: RwLock::new(clients),
+            move_vm: Arc::new(
+                adapter::new_move_vm(native_functions.clone())
+                    .expect("We defined natives to not fail here"),
+            ),
+            native_functions,
+            cost_schedules: RwLock::new(cost_schedules),
+            execution_lock: Mutex::new(()),
+            notification_sender: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+            metrics: &METRICS,
+        };
+        // Resume at the epoch of the stored system state, if it moved past the committee
+        // the replica was started with.
+        if replica
+            .store
+            .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)?
+            .is_some()
+        {
+            replica.reconfigure_from_system_state(&SUI_SYSTEM_STATE_OBJECT_ID)?;
+        }
+        Ok(replica)
+    }
+
+    pub fn store(&self) -> Arc<ReplicaStore> {
+        self.store.clone()
+    }
+
+    /// The cost schedule in effect at `epoch`, which must be one the replica went through.
+    fn cost_schedule(&self, epoch: EpochId) -> SuiResult<Arc<SuiCostSchedule>> {
+        self.cost_schedules
+            .read()
+            .get(&epoch)
+            .cloned()
+            .ok_or_else(|| SuiError::InvalidCostSchedule {
+                error: format!("No cost schedule is recorded for epoch {epoch}"),
+            })
+    }
+
+    /// The committee of the current epoch.
+    pub fn committee(&self) -> Arc<Committee> {
+        self.committee.read().clone()
+    }
+
+    /// Subscribe to the transactions replicated from now on.
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<Arc<ExecutedTransaction>> {
+        self.notification_sender.subscribe()
+    }
+
+    /// Executes a transaction against the replicated state, without committing it. The
+    /// state lags behind the authorities by the transactions not yet replicated.
+    pub async fn dry_run_transaction(
+        &self,
+        transaction_data: TransactionData,
+    ) -> SuiResult<DryRunTransactionResponse> {
+        let cost_schedule = self.cost_schedule(self.committee().epoch)?;
+        execution_engine::dry_run_transaction(
+            &self.store,
+            transaction_data,
+            &cost_schedule,
+            &self.move_vm,
+            &self.native_functions,
+        )
+        .await
+    }
+
+    fn client(&self, name: &AuthorityName) -> SuiResult<SafeClient<A>> {
+        self.clients
+            .read()
+            .get(name)
+            .cloned()
+            .ok_or(SuiError::UnknownSigner)
+    }
+
+    /// Read the Sui system state, and move to the committee derived from its active
+    /// validators and to the cost schedule of its epoch. Nothing changes if the system state
+    /// is still at the current epoch. The replica keeps following the same authorities,
+    /// and does not connect to the ones joining the committee.
+    fn reconfigure_from_system_state(&self, system_state_id: &ObjectID) -> SuiResult {
+        let object = self
+            .store
+            .get_object(system_state_id)?
+            .ok_or(SuiError::ObjectNotFound {
+                object_id: *system_state_id,
+            })?;
+        let system_state = SuiSystemState::from_object(&object)?;
+        if system_state.epoch <= self.committee().epoch {
+            return Ok(());
+        }
+        let committee = system_state.get_committee()?;
+        let (_, cost_schedule) = self.store.epoch_cost_schedule(committee.epoch)?;
+        self.cost_schedules
+            .write()
+            .insert(committee.epoch, Arc::new(cost_schedule));
+        for client in self.clients.write().values_mut() {
+            *client = client.clone().with_committee(committee.clone());
+        }
+        info!(epoch = committee.epoch, "Replica moved to a new committee");
+        *self.committee.write() = Arc::new(committee);
+        Ok(())
+    }
+
+    /// Once the epoch change is executed, the system state holds the next committee.
+    fn reconfigure_after_epoch_change(&self, certificate: &CertifiedTransaction) -> SuiResult {
+        if certificate.data.is_change_epoch() {
+            if let Some(system_state_id) = certificate.shared_input_objects().next() {
+                self.reconfigure_from_system_state(system_state_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Follows all the authorities the replica knows about. A follower that fails is
+    /// restarted after a delay; this only returns if there are no authorities to follow.
+    pub async fn run(self: Arc<Self>) {
+        let names: Vec<_> = self.clients.read().keys().copied().collect();
+        info!(num_authorities = names.len(), "Starting the replica node");
+        join_all(names.into_iter().map(|name| {
+            let replica = self.clone();
+            async move {
+                loop {
+                    if let Err(err) = replica.follow_authority(name).await {
+                        error!("Replica follower of {:?} returned error: {}", name, err);
+                    }
+                    tokio::time::sleep(Duration::from_secs(FOLLOWER_RETRY_DELAY_SECS)).await;
+                }
+            }
+        }))
+        .await;
+    }
+
+    /// Follows the batch stream of one authority from the sequence number the replica
+    /// stopped at, and replicates every transaction it sequences. Only returns on error.
+    pub async fn follow_authority(&self, name: AuthorityName) -> SuiResult {
+        let client = self.client(&name)?;
+
+        // Transactions after the last batch are sent again when the stream is
+        // re-requested, but they are only executed once.
+        let mut next_sequence_number: TxSequenceNumber =
+            self.store.follower_next_sequence_number(&name)?;
+        loop {
+            let request = BatchInfoRequest {
+                start: Some(next_sequence_number),
+                length: REQUEST_FOLLOW_NUM_DIGESTS,
+            };
+            let mut stream = Box::pin(client.handle_batch_stream(request).await?);
+            while let Some(item) = stream.next().await {
+                match item? {
+                    // The transactions of the batch were all replicated before it is received.
+                    BatchInfoResponseItem(UpdateItem::Batch(signed_batch)) => {
+                        next_sequence_number = signed_batch.batch.next_sequence_number;
+                        self.store
+                            .set_follower_next_sequence_number(&name, next_sequence_number)?;
+                    }
+                    BatchInfoResponseItem(UpdateItem::Transaction((_seq, digest))) => {
+                        self.replicate_transaction(name, digest).await?;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn replicate_transaction(
+        &self,
+        name: AuthorityName,
+        digest: TransactionDigest,
+    ) -> SuiResult {
+        if self.store.effects_exists(&digest)? {
+            return Ok(());
+        }
+
+        // The safe client checks the certificate and the signature on the effects, with
+        // the committee of the current epoch.
+        let response = self
+            .client(&name)?
+            .handle_transaction_info_request(TransactionInfoRequest::from(digest))
+            .await?;
+        match (response.certified_transaction, response.signed_effects) {
+            (Some(certificate), Some(signed_effects)) => {
+                self.execute_certificate(&certificate, &signed_effects)
+                    .await?;
+                Ok(())
+            }
+            // The authority sequenced the transaction, so it must know its certificate
+            // and effects.
+            _ => Err(SuiError::ByzantineAuthoritySuspicion { authority: name }),
+        }
+    }
+
+    /// Re-executes a certificate against the replica store, and checks the effects are
+    /// the same as the signed effects of the authority before persisting them.
+    pub async fn execute_certificate(
+        &self,
+        certificate: &CertifiedTransaction,
+        signed_effects: &SignedTransactionEffects,
+    ) -> SuiResult<TransactionEffects> {
+        let transaction_digest = *certificate.digest();
+        let authority = signed_effects.auth_signature.authority;
+        certificate.verify(&self.committee())?;
+        fp_ensure!(
+            signed_effects.effects.transaction_digest == transaction_digest,
+            SuiError::ByzantineAuthoritySuspicion { authority }
+        );
+
+        let _execution_guard = self.execution_lock.lock().await;
+        if self.store.effects_exists(&transaction_digest)? {
+            return self.store.get_effects(&transaction_digest);
+        }
+
+        let cost_schedule = self.cost_schedule(certificate.auth_sign_info.epoch)?;
+        let (gas_status, objects_by_kind) = transaction_input_checker::check_transaction_input(
+            &self.store,
+            certificate,
+            &cost_schedule,
+            &self.metrics.shared_obj_tx,
+        )
+        .await?;
+
+        // The replica does not see the consensus output, but executing in the order of
+        // the authority means shared objects are at the version the authority used.
+        let shared_object_refs: Vec<_> = objects_by_kind
+            .iter()
+            .filter(|(kind, _)| matches!(kind, InputObjectKind::SharedMoveObject(_)))
+            .map(|(_, obj)| obj.compute_object_reference())
+            .sorted()
+            .collect();
+        fp_ensure!(
+            shared_object_refs
+                == signed_effects
+                    .effects
+                    .shared_objects
+                    .iter()
+                    .cloned()
+                    .sorted()
+                    .collect::<Vec<_>>(),
+            SuiError::ReplicaEffectsMismatch {
+                authority,
+                digest: transaction_digest,
+            }
+        );
+
+        let transaction_dependencies = objects_by_kind
+            .iter()
+            .map(|(_, obj)| obj.previous_transaction)
+            .collect();
+        let input_owners = objects_by_kind
+            .iter()
+            .map(|(_, object)| (object.id(), object.owner))
+            .collect();
+        let mut temporary_store =
+            AuthorityTemporaryStore::new(self.store.clone(), objects_by_kind, transaction_digest);
+        let effects = execution_engine::execute_transaction_to_effects(
+            shared_object_refs,
+            &mut temporary_store,
+            certificate.data.clone(),
+            transaction_digest,
+            transaction_dependencies,
+            &self.move_vm,
+            &self.native_functions,
+            gas_status,
+        )?;
+        fp_ensure!(
+            effects == signed_effects.effects,
+            SuiError::ReplicaEffectsMismatch {
+                authority,
+                digest: transaction_digest,
+            }
+        );
+
+        let sequence_number = self.store.next_sequence_number()?;
+        self.store.update_state(
+            temporary_store,
+            certificate,
+            &effects.clone().to_unsigned_effects(),
+            Some(sequence_number),
+        )?;
+        self.reconfigure_after_epoch_change(certificate)?;
+        if self.notification_sender.receiver_count() > 0 {
+            // Sending only fails when the last subscriber just went away.
+            let _ = self
+                .notification_sender
+                .send(Arc::new(ExecutedTransaction::new(
+                    sequence_number,
+                    certificate,
+                    &effects,
+                    input_owners,
+                )));
+        }
+        self.metrics.replicated_transactions.inc();
+        debug!(
+            ?transaction_digest,
+            sequence_number, "Replicated transaction"
+        );
+        Ok(effects)
+    }
+}
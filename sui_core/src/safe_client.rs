@@ -30,6 +30,12 @@ impl<C> SafeClient<C> {
         }
     }
 
+    /// The same client, checking the responses against the committee of another epoch.
+    pub fn with_committee(mut self, committee: Committee) -> Self {
+        self.committee = committee;
+        self
+    }
+
     #[cfg(test)]
     pub fn authority_client(&mut self) -> &mut C {
         &mut self.authority_client
@@ -127,12 +133,13 @@ impl<C> SafeClient<C> {
         }
 
         if let Some(object_and_lock) = &response.object_and_lock {
-            // We should only be returning the object and lock data if requesting the latest object info.
+            // We should only be returning the lock data if requesting the latest object info.
             fp_ensure!(
-                matches!(
-                    request.request_kind,
-                    ObjectInfoRequestKind::LatestObjectInfo(_)
-                ),
+                object_and_lock.lock.is_none()
+                    || matches!(
+                        request.request_kind,
+                        ObjectInfoRequestKind::LatestObjectInfo(_)
+                    ),
                 SuiError::ByzantineAuthoritySuspicion {
                     authority: self.address
                 }
@@ -140,8 +147,8 @@ impl<C> SafeClient<C> {
 
             match response.requested_object_reference {
                 Some(obj_ref) => {
-                    // Since we are requesting the latest version, we should validate that if the object's
-                    // reference actually match with the one from the responded object reference.
+                    // We should validate that the object's reference actually matches the
+                    // responded object reference, for both the latest and past versions.
                     fp_ensure!(
                         object_and_lock.object.compute_object_reference() == obj_ref,
                         SuiError::ByzantineAuthoritySuspicion {
@@ -150,8 +157,8 @@ impl<C> SafeClient<C> {
                     );
                 }
                 None => {
-                    // Since we are returning the object for the latest version,
-                    // we must also have the requested object reference in the response.
+                    // Since we are returning the object, we must also have the
+                    // requested object reference in the response.
                     // Otherwise the authority has inconsistent data.
                     return Err(SuiError::ByzantineAuthoritySuspicion {
                         authority: self.address,
@@ -214,7 +221,9 @@ impl<C> SafeClient<C> {
                     error: "Safe Client: Batches must have some contents.".to_string()
                 }
             );
-            let reconstructed_batch = AuthorityBatch::make_next(prev_batch, transactions)?;
+            let mut reconstructed_batch = AuthorityBatch::make_next(prev_batch, transactions)?;
+            // The state root cannot be recomputed from the transaction digests alone.
+            reconstructed_batch.state_root = signed_batch.batch.state_root;
 
             fp_ensure!(
                 reconstructed_batch == signed_batch.batch,
@@ -9,7 +9,7 @@ use sui_types::{
     base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress},
     error::{SuiError, SuiResult},
     fp_ensure,
-    gas::{self, SuiGasStatus},
+    gas::{self, SuiCostSchedule, SuiGasStatus},
     messages::{InputObjectKind, SingleTransactionKind, TransactionData, TransactionEnvelope},
     object::{Object, Owner},
 };
@@ -18,26 +18,49 @@ use tracing::{debug, instrument};
 use crate::authority::SuiDataStore;
 
 #[instrument(level = "trace", skip_all)]
-pub async fn check_transaction_input<const A: bool, S, T>(
+pub async fn check_transaction_input<'a, const A: bool, S, T>(
     store: &SuiDataStore<A, S>,
     transaction: &TransactionEnvelope<T>,
+    cost_schedule: &'a SuiCostSchedule,
     shared_obj_metric: &IntCounter,
-) -> Result<(SuiGasStatus<'static>, Vec<(InputObjectKind, Object)>), SuiError>
+) -> Result<(SuiGasStatus<'a>, Vec<(InputObjectKind, Object)>), SuiError>
+where
+    S: Eq + Serialize + for<'de> Deserialize<'de>,
+{
+    let result = check_transaction_data_input(store, &transaction.data, cost_schedule).await?;
+    if transaction.contains_shared_object() {
+        shared_obj_metric.inc();
+    }
+    Ok(result)
+}
+
+/// Checks the inputs of a transaction from its data only, without any signature.
+/// Shared objects are read at their current version.
+/// The transaction is metered with the costs of `cost_schedule`.
+#[instrument(level = "trace", skip_all)]
+pub async fn check_transaction_data_input<'a, const A: bool, S>(
+    store: &SuiDataStore<A, S>,
+    transaction_data: &TransactionData,
+    cost_schedule: &'a SuiCostSchedule,
+) -> Result<(SuiGasStatus<'a>, Vec<(InputObjectKind, Object)>), SuiError>
 where
     S: Eq + Serialize + for<'de> Deserialize<'de>,
 {
     let (gas_object, mut gas_status) = check_gas(
         store,
-        transaction.gas_payment_object_ref().0,
-        transaction.data.gas_budget,
+        transaction_data.gas_payment_object_ref().0,
+        transaction_data.gas_budget,
+        transaction_data.gas_price,
+        cost_schedule,
     )
     .await?;
 
-    let objects_by_kind = check_locks(store, &transaction.data, gas_object).await?;
-
-    if transaction.contains_shared_object() {
-        shared_obj_metric.inc();
+    let objects_by_kind = check_locks(store, transaction_data, gas_object).await?;
 
+    if objects_by_kind
+        .iter()
+        .any(|(kind, _)| matches!(kind, InputObjectKind::SharedMoveObject(_)))
+    {
         // It's important that we do this here to make sure there is enough
         // gas to cover shared objects, before we lock all objects.
         gas_status.charge_consensus()?;
@@ -48,14 +71,17 @@ where
 
 /// Checking gas budget by fetching the gas object only from the store,
 /// and check whether the balance and budget satisfies the miminum requirement.
+/// Both computation and storage are charged at `gas_price` per gas unit.
 /// Returns the gas object (to be able to reuse it latter) and a gas status
 /// that will be used in the entire lifecycle of the transaction execution.
 #[instrument(level = "trace", skip_all)]
-async fn check_gas<const A: bool, S>(
+async fn check_gas<'a, const A: bool, S>(
     store: &SuiDataStore<A, S>,
     gas_payment_id: ObjectID,
     gas_budget: u64,
-) -> SuiResult<(Object, SuiGasStatus<'static>)>
+    gas_price: u64,
+    cost_schedule: &'a SuiCostSchedule,
+) -> SuiResult<(Object, SuiGasStatus<'a>)>
 where
     S: Eq + Serialize + for<'de> Deserialize<'de>,
 {
@@ -63,9 +89,8 @@ where
     let gas_object = gas_object.ok_or(SuiError::ObjectNotFound {
         object_id: gas_payment_id,
     })?;
-    gas::check_gas_balance(&gas_object, gas_budget)?;
-    // TODO: Pass in real computation gas unit price and storage gas unit price.
-    let gas_status = gas::start_gas_metering(gas_budget, 1, 1)?;
+    gas::check_gas_balance(&gas_object, gas_budget, gas_price)?;
+    let gas_status = gas::start_gas_metering(gas_budget, gas_price, gas_price, cost_schedule)?;
     Ok((gas_object, gas_status))
 }
 
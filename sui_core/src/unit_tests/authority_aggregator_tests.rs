@@ -74,7 +74,7 @@ pub fn get_local_client(
     clients.next().unwrap().authority_client()
 }
 
-fn transfer_coin_transaction(
+pub fn transfer_coin_transaction(
     src: SuiAddress,
     secret: &dyn signature::Signer<Signature>,
     dest: SuiAddress,
@@ -811,3 +811,307 @@ async fn test_process_transaction_fault_fail() {
     .await
     .is_err());
 }
+
+#[tokio::test]
+async fn test_retry_locked_object() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _) = get_key_pair();
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let mut authorities = init_local_authorities(genesis_objects).await.0;
+    let coin_ref = coin_object.compute_object_reference();
+    let gas_ref = gas_object.compute_object_reference();
+
+    // Nothing to do while the object is not locked.
+    assert!(authorities
+        .retry_locked_object(coin_ref, Duration::from_secs(5))
+        .await
+        .unwrap()
+        .is_none());
+
+    // The transaction only reaches two authorities, and a conflicting one reaches a third
+    // authority, which leaves the objects locked.
+    let tx1 = transfer_coin_transaction(addr1, &key1, addr2, coin_ref, gas_ref);
+    let tx2 = transfer_coin_transaction(addr1, &key1, addr1, coin_ref, gas_ref);
+    do_transaction(get_local_client(&mut authorities, 0), &tx1).await;
+    do_transaction(get_local_client(&mut authorities, 1), &tx1).await;
+    do_transaction(get_local_client(&mut authorities, 2), &tx2).await;
+
+    let object_locks = authorities
+        .get_object_locks(coin_ref, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(object_locks.locks.len(), 2);
+    assert_eq!(object_locks.locks[tx1.digest()].stake, 2);
+    assert_eq!(object_locks.locks[tx1.digest()].signatures.len(), 2);
+    assert_eq!(object_locks.locks[tx2.digest()].stake, 1);
+    assert_eq!(object_locks.unlocked_stake, 1);
+    assert_eq!(object_locks.outdated_stake, 0);
+    assert_eq!(object_locks.other_stake, 0);
+    assert!(!authorities
+        .is_transaction_rejected(&tx1, Duration::from_secs(5))
+        .await
+        .unwrap());
+
+    // The transaction locked by the most stake is certified and executed.
+    let (certificate, effects) = authorities
+        .retry_locked_object(coin_ref, Duration::from_secs(5))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(certificate.digest(), tx1.digest());
+    assert_eq!(effects.transaction_digest, *tx1.digest());
+    assert_eq!(
+        authorities
+            .get_latest_sequence_number(coin_object.id())
+            .await,
+        SequenceNumber::from(1)
+    );
+
+    // The conflicting transaction can no longer be certified, as its inputs are outdated.
+    assert!(authorities
+        .is_transaction_rejected(&tx2, Duration::from_secs(5))
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn test_retry_locked_object_conflict() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _) = get_key_pair();
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let mut authorities = init_local_authorities(genesis_objects).await.0;
+    let coin_ref = coin_object.compute_object_reference();
+    let gas_ref = gas_object.compute_object_reference();
+
+    // Two conflicting transactions each lock the objects on half of the authorities.
+    let tx1 = transfer_coin_transaction(addr1, &key1, addr2, coin_ref, gas_ref);
+    let tx2 = transfer_coin_transaction(addr1, &key1, addr1, coin_ref, gas_ref);
+    do_transaction(get_local_client(&mut authorities, 0), &tx1).await;
+    do_transaction(get_local_client(&mut authorities, 1), &tx1).await;
+    do_transaction(get_local_client(&mut authorities, 2), &tx2).await;
+    do_transaction(get_local_client(&mut authorities, 3), &tx2).await;
+
+    // Neither of them can ever be certified.
+    let result = authorities
+        .retry_locked_object(coin_ref, Duration::from_secs(5))
+        .await;
+    assert!(matches!(result, Err(SuiError::ObjectLockConflict { .. })));
+    for tx in [&tx1, &tx2] {
+        assert!(authorities
+            .is_transaction_rejected(tx, Duration::from_secs(5))
+            .await
+            .unwrap());
+    }
+}
+
+#[tokio::test]
+async fn test_execute_transaction_with_quorum_driver_configs() {
+    let configs = [
+        QuorumDriverConfig::default(),
+        // Latency sensitive: return as soon as a quorum answered, one authority at a time.
+        QuorumDriverConfig {
+            process_certificate: QuorumPhaseConfig {
+                timeout: Duration::from_secs(5),
+                wait_for_stragglers: false,
+            },
+            max_in_flight: Some(1),
+            prefer_low_latency: true,
+            ..Default::default()
+        },
+        // Auditing: wait for all the authorities in both phases.
+        QuorumDriverConfig {
+            process_transaction: QuorumPhaseConfig {
+                timeout: Duration::from_secs(5),
+                wait_for_stragglers: true,
+            },
+            timeout_after_quorum: Duration::from_secs(5),
+            ..Default::default()
+        },
+    ];
+    for config in configs {
+        let (addr1, key1) = get_key_pair();
+        let (addr2, _) = get_key_pair();
+        let coin_object = Object::with_owner_for_testing(addr1);
+        let gas_object = Object::with_owner_for_testing(addr1);
+        let genesis_objects =
+            authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+        let (authorities, states) = init_local_authorities(genesis_objects).await;
+        let authorities = authorities.with_config(config.clone());
+
+        let tx = transfer_coin_transaction(
+            addr1,
+            &key1,
+            addr2,
+            coin_object.compute_object_reference(),
+            gas_object.compute_object_reference(),
+        );
+        let (certificate, _effects) = authorities.execute_transaction(&tx).await.unwrap();
+
+        // Every authority answered when waiting for stragglers after the certificate.
+        let num_executed = states
+            .iter()
+            .filter(|state| {
+                state
+                    ._database
+                    .effects_exists(certificate.digest())
+                    .unwrap()
+            })
+            .count();
+        if config.process_certificate.wait_for_stragglers {
+            assert_eq!(num_executed, 4);
+        } else {
+            assert!(num_executed >= 3);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_max_in_flight_with_stalled_authority() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _) = get_key_pair();
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let mut authorities = init_local_authorities(genesis_objects).await.0;
+
+    // The first authority queried never answers.
+    get_local_client(&mut authorities, 0)
+        .fault_config
+        .stall_handle_transaction = true;
+    let authorities = authorities.with_config(QuorumDriverConfig {
+        process_transaction: QuorumPhaseConfig {
+            timeout: Duration::from_secs(5),
+            wait_for_stragglers: false,
+        },
+        max_in_flight: Some(1),
+        request_timeout: Duration::from_millis(100),
+        ..Default::default()
+    });
+
+    // The next authorities are queried once the stalled one is late.
+    let tx = transfer_coin_transaction(
+        addr1,
+        &key1,
+        addr2,
+        coin_object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    let start = Instant::now();
+    let certificate = authorities
+        .process_transaction(tx.clone(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(certificate.digest(), tx.digest());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_authorities_query_order() {
+    let genesis_objects = authority_genesis_objects(4, vec![]);
+    let (authorities, _) = init_local_authorities(genesis_objects).await;
+    let names: Vec<_> = authorities.authority_clients.keys().cloned().collect();
+    let query_order = |authorities: &AuthorityAggregator<LocalAuthorityClient>| {
+        authorities
+            .authorities_in_query_order()
+            .into_iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+    };
+
+    authorities.record_latency(names[0], Duration::from_millis(30));
+    authorities.record_latency(names[1], Duration::from_millis(10));
+    authorities.record_latency(names[2], Duration::from_millis(20));
+    // Authorities are queried in a fixed order by default.
+    assert_eq!(query_order(&authorities), names);
+
+    // The authorities never heard from come first, then the fastest ones.
+    let authorities = authorities.with_config(QuorumDriverConfig {
+        prefer_low_latency: true,
+        ..Default::default()
+    });
+    assert_eq!(
+        query_order(&authorities),
+        vec![names[3], names[1], names[2], names[0]]
+    );
+}
+
+#[tokio::test]
+async fn test_reconfigure() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _) = get_key_pair();
+    let gas_objects: Vec<_> = (0..4)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let genesis_objects = authority_genesis_objects(4, gas_objects.clone());
+    let (authorities, states) = init_local_authorities(genesis_objects).await;
+    let names: Vec<_> = authorities.authority_clients.keys().cloned().collect();
+
+    // A certificate of the first epoch, not yet executed when the epoch changes.
+    let tx = transfer_coin_transaction(
+        addr1,
+        &key1,
+        addr2,
+        gas_objects[0].compute_object_reference(),
+        gas_objects[1].compute_object_reference(),
+    );
+    let cert = authorities
+        .process_transaction(tx, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(cert.auth_sign_info.epoch, 0);
+
+    // The last authority leaves the committee of the next epoch.
+    let committee = Committee::new(1, names[..3].iter().map(|name| (*name, 1)).collect());
+    for state in &states {
+        state.reconfigure(committee.clone()).unwrap();
+    }
+
+    // The authorities joining the committee need a client, and the committee
+    // never goes back to a previous epoch.
+    let (_, key_pair) = get_key_pair();
+    let mut voting_rights = committee.voting_rights.clone();
+    voting_rights.insert(*key_pair.public_key_bytes(), 1);
+    let result = authorities.reconfigure(Committee::new(1, voting_rights), BTreeMap::new());
+    assert!(matches!(result, Err(SuiError::InvalidCommittee { .. })));
+    let result = authorities.reconfigure(authorities.committee.clone(), BTreeMap::new());
+    assert!(matches!(result, Err(SuiError::InvalidCommittee { .. })));
+
+    let authorities = authorities
+        .reconfigure(committee.clone(), BTreeMap::new())
+        .unwrap();
+    assert_eq!(authorities.committee, committee);
+    assert_eq!(
+        authorities.authority_clients.keys().collect::<Vec<_>>(),
+        names[..3].iter().collect::<Vec<_>>()
+    );
+
+    // The certificates of the previous epoch are rejected, while the new ones are
+    // certified by the new committee.
+    assert!(authorities
+        .process_certificate(cert, Duration::from_secs(5))
+        .await
+        .is_err());
+    let tx = transfer_coin_transaction(
+        addr1,
+        &key1,
+        addr2,
+        gas_objects[2].compute_object_reference(),
+        gas_objects[3].compute_object_reference(),
+    );
+    let cert = authorities
+        .process_transaction(tx, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(cert.auth_sign_info.epoch, 1);
+    authorities
+        .process_certificate(cert, Duration::from_secs(5))
+        .await
+        .unwrap();
+}
@@ -8,7 +8,12 @@ use move_binary_format::{
     file_format::{self, AddressIdentifierIndex, IdentifierIndex, ModuleHandle},
     CompiledModule,
 };
-use move_core_types::{account_address::AccountAddress, ident_str, language_storage::TypeTag};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{AbstractMemorySize, GasAlgebra},
+    ident_str,
+    language_storage::TypeTag,
+};
 use narwhal_executor::ExecutionIndices;
 use rand::{prelude::StdRng, SeedableRng};
 use sui_adapter::genesis;
@@ -16,8 +21,15 @@ use sui_types::{
     base_types::dbg_addr,
     crypto::KeyPair,
     crypto::{get_key_pair, Signature},
+    gas::{ComputationCost, INITIAL_SUI_COST_SCHEDULE, MIN_GAS_BUDGET},
+    id::VersionedID,
     messages::Transaction,
-    object::{Owner, OBJECT_START_VERSION},
+    object::{MoveObject, Owner, OBJECT_START_VERSION},
+    snapshot::StateSnapshot,
+    sui_system_state::{
+        Balance, GasChargeReports, GasSchedule, SuiSystemState, SystemParameters, TreasuryCap,
+        Validator, ValidatorSet,
+    },
 };
 
 use std::fs;
@@ -355,7 +367,7 @@ pub async fn send_and_confirm_transaction(
     let vote = response.signed_transaction.unwrap();
 
     // Collect signatures from a quorum of authorities
-    let mut builder = SignatureAggregator::try_new(transaction, &authority.committee).unwrap();
+    let mut builder = SignatureAggregator::try_new(transaction, &authority.committee()).unwrap();
     let certificate = builder
         .append(vote.auth_sign_info.authority, vote.auth_sign_info.signature)
         .unwrap()
@@ -827,6 +839,13 @@ async fn test_handle_confirmation_transaction_ok() {
         .expect("Exists")
         .is_none());
 
+    // Authorities do not index transactions for client queries.
+    let (transactions, _) = authority_state
+        .db()
+        .get_transactions_by_address(recipient, None, 10)
+        .unwrap();
+    assert!(transactions.is_empty());
+
     // Check that all the parents are returned.
     assert_eq!(
         authority_state
@@ -838,6 +857,73 @@ async fn test_handle_confirmation_transaction_ok() {
     );
 }
 
+#[tokio::test]
+async fn test_dry_run_transaction() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    let response = authority_state
+        .dry_run_transaction(transaction.data.clone())
+        .await
+        .unwrap();
+    assert_eq!(response.effects.transaction_digest, *transaction.digest());
+    assert_eq!(
+        &response.gas_cost_summary,
+        response.effects.status.gas_cost_summary()
+    );
+    assert!(response.gas_cost_summary.gas_used() > 0);
+
+    // Nothing was written, and no object was locked.
+    assert_eq!(
+        authority_state.get_object(&object_id).await.unwrap(),
+        Some(object.clone())
+    );
+    assert!(!authority_state
+        ._database
+        .effects_exists(transaction.digest())
+        .unwrap());
+
+    // The dry run predicts the effects of the actual execution.
+    let info = send_and_confirm_transaction(&authority_state, transaction)
+        .await
+        .unwrap();
+    assert_eq!(info.signed_effects.unwrap().effects, response.effects);
+
+    // Once the objects are used, the transaction can no longer be simulated.
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    assert!(authority_state
+        .dry_run_transaction(transaction.data)
+        .await
+        .is_err());
+}
+
 #[tokio::test]
 async fn test_handle_confirmation_transaction_idempotent() {
     let (sender, sender_key) = get_key_pair();
@@ -1115,6 +1201,106 @@ async fn test_get_latest_parent_entry() {
     assert_eq!(effects.transaction_digest, tx);
 }
 
+#[tokio::test]
+async fn test_handle_past_object_info_request() {
+    let (sender, sender_key) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let authority_state = init_state_with_ids(vec![(sender, gas_object_id)]).await;
+
+    let effects = create_move_object(&authority_state, &gas_object_id, &sender, &sender_key)
+        .await
+        .unwrap();
+    let (object_id, created_version, _) = effects.created[0].0;
+
+    call_framework_code(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        "ObjectBasics",
+        "set_value",
+        vec![],
+        vec![object_id],
+        vec![],
+        vec![42u64.to_le_bytes().to_vec()],
+    )
+    .await
+    .unwrap();
+    let latest = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // An authority does not keep past versions, but still returns the parent certificate.
+    let response = authority_state
+        .handle_object_info_request(ObjectInfoRequest::past_object_info_request(
+            object_id,
+            created_version,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.requested_object_reference.unwrap().1,
+        created_version
+    );
+    assert!(response.parent_certificate.is_some());
+    assert!(response.object_and_lock.is_none());
+
+    // The version currently held by the authority is served in full.
+    let response = authority_state
+        .handle_object_info_request(ObjectInfoRequest::past_object_info_request(
+            object_id,
+            latest.version(),
+        ))
+        .await
+        .unwrap();
+    let object_response = response.object_and_lock.unwrap();
+    assert_eq!(object_response.object, latest);
+    assert!(object_response.lock.is_none());
+}
+
+#[tokio::test]
+async fn test_replica_store_keeps_all_object_versions() {
+    let dir = env::temp_dir();
+    let path = dir.join(format!("DB_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+    let mut opts = rocksdb::Options::default();
+    opts.set_max_open_files(max_files_authority_tests());
+    let store = ReplicaStore::open(&path, Some(opts));
+
+    let object_id = ObjectID::random();
+    let mut object = Object::with_id_owner_for_testing(object_id, dbg_addr(1));
+    let first_version = object.clone();
+    store
+        .insert_object_direct(object.compute_object_reference(), &object)
+        .unwrap();
+
+    object.data.try_as_move_mut().unwrap().increment_version();
+    store
+        .insert_object_direct(object.compute_object_reference(), &object)
+        .unwrap();
+
+    assert_eq!(
+        store
+            .get_object_by_version(&object_id, first_version.version())
+            .unwrap(),
+        Some(first_version)
+    );
+    assert_eq!(
+        store
+            .get_object_by_version(&object_id, object.version())
+            .unwrap(),
+        Some(object.clone())
+    );
+    assert_eq!(
+        store
+            .get_object_by_version(&object_id, object.version().increment())
+            .unwrap(),
+        None
+    );
+}
+
 #[tokio::test]
 async fn test_account_state_ok() {
     let sender = dbg_addr(1);
@@ -1189,6 +1375,113 @@ async fn test_authority_persist() {
     assert_eq!(obj2.owner, recipient);
 }
 
+#[tokio::test]
+async fn test_state_snapshot_export_import() {
+    let (sender, sender_key) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let mut authority_state = init_state_with_ids(vec![(sender, gas_object_id)]).await;
+    let effects = create_move_object(&authority_state, &gas_object_id, &sender, &sender_key)
+        .await
+        .unwrap();
+    let (object_id, _, _) = effects.created[0].0;
+
+    // The executed transaction is not yet included in a batch.
+    assert!(authority_state.db().export_snapshot(1).is_err());
+    authority_state.init_batches_from_database().unwrap();
+
+    let snapshot = authority_state.db().export_snapshot(1).unwrap();
+    assert!(snapshot.batch.batch.state_root.is_some());
+    snapshot.verify(&authority_state.committee()).unwrap();
+    assert_eq!(snapshot.batch.batch.next_sequence_number, 1);
+
+    let mut tampered = snapshot.clone();
+    tampered.objects.pop();
+    assert!(tampered.verify(&authority_state.committee()).is_err());
+
+    // The state root must be signed by a quorum of the committee.
+    let (_, other_key) = get_key_pair();
+    let mut voting_rights = BTreeMap::new();
+    voting_rights.insert(authority_state.name, 1);
+    voting_rights.insert(*other_key.public_key_bytes(), 1);
+    assert!(snapshot.verify(&Committee::new(0, voting_rights)).is_err());
+
+    // A snapshot whose objects do not match its state root leaves the store empty.
+    let (_, _, _, store) = init_state_parameters();
+    let mut objects = snapshot.objects.clone();
+    objects.pop();
+    let mismatched = StateSnapshot::new(
+        snapshot.batch.clone(),
+        objects,
+        snapshot.parent_certificates.clone(),
+        snapshot.schedule.clone(),
+        snapshot.last_consensus_index.clone(),
+    );
+    assert!(store.import_snapshot(&mismatched).is_err());
+    assert!(store.database_is_empty().unwrap());
+
+    store.import_snapshot(&snapshot).unwrap();
+    assert_eq!(
+        store.get_object(&object_id).unwrap(),
+        authority_state.get_object(&object_id).await.unwrap()
+    );
+    assert_eq!(
+        store.get_account_objects(sender).unwrap(),
+        authority_state.db().get_account_objects(sender).unwrap()
+    );
+    assert!(store
+        .read_certificate(&effects.transaction_digest)
+        .unwrap()
+        .is_some());
+    assert_eq!(store.next_sequence_number().unwrap(), 1);
+    assert_eq!(
+        store.state_root().unwrap(),
+        authority_state.state_root().unwrap()
+    );
+    assert_eq!(
+        bcs::to_bytes(&store.last_consensus_index().unwrap()).unwrap(),
+        bcs::to_bytes(&authority_state.db().last_consensus_index().unwrap()).unwrap()
+    );
+
+    // Only an empty store can be bootstrapped from a snapshot.
+    assert!(store.import_snapshot(&snapshot).is_err());
+}
+
+#[tokio::test]
+async fn test_object_proofs_against_batch_state_root() {
+    let (sender, sender_key) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let mut authority_state = init_state_with_ids(vec![(sender, gas_object_id)]).await;
+    let genesis_root = authority_state.state_root().unwrap();
+
+    let effects = create_move_object(&authority_state, &gas_object_id, &sender, &sender_key)
+        .await
+        .unwrap();
+    let object_ref = effects.created[0].0;
+
+    // The state tree is brought up to date when the transaction is batched, and the batch
+    // carries the state root after it.
+    assert_eq!(authority_state.state_root().unwrap(), genesis_root);
+    let batch = authority_state.init_batches_from_database().unwrap();
+    let state_root = authority_state.state_root().unwrap();
+    assert_ne!(state_root, genesis_root);
+    assert_eq!(batch.state_root, Some(state_root));
+    assert_eq!(
+        authority_state.db().state_root_at(0).unwrap(),
+        Some(state_root)
+    );
+
+    let proof = authority_state.get_object_proof(object_ref.0).unwrap();
+    assert_eq!(proof.object_ref, Some(object_ref));
+    proof.verify(&state_root).unwrap();
+    assert!(proof.verify(&genesis_root).is_err());
+
+    let proof = authority_state
+        .get_object_proof(ObjectID::random())
+        .unwrap();
+    assert!(proof.object_ref.is_none());
+    proof.verify(&state_root).unwrap();
+}
+
 #[tokio::test]
 async fn test_idempotent_reversed_confirmation() {
     // In this test we exercise the case where an authority first receive the certificate,
@@ -1227,10 +1520,342 @@ async fn test_idempotent_reversed_confirmation() {
     );
 }
 
+#[tokio::test]
+async fn test_change_epoch() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object = Object::with_owner_for_testing(sender);
+    let object_ref = object.compute_object_reference();
+    let gas_object = Object::with_owner_for_testing(sender);
+    let gas_object_ref = gas_object.compute_object_reference();
+    let epoch_gas_object = Object::with_owner_for_testing(sender);
+    let epoch_gas_object_ref = epoch_gas_object.compute_object_reference();
+    let system_state_id = ObjectID::random();
+    let authority_state = init_state_with_objects([object, gas_object, epoch_gas_object]).await;
+    let system_state =
+        sui_system_state_for_testing(system_state_id, 1, &[(authority_state.name, 100)]);
+    authority_state.insert_genesis_object(system_state).await;
+
+    // At the end of the epoch, only the transaction changing the epoch is signed.
+    authority_state.halt_validator();
+    let transfer_transaction =
+        init_transfer_transaction(sender, &sender_key, recipient, object_ref, gas_object_ref);
+    let result = authority_state
+        .handle_transaction(transfer_transaction.clone())
+        .await;
+    assert_eq!(
+        result.unwrap_err(),
+        SuiError::ValidatorHaltedAtEpochEnd { epoch: 0 }
+    );
+
+    let data = authority_state
+        .new_change_epoch_transaction(sender, system_state_id, epoch_gas_object_ref, MAX_GAS)
+        .await
+        .unwrap();
+    assert!(data.is_change_epoch());
+    let signature = Signature::new(&data, &sender_key);
+    let response = authority_state
+        .handle_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap();
+    assert_eq!(response.signed_transaction.unwrap().auth_sign_info.epoch, 0);
+
+    // Once the epoch changed, the committee is the one of the system state.
+    authority_state
+        .reconfigure_from_system_state(&system_state_id)
+        .await
+        .unwrap();
+    assert!(!authority_state.is_halted());
+    let committee = authority_state.committee();
+    assert_eq!(committee.epoch, 1);
+    assert_eq!(committee.weight(&authority_state.name), 100);
+
+    let response = authority_state
+        .handle_transaction(transfer_transaction)
+        .await
+        .unwrap();
+    assert_eq!(response.signed_transaction.unwrap().auth_sign_info.epoch, 1);
+
+    // The authority never goes back to a previous epoch.
+    let result = authority_state.reconfigure(committee.as_ref().clone());
+    assert!(matches!(result, Err(SuiError::InvalidCommittee { .. })));
+}
+
+#[tokio::test]
+async fn test_change_epoch_gas_charges() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object = Object::with_owner_for_testing(sender);
+    let object_ref = object.compute_object_reference();
+    let gas_object = Object::with_owner_for_testing(sender);
+    let gas_object_ref = gas_object.compute_object_reference();
+    let epoch_gas_object = Object::with_owner_for_testing(sender);
+    let epoch_gas_object_ref = epoch_gas_object.compute_object_reference();
+    let system_state_id = ObjectID::random();
+    let authority_state = init_state_with_objects([object, gas_object, epoch_gas_object]).await;
+    let system_state =
+        sui_system_state_for_testing(system_state_id, 0, &[(authority_state.name, 100)]);
+    authority_state.insert_genesis_object(system_state).await;
+    assert_eq!(
+        authority_state.epoch_gas_charges().unwrap(),
+        GasCostSummary::default()
+    );
+
+    // The fees of the executed transactions are collected for the epoch.
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object_ref,
+        gas_object_ref,
+        &authority_state,
+    );
+    let effects = authority_state
+        .handle_confirmation_transaction(ConfirmationTransaction::new(certificate))
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects;
+    let gas_charges = authority_state.epoch_gas_charges().unwrap();
+    assert_eq!(&gas_charges, effects.status.gas_cost_summary());
+    assert!(gas_charges.computation_cost > 0);
+
+    // The fees are reported once the epoch ended.
+    let data = authority_state
+        .new_report_gas_charges_transaction(sender, system_state_id, epoch_gas_object_ref, MAX_GAS)
+        .await
+        .unwrap();
+    assert!(data.is_report_gas_charges());
+    let report = Transaction::new(data.clone(), Signature::new(&data, &sender_key));
+    let result = authority_state.handle_transaction(report.clone()).await;
+    assert!(matches!(result, Err(SuiError::InvalidEpochChange { .. })));
+
+    authority_state.halt_validator();
+    authority_state.handle_transaction(report).await.unwrap();
+
+    // Only the change to the next epoch is signed.
+    let framework_ref = authority_state
+        .get_object(&ObjectID::from(SUI_FRAMEWORK_ADDRESS))
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+    let data = TransactionData::new_change_epoch(
+        sender,
+        framework_ref,
+        system_state_id,
+        epoch_gas_object_ref,
+        2,
+        MAX_GAS,
+    );
+    let signature = Signature::new(&data, &sender_key);
+    let result = authority_state
+        .handle_transaction(Transaction::new(data, signature))
+        .await;
+    assert!(matches!(result, Err(SuiError::InvalidEpochChange { .. })));
+}
+
+#[tokio::test]
+async fn test_change_epoch_cost_schedule() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let objects: Vec<_> = (0..4)
+        .map(|_| Object::with_owner_for_testing(sender))
+        .collect();
+    let object_refs: Vec<_> = objects
+        .iter()
+        .map(|o| o.compute_object_reference())
+        .collect();
+    let system_state_id = ObjectID::random();
+    let authority_state = init_state_with_objects(objects).await;
+    let system_state =
+        sui_system_state_for_testing(system_state_id, 1, &[(authority_state.name, 100)]);
+    authority_state.insert_genesis_object(system_state).await;
+
+    // The validators voted for a schedule doubling the flat transaction fee, which takes
+    // effect at the next epoch.
+    let mut cost_schedule = INITIAL_SUI_COST_SCHEDULE.clone();
+    let min_transaction_cost = cost_schedule.sui_cost_table.min_transaction_cost.0;
+    cost_schedule.sui_cost_table.min_transaction_cost =
+        ComputationCost(min_transaction_cost.mul(AbstractMemorySize::new(2)));
+    let mut gas_schedule = GasSchedule::genesis();
+    gas_schedule.pending_schedule = vec![cost_schedule.to_bytes()];
+    gas_schedule.pending_epoch = 1;
+    authority_state
+        .insert_genesis_object(gas_schedule.to_object())
+        .await;
+
+    // Certificates are metered with the schedule of the epoch they were certified in.
+    let transfer = |object_ref, gas_object_ref| {
+        let mut certificate = init_certified_transfer_transaction(
+            sender,
+            &sender_key,
+            recipient,
+            object_ref,
+            gas_object_ref,
+            &authority_state,
+        );
+        certificate.auth_sign_info.epoch = authority_state.epoch();
+        ConfirmationTransaction::new(certificate)
+    };
+    let gas_cost_summary = |response: TransactionInfoResponse| {
+        response
+            .signed_effects
+            .unwrap()
+            .effects
+            .status
+            .gas_cost_summary()
+            .clone()
+    };
+    let response = authority_state
+        .handle_confirmation_transaction(transfer(object_refs[0], object_refs[1]))
+        .await
+        .unwrap();
+    let gas_cost = gas_cost_summary(response);
+
+    authority_state
+        .reconfigure_from_system_state(&system_state_id)
+        .await
+        .unwrap();
+    let response = authority_state
+        .handle_confirmation_transaction(transfer(object_refs[2], object_refs[3]))
+        .await
+        .unwrap();
+    let next_gas_cost = gas_cost_summary(response);
+    assert_eq!(
+        next_gas_cost.computation_cost,
+        gas_cost.computation_cost + *MIN_GAS_BUDGET
+    );
+    assert_eq!(
+        authority_state.cost_schedule(0).unwrap().to_bytes(),
+        INITIAL_SUI_COST_SCHEDULE.to_bytes()
+    );
+    assert_eq!(
+        authority_state.cost_schedule(1).unwrap().to_bytes(),
+        cost_schedule.to_bytes()
+    );
+
+    // The schedules are recorded in the store, and an epoch the authority never went
+    // through has none.
+    assert_eq!(authority_state.db().cost_schedules().len(), 2);
+    let result = authority_state.cost_schedule(2);
+    assert!(matches!(result, Err(SuiError::InvalidCostSchedule { .. })));
+}
+
+#[tokio::test]
+async fn test_change_epoch_seal() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let objects: Vec<_> = (0..6)
+        .map(|_| Object::with_owner_for_testing(sender))
+        .collect();
+    let object_refs: Vec<_> = objects
+        .iter()
+        .map(|o| o.compute_object_reference())
+        .collect();
+    let system_state_id = ObjectID::random();
+    let authority_state = init_state_with_objects(objects).await;
+    let system_state =
+        sui_system_state_for_testing(system_state_id, 1, &[(authority_state.name, 100)]);
+    authority_state.insert_genesis_object(system_state).await;
+    let certificate = |object_ref, gas_object_ref| {
+        init_certified_transfer_transaction(
+            sender,
+            &sender_key,
+            recipient,
+            object_ref,
+            gas_object_ref,
+            &authority_state,
+        )
+    };
+
+    // A certificate executed outside of consensus, and a transaction signed but never
+    // certified.
+    let executed = certificate(object_refs[0], object_refs[1]);
+    authority_state
+        .handle_confirmation_transaction(ConfirmationTransaction::new(executed.clone()))
+        .await
+        .unwrap();
+    let pending = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object_refs[4],
+        object_refs[5],
+    );
+    authority_state
+        .handle_transaction(pending.clone())
+        .await
+        .unwrap();
+
+    // Once sealed, certificates without shared objects only execute when sequenced.
+    authority_state.halt_validator();
+    let sealed = authority_state.seal_direct_execution().await.unwrap();
+    assert_eq!(sealed.len(), 1);
+    assert_eq!(sealed[0].digest(), executed.digest());
+
+    let late = certificate(object_refs[2], object_refs[3]);
+    let result = authority_state
+        .handle_confirmation_transaction(ConfirmationTransaction::new(late.clone()))
+        .await;
+    assert_eq!(
+        result.unwrap_err(),
+        SuiError::ValidatorHaltedAtEpochEnd { epoch: 0 }
+    );
+    authority_state
+        .handle_consensus_transaction(
+            ExecutionIndices::default(),
+            ConsensusTransaction::UserTransaction(late.clone()),
+        )
+        .await
+        .unwrap();
+    assert!(authority_state.db().effects_exists(late.digest()).unwrap());
+
+    // The epoch can change once a quorum sequenced the end of their epoch.
+    assert_eq!(authority_state.sealed_stake().unwrap(), 0);
+    authority_state
+        .handle_consensus_transaction(
+            ExecutionIndices::default(),
+            ConsensusTransaction::EndOfEpoch(authority_state.new_end_of_epoch()),
+        )
+        .await
+        .unwrap();
+    assert!(
+        authority_state.sealed_stake().unwrap() >= authority_state.committee().quorum_threshold()
+    );
+
+    // The locks of the transactions signed in the previous epoch are released, and they
+    // are signed again for the new epoch. Certificates of the previous epoch are rejected.
+    authority_state
+        .reconfigure_from_system_state(&system_state_id)
+        .await
+        .unwrap();
+    assert!(authority_state
+        .get_transaction_lock(&object_refs[4])
+        .await
+        .unwrap()
+        .is_none());
+    let response = authority_state.handle_transaction(pending).await.unwrap();
+    assert_eq!(response.signed_transaction.unwrap().auth_sign_info.epoch, 1);
+
+    let stale = certificate(object_refs[4], object_refs[5]);
+    let result = authority_state
+        .handle_consensus_transaction(
+            ExecutionIndices::default(),
+            ConsensusTransaction::UserTransaction(stale),
+        )
+        .await;
+    assert_eq!(
+        result.unwrap_err(),
+        SuiError::WrongEpoch { expected_epoch: 1 }
+    );
+}
+
 // helpers
 
 #[cfg(test)]
-fn init_state_parameters() -> (Committee, SuiAddress, KeyPair, Arc<AuthorityStore>) {
+pub fn init_state_parameters() -> (Committee, SuiAddress, KeyPair, Arc<AuthorityStore>) {
     let (authority_address, authority_key) = get_key_pair();
     let mut authorities = BTreeMap::new();
     authorities.insert(
@@ -1304,7 +1929,7 @@ pub fn init_transfer_transaction(
 }
 
 #[cfg(test)]
-fn init_certified_transfer_transaction(
+pub fn init_certified_transfer_transaction(
     sender: SuiAddress,
     secret: &KeyPair,
     recipient: SuiAddress,
@@ -1321,7 +1946,7 @@ fn init_certified_transfer_transaction(
         &*authority_state.secret,
     );
     let mut builder =
-        SignatureAggregator::try_new(transfer_transaction, &authority_state.committee).unwrap();
+        SignatureAggregator::try_new(transfer_transaction, &authority_state.committee()).unwrap();
     builder
         .append(vote.auth_sign_info.authority, vote.auth_sign_info.signature)
         .unwrap()
@@ -1506,7 +2131,7 @@ async fn shared_object() {
         .await
         .unwrap();
     let vote = response.signed_transaction.unwrap();
-    let certificate = SignatureAggregator::try_new(transaction, &authority.committee)
+    let certificate = SignatureAggregator::try_new(transaction, &authority.committee())
         .unwrap()
         .append(vote.auth_sign_info.authority, vote.auth_sign_info.signature)
         .unwrap()
@@ -1556,3 +2181,59 @@ async fn shared_object() {
         .version();
     assert_eq!(shared_object_version, SequenceNumber::from(2));
 }
+
+#[cfg(test)]
+pub fn sui_system_state_for_testing(
+    object_id: ObjectID,
+    epoch: EpochId,
+    validators: &[(AuthorityName, u64)],
+) -> Object {
+    let validator = |(name, stake): &(AuthorityName, u64)| Validator {
+        sui_address: SuiAddress::from(name),
+        pubkey_bytes: name.as_ref().to_vec(),
+        name: b"Validator".to_vec(),
+        net_address: vec![],
+        gas_price: INITIAL_REFERENCE_GAS_PRICE,
+        stake: Balance { value: *stake },
+        delegation: 0,
+        pending_stake: vec![],
+        pending_withdraw: 0,
+        pending_delegation: 0,
+        pending_delegation_withdraw: 0,
+        delegator_count: 0,
+        pending_delegator_count: 0,
+        pending_delegator_withdraw_count: 0,
+    };
+    let system_state = SuiSystemState {
+        id: VersionedID::new(object_id, OBJECT_START_VERSION),
+        epoch,
+        validators: ValidatorSet {
+            validator_stake: validators.iter().map(|(_, stake)| stake).sum(),
+            delegation_stake: 0,
+            quorum_stake_threshold: 0,
+            active_validators: validators.iter().map(validator).collect(),
+            pending_validators: vec![],
+            pending_removals: vec![],
+        },
+        treasury_cap: TreasuryCap {
+            id: VersionedID::new(ObjectID::random(), OBJECT_START_VERSION),
+            total_supply: 0,
+        },
+        storage_fund: Balance { value: 0 },
+        parameters: SystemParameters {
+            min_validator_stake: 0,
+            max_validator_stake: u64::MAX,
+            max_validator_candidate_count: 100,
+        },
+        delegation_reward: Balance { value: 0 },
+        reference_gas_price: INITIAL_REFERENCE_GAS_PRICE,
+        gas_charge_reports: GasChargeReports::default(),
+        gas_pool: Balance { value: 0 },
+    };
+    let contents = bcs::to_bytes(&system_state).unwrap();
+    Object::new_move(
+        MoveObject::new(SuiSystemState::type_(), contents),
+        Owner::Shared,
+        TransactionDigest::genesis(),
+    )
+}
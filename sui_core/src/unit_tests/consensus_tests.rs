@@ -83,7 +83,7 @@ pub async fn test_certificates(authority: &AuthorityState) -> Vec<CertifiedTrans
             .await
             .unwrap();
         let vote = response.signed_transaction.unwrap();
-        let certificate = SignatureAggregator::try_new(transaction, &authority.committee)
+        let certificate = SignatureAggregator::try_new(transaction, &authority.committee())
             .unwrap()
             .append(vote.auth_sign_info.authority, vote.auth_sign_info.signature)
             .unwrap()
@@ -152,7 +152,7 @@ async fn submit_transaction_to_consensus() {
     // Make a new consensus submitter instance.
     let submitter = ConsensusAdapter::new(
         consensus_address.clone(),
-        state.committee.clone(),
+        state.committee().as_ref().clone(),
         tx_consensus_listener,
         /* max_delay */ Duration::from_millis(1_000),
     );
@@ -169,7 +169,10 @@ async fn submit_transaction_to_consensus() {
 
         let message =
             bincode::deserialize(&serialized).expect("Failed to deserialize consensus tx");
-        let ConsensusTransaction::UserTransaction(certificate) = message;
+        let certificate = match message {
+            ConsensusTransaction::UserTransaction(certificate) => certificate,
+            message => panic!("Unexpected consensus transaction {message:?}"),
+        };
         // Set the shared object locks.
         state
             .handle_consensus_certificate(certificate.clone(), ExecutionIndices::default())
@@ -199,6 +202,7 @@ async fn submit_transaction_to_consensus() {
         ConsensusTransaction::UserTransaction(x) => {
             assert_eq!(x.to_transaction(), expected_transaction)
         }
+        message => panic!("Unexpected consensus transaction {message:?}"),
     }
 }
 
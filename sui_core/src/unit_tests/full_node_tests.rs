@@ -0,0 +1,126 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use signature::Signer;
+use sui_config::genesis;
+use sui_framework::DEFAULT_FRAMEWORK_PATH;
+use sui_types::{
+    crypto::get_key_pair,
+    gas_coin::GAS,
+    object::{Object, Owner, GAS_VALUE_FOR_TESTING},
+};
+
+use super::*;
+use crate::authority::{authority_tests::init_state_parameters, AuthorityState};
+use crate::authority_client::LocalAuthorityClient;
+
+async fn init_authority_and_full_node(
+    objects: Vec<Object>,
+) -> (Arc<AuthorityState>, FullNodeState<LocalAuthorityClient>) {
+    let genesis = genesis::Builder::new()
+        .sui_framework(PathBuf::from(DEFAULT_FRAMEWORK_PATH))
+        .move_framework(
+            PathBuf::from(DEFAULT_FRAMEWORK_PATH)
+                .join("deps")
+                .join("move-stdlib"),
+        )
+        .add_objects(objects)
+        .build();
+
+    let (committee, _, authority_key, store) = init_state_parameters();
+    let name = *authority_key.public_key_bytes();
+    let authority_state = Arc::new(
+        AuthorityState::new_with_genesis(
+            committee.clone(),
+            name,
+            Arc::pin(authority_key),
+            store,
+            &genesis,
+        )
+        .await,
+    );
+
+    let client = LocalAuthorityClient {
+        state: authority_state.clone(),
+        fault_config: Default::default(),
+    };
+    let path = tempfile::tempdir().unwrap().into_path();
+    let full_node = FullNodeState::new(path, committee, BTreeMap::from([(name, client)]), &genesis)
+        .await
+        .unwrap();
+    (authority_state, full_node)
+}
+
+#[tokio::test]
+async fn test_full_node_serves_reads_from_local_store() -> Result<(), anyhow::Error> {
+    let (sender, sender_key) = get_key_pair();
+    let (recipient, _) = get_key_pair();
+    let coin_object = Object::with_owner_for_testing(sender);
+    let gas_object = Object::with_owner_for_testing(sender);
+    let (authority_state, full_node) =
+        init_authority_and_full_node(vec![coin_object.clone(), gas_object.clone()]).await;
+
+    let _batch_join = tokio::task::spawn(async move {
+        authority_state
+            .run_batch_service(1, Duration::from_millis(10))
+            .await
+    });
+    let _replica_join = full_node.spawn_replica();
+    let mut transactions = full_node.subscribe_transactions()?;
+
+    // Writes are forwarded to the committee.
+    let data = full_node
+        .transfer_coin(
+            sender,
+            coin_object.id(),
+            Some(gas_object.id()),
+            Some(GAS_VALUE_FOR_TESTING),
+            recipient,
+        )
+        .await?;
+    let signature = sender_key.sign(&data.to_bytes());
+    let (certificate, _) = full_node
+        .execute_transaction(Transaction::new(data, signature))
+        .await?
+        .to_effect_response()?;
+    let digest = *certificate.digest();
+
+    // Reads are served once the replica re-executed the transaction.
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while full_node.get_transaction(digest).await.is_err() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Full node did not catch up with the authority");
+
+    // Subscribers are notified of the transactions the replica re-executed.
+    let (_, notified) = transactions.next().await.unwrap();
+    assert_eq!(notified, digest);
+
+    let object = full_node
+        .get_object_info(coin_object.id())
+        .await?
+        .into_object()?;
+    assert_eq!(object.owner, Owner::AddressOwner(recipient));
+    assert!(full_node
+        .get_owned_objects(recipient)
+        .await?
+        .iter()
+        .any(|(id, _, _)| *id == coin_object.id()));
+
+    let page = full_node
+        .get_transactions_by_address(recipient, None, 10)
+        .await?;
+    assert_eq!(
+        page.data.iter().map(|(_, d)| *d).collect::<Vec<_>>(),
+        vec![digest]
+    );
+    let balance = full_node.get_balance(recipient, GAS::type_tag()).await?;
+    assert_eq!(balance.coin_object_count, 1);
+    assert_eq!(balance.total_balance, GAS_VALUE_FOR_TESTING as u128);
+    Ok(())
+}
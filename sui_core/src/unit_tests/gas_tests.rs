@@ -8,6 +8,7 @@ use super::*;
 use super::authority_tests::{init_state_with_ids, send_and_confirm_transaction};
 use super::move_integration_tests::build_and_try_publish_test_package;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::gas_schedule::{AbstractMemorySize, GasAlgebra};
 use move_core_types::ident_str;
 use sui_adapter::genesis;
 use sui_types::gas_coin::GasCoin;
@@ -15,7 +16,10 @@ use sui_types::object::GAS_VALUE_FOR_TESTING;
 use sui_types::{
     base_types::dbg_addr,
     crypto::{get_key_pair, Signature},
-    gas::{MAX_GAS_BUDGET, MIN_GAS_BUDGET},
+    gas::{
+        ComputationCost, SuiCostSchedule, INITIAL_REFERENCE_GAS_PRICE, INITIAL_SUI_COST_SCHEDULE,
+        MAX_GAS_BUDGET, MIN_GAS_BUDGET,
+    },
     messages::Transaction,
 };
 
@@ -104,7 +108,8 @@ async fn test_native_transfer_sufficient_gas() -> SuiResult {
 
     // Mimic the process of gas charging, to check that we are charging
     // exactly what we should be charging.
-    let mut gas_status = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 1);
+    let mut gas_status =
+        SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 1, &INITIAL_SUI_COST_SCHEDULE);
     gas_status.charge_min_tx_gas()?;
     let obj_size = object.object_size_for_gas_metering();
     let gas_size = gas_object.object_size_for_gas_metering();
@@ -225,7 +230,8 @@ async fn test_publish_gas() -> SuiResult {
     };
 
     // Mimic the gas charge behavior and cross check the result with above.
-    let mut gas_status = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 1);
+    let mut gas_status =
+        SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 1, &INITIAL_SUI_COST_SCHEDULE);
     gas_status.charge_min_tx_gas()?;
     gas_status.charge_storage_read(
         genesis_objects
@@ -353,7 +359,8 @@ async fn test_move_call_gas() -> SuiResult {
     );
 
     // Mimic the gas charge behavior and cross check the result with above.
-    let mut gas_status = SuiGasStatus::new_with_budget(GAS_VALUE_FOR_TESTING, 1, 1);
+    let mut gas_status =
+        SuiGasStatus::new_with_budget(GAS_VALUE_FOR_TESTING, 1, 1, &INITIAL_SUI_COST_SCHEDULE);
     gas_status.charge_min_tx_gas()?;
     let package_object = authority_state
         .get_object(&package_object_ref.0)
@@ -384,6 +391,12 @@ async fn test_move_call_gas() -> SuiResult {
     // This is the total amount of storage cost paid. We will use this
     // to check if we get back the same amount of rebate latter.
     let prev_storage_cost = gas_cost.storage_cost;
+    // The storage cost paid is recorded on the written objects as their rebate.
+    let gas_object = authority_state.get_object(&gas_object_id).await?.unwrap();
+    assert_eq!(
+        created_object.storage_rebate + gas_object.storage_rebate,
+        prev_storage_cost
+    );
 
     // Execute object deletion, and make sure we have storage rebate.
     let data = TransactionData::new_move_call(
@@ -443,10 +456,12 @@ async fn test_move_call_gas() -> SuiResult {
 
 #[tokio::test]
 async fn test_storage_gas_unit_price() -> SuiResult {
-    let mut gas_status1 = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 1);
+    let mut gas_status1 =
+        SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 1, &INITIAL_SUI_COST_SCHEDULE);
     gas_status1.charge_storage_mutation(100, 200, 5)?;
     let gas_cost1 = gas_status1.summary(true);
-    let mut gas_status2 = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 3);
+    let mut gas_status2 =
+        SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 3, &INITIAL_SUI_COST_SCHEDULE);
     gas_status2.charge_storage_mutation(100, 200, 5)?;
     let gas_cost2 = gas_status2.summary(true);
     // Computation unit price is the same, hence computation cost should be the same.
@@ -458,6 +473,81 @@ async fn test_storage_gas_unit_price() -> SuiResult {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cost_schedule() -> SuiResult {
+    // The cost schedule survives its round trip through the on-chain gas schedule.
+    let mut cost_schedule =
+        SuiCostSchedule::from_bytes(&INITIAL_SUI_COST_SCHEDULE.to_bytes()).unwrap();
+    assert_eq!(
+        cost_schedule.to_bytes(),
+        INITIAL_SUI_COST_SCHEDULE.to_bytes()
+    );
+    assert!(matches!(
+        SuiCostSchedule::from_bytes(&[1, 2, 3]),
+        Err(SuiError::InvalidCostSchedule { .. })
+    ));
+
+    // Transactions are metered with the costs of the schedule they are given.
+    let min_transaction_cost = cost_schedule.sui_cost_table.min_transaction_cost.0;
+    cost_schedule.sui_cost_table.min_transaction_cost =
+        ComputationCost(min_transaction_cost.mul(AbstractMemorySize::new(2)));
+    let mut gas_status1 =
+        SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 1, &INITIAL_SUI_COST_SCHEDULE);
+    gas_status1.charge_min_tx_gas()?;
+    let mut gas_status2 = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1, 1, &cost_schedule);
+    gas_status2.charge_min_tx_gas()?;
+    assert_eq!(gas_status1.summary(true).computation_cost, *MIN_GAS_BUDGET);
+    assert_eq!(
+        gas_status2.summary(true).computation_cost,
+        *MIN_GAS_BUDGET * 2
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_native_transfer_gas_price() {
+    // The same transfer at twice the gas price costs twice as much.
+    let budget = *MAX_GAS_BUDGET / 2;
+    let result = execute_transfer(*MAX_GAS_BUDGET, budget, true).await;
+    let effects = result.response.unwrap().signed_effects.unwrap().effects;
+    let gas_cost = effects.status.gas_cost_summary();
+    let result = execute_transfer_with_price(*MAX_GAS_BUDGET, budget, 2, true).await;
+    let effects = result.response.unwrap().signed_effects.unwrap().effects;
+    let double_gas_cost = effects.status.gas_cost_summary();
+    assert_eq!(
+        double_gas_cost.computation_cost,
+        gas_cost.computation_cost * 2
+    );
+    assert_eq!(double_gas_cost.storage_cost, gas_cost.storage_cost * 2);
+    let gas_object = result
+        .authority_state
+        .get_object(&result.gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        GasCoin::try_from(&gas_object).unwrap().value(),
+        *MAX_GAS_BUDGET - double_gas_cost.gas_used()
+    );
+
+    // The balance must cover the budget at the gas price.
+    let result = execute_transfer_with_price(*MAX_GAS_BUDGET, budget + 1, 2, false).await;
+    assert!(matches!(
+        result.response.unwrap_err(),
+        SuiError::InsufficientGas { .. }
+    ));
+
+    // Transactions priced under the reference gas price are not signed.
+    let result = execute_transfer_with_price(*MAX_GAS_BUDGET, budget, 0, false).await;
+    assert_eq!(
+        result.response.unwrap_err(),
+        SuiError::GasPriceUnderReference {
+            gas_price: 0,
+            reference_gas_price: INITIAL_REFERENCE_GAS_PRICE,
+        }
+    );
+}
+
 struct TransferResult {
     pub authority_state: AuthorityState,
     pub object_id: ObjectID,
@@ -466,6 +556,21 @@ struct TransferResult {
 }
 
 async fn execute_transfer(gas_balance: u64, gas_budget: u64, run_confirm: bool) -> TransferResult {
+    execute_transfer_with_price(
+        gas_balance,
+        gas_budget,
+        INITIAL_REFERENCE_GAS_PRICE,
+        run_confirm,
+    )
+    .await
+}
+
+async fn execute_transfer_with_price(
+    gas_balance: u64,
+    gas_budget: u64,
+    gas_price: u64,
+    run_confirm: bool,
+) -> TransferResult {
     let (sender, sender_key) = get_key_pair();
     let object_id: ObjectID = ObjectID::random();
     let recipient = dbg_addr(2);
@@ -485,13 +590,14 @@ async fn execute_transfer(gas_balance: u64, gas_budget: u64, run_confirm: bool)
         .unwrap()
         .unwrap();
 
-    let data = TransactionData::new_transfer(
+    let mut data = TransactionData::new_transfer(
         recipient,
         object.compute_object_reference(),
         sender,
         gas_object_ref,
         gas_budget,
     );
+    data.gas_price = gas_price;
     let signature = Signature::new(&data, &sender_key);
     let tx = Transaction::new(data, signature);
 
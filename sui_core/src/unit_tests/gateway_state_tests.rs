@@ -4,21 +4,22 @@
 
 use std::{collections::HashSet, path::Path};
 
+use move_core_types::ident_str;
 use signature::Signer;
 
 use sui_framework::build_move_package_to_bytes;
 use sui_types::crypto::KeyPair;
 use sui_types::{crypto::get_key_pair, object::Owner};
 
-use sui_types::gas_coin::GasCoin;
+use sui_types::gas_coin::{GasCoin, GAS};
 use sui_types::messages::Transaction;
 use sui_types::object::{Object, GAS_VALUE_FOR_TESTING};
 use typed_store::Map;
 
 use super::*;
 use crate::authority_aggregator::authority_aggregator_tests::{
-    authority_genesis_objects, crate_object_move_transaction, get_local_client,
-    init_local_authorities,
+    authority_genesis_objects, crate_object_move_transaction, do_transaction, get_local_client,
+    init_local_authorities, transfer_coin_transaction,
 };
 use crate::authority_client::LocalAuthorityClient;
 use crate::gateway_state::{GatewayAPI, GatewayState};
@@ -52,7 +53,7 @@ async fn transfer_coin(
             signer,
             coin_object_id,
             Some(gas_object_id),
-            GAS_VALUE_FOR_TESTING,
+            Some(GAS_VALUE_FOR_TESTING),
             recipient,
         )
         .await?;
@@ -95,6 +96,215 @@ async fn test_transfer_coin() {
     assert_eq!(gateway.get_total_transaction_number().unwrap(), 1);
 }
 
+#[tokio::test]
+async fn test_transfer_coin_with_estimated_gas_budget() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    // Without a gas object either, the gateway picks the gas coin that is not transferred.
+    let data = gateway
+        .transfer_coin(addr1, coin_object.id(), None, None, addr2)
+        .await
+        .unwrap();
+    assert_eq!(data.gas().0, gas_object.id());
+    assert!(data.gas_budget >= *gas::MIN_GAS_BUDGET);
+    assert!(data.gas_budget < GAS_VALUE_FOR_TESTING);
+    let gas_budget = data.gas_budget;
+
+    let signature = key1.sign(&data.to_bytes());
+    let (_cert, effects) = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .to_effect_response()
+        .unwrap();
+    assert!(effects.status.is_ok());
+    let gas_used = effects.status.gas_cost_summary().gas_used();
+    assert_eq!(
+        gas_budget,
+        gas_used + gas_used * DEFAULT_GAS_ESTIMATION_MARGIN / 100
+    );
+}
+
+#[tokio::test]
+async fn test_estimated_gas_budget_insufficient_balance() {
+    let (addr1, _key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_id_owner_gas_for_testing(
+        ObjectID::random(),
+        SequenceNumber::new(),
+        addr1,
+        *gas::MIN_GAS_BUDGET,
+    );
+
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    let err = gateway
+        .transfer_coin(addr1, coin_object.id(), Some(gas_object.id()), None, addr2)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<SuiError>(),
+        Some(SuiError::InsufficientGasForEstimatedBudget { gas_object_id, balance, .. })
+            if *gas_object_id == gas_object.id() && *balance == *gas::MIN_GAS_BUDGET
+    ));
+}
+
+#[tokio::test]
+async fn test_choose_smallest_sufficient_gas_coin() {
+    let (addr1, _key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_objects: Vec<_> = [50000, 20000, 80000]
+        .into_iter()
+        .map(|value| {
+            Object::with_id_owner_gas_for_testing(
+                ObjectID::random(),
+                SequenceNumber::new(),
+                addr1,
+                value,
+            )
+        })
+        .collect();
+    let mut objects = gas_objects.clone();
+    objects.push(coin_object.clone());
+    let gateway = create_gateway_state(authority_genesis_objects(4, objects)).await;
+
+    // The coin to transfer is the richest one, but it cannot pay for gas.
+    let data = gateway
+        .transfer_coin(addr1, coin_object.id(), None, Some(30000), addr2)
+        .await
+        .unwrap();
+    assert_eq!(data.gas().0, gas_objects[0].id());
+
+    // A coin used as an input cannot pay for gas.
+    let err = gateway
+        .transfer_coin(
+            addr1,
+            coin_object.id(),
+            Some(coin_object.id()),
+            Some(30000),
+            addr2,
+        )
+        .await;
+    assert!(err.is_err());
+}
+
+#[tokio::test]
+async fn test_merge_gas_coins() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_objects: Vec<_> = (0..3)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let mut objects = gas_objects.clone();
+    objects.push(coin_object.clone());
+    let gateway = create_gateway_state(authority_genesis_objects(4, objects)).await;
+
+    // No single coin covers the budget, but merging coins does.
+    let gas_budget = GAS_VALUE_FOR_TESTING * 3 / 2;
+    let err = gateway
+        .transfer_coin(addr1, coin_object.id(), None, Some(gas_budget), addr2)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<SuiError>(),
+        Some(SuiError::GasCoinMergeRequired { .. })
+    ));
+
+    let data = gateway
+        .merge_gas_coins(addr1, gas_budget, vec![coin_object.id()])
+        .await
+        .unwrap()
+        .unwrap();
+    let signature = key1.sign(&data.to_bytes());
+    let response = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .to_merge_coin_response()
+        .unwrap();
+    let merged_coin = response.updated_coin.id();
+
+    assert!(gateway
+        .merge_gas_coins(addr1, gas_budget, vec![coin_object.id()])
+        .await
+        .unwrap()
+        .is_none());
+    let data = gateway
+        .transfer_coin(addr1, coin_object.id(), None, Some(gas_budget), addr2)
+        .await
+        .unwrap();
+    assert_eq!(data.gas().0, merged_coin);
+
+    // Even merged, the coins cannot cover this budget.
+    let err = gateway
+        .merge_gas_coins(addr1, GAS_VALUE_FOR_TESTING * 4, vec![coin_object.id()])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<SuiError>(),
+        Some(SuiError::InsufficientGasCoins { .. })
+    ));
+}
+
+#[test]
+fn test_total_balance_does_not_overflow() {
+    let (addr1, _key1) = get_key_pair();
+    let coins: Vec<_> = (0..3)
+        .map(|_| {
+            let coin = Object::with_owner_for_testing(addr1);
+            (coin.compute_object_reference(), u64::MAX)
+        })
+        .collect();
+    assert_eq!(total_balance(&coins), u64::MAX as u128 * 3);
+}
+
+#[test]
+fn test_plan_gas_coin_merge() {
+    let (addr1, _key1) = get_key_pair();
+    let coins: Vec<(ObjectRef, u64)> = [50, 40, 30, 5]
+        .into_iter()
+        .map(|balance| {
+            let coin = Object::with_owner_for_testing(addr1);
+            (coin.compute_object_reference(), balance)
+        })
+        .collect();
+    let id = |index: usize| coins[index].0 .0;
+
+    // The smallest coin which covers the merge pays for it, and the largest coins are merged.
+    assert_eq!(
+        plan_gas_coin_merge(&coins, 80, 5),
+        Some((id(0), vec![id(1)], id(3)))
+    );
+    assert_eq!(
+        plan_gas_coin_merge(&coins, 80, 10),
+        Some((id(0), vec![id(1)], id(2)))
+    );
+    // Paying for the merge leaves too little to cover the budget.
+    assert_eq!(plan_gas_coin_merge(&coins, 100, 10), None);
+    assert_eq!(
+        plan_gas_coin_merge(&coins, 100, 5),
+        Some((id(0), vec![id(1), id(2)], id(3)))
+    );
+    // No coin covers the merge.
+    assert_eq!(plan_gas_coin_merge(&coins, 80, 60), None);
+}
+
 #[tokio::test]
 async fn test_move_call() {
     let (addr1, key1) = get_key_pair();
@@ -141,7 +351,7 @@ async fn test_publish() {
             addr1,
             compiled_modules,
             Some(gas_object.id()),
-            GAS_VALUE_FOR_TESTING,
+            Some(GAS_VALUE_FOR_TESTING),
         )
         .await
         .unwrap();
@@ -155,6 +365,86 @@ async fn test_publish() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_batch_transaction() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coin_object1 = Object::with_owner_for_testing(addr1);
+    let coin_object2 = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects = authority_genesis_objects(
+        4,
+        vec![
+            coin_object1.clone(),
+            coin_object2.clone(),
+            gas_object.clone(),
+        ],
+    );
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    let transfer = |object_id| {
+        SingleTransactionParams::TransferCoin(TransferCoinParams {
+            recipient: addr2,
+            object_id,
+        })
+    };
+    let create_object = SingleTransactionParams::MoveCall(MoveCallParams {
+        package_object_id: ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+        module: ident_str!("ObjectBasics").to_owned(),
+        function: ident_str!("create").to_owned(),
+        type_arguments: vec![],
+        arguments: vec![
+            SuiJsonValue::new(serde_json::json!(100)).unwrap(),
+            SuiJsonValue::new(serde_json::json!(format!("{:#x}", addr1))).unwrap(),
+        ],
+    });
+    let data = gateway
+        .batch_transaction(
+            addr1,
+            vec![
+                transfer(coin_object1.id()),
+                create_object,
+                transfer(coin_object2.id()),
+            ],
+            None,
+            Some(GAS_VALUE_FOR_TESTING),
+        )
+        .await
+        .unwrap();
+    // The transferred coins cannot pay for gas.
+    assert_eq!(data.gas().0, gas_object.id());
+    assert_eq!(data.kind.batch_size(), 3);
+
+    let signature = key1.sign(&data.to_bytes());
+    let (_cert, effects) = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .to_effect_response()
+        .unwrap();
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.created.len(), 1);
+    assert_eq!(effects.created[0].1, Owner::AddressOwner(addr1));
+    let transferred: BTreeSet<_> = effects
+        .mutated_excluding_gas()
+        .map(|(object_ref, owner)| {
+            assert_eq!(*owner, Owner::AddressOwner(addr2));
+            object_ref.0
+        })
+        .collect();
+    assert_eq!(
+        transferred,
+        BTreeSet::from([coin_object1.id(), coin_object2.id()])
+    );
+
+    // An empty batch is rejected.
+    assert!(gateway
+        .batch_transaction(addr1, vec![], None, Some(GAS_VALUE_FOR_TESTING))
+        .await
+        .is_err());
+}
+
 #[tokio::test]
 async fn test_coin_split() {
     let (addr1, key1) = get_key_pair();
@@ -175,7 +465,7 @@ async fn test_coin_split() {
             coin_object.id(),
             split_amounts.clone(),
             Some(gas_object.id()),
-            GAS_VALUE_FOR_TESTING,
+            Some(GAS_VALUE_FOR_TESTING),
         )
         .await
         .unwrap();
@@ -232,7 +522,7 @@ async fn test_coin_split_insufficient_gas() {
             coin_object.id(),
             split_amounts.clone(),
             Some(gas_object.id()),
-            9, /* Insufficient gas */
+            Some(9), /* Insufficient gas */
         )
         .await
         .unwrap();
@@ -275,9 +565,9 @@ async fn test_coin_merge() {
         .merge_coins(
             addr1,
             coin_object1.id(),
-            coin_object2.id(),
+            vec![coin_object2.id()],
             Some(gas_object.id()),
-            GAS_VALUE_FOR_TESTING,
+            Some(GAS_VALUE_FOR_TESTING),
         )
         .await
         .unwrap();
@@ -302,6 +592,108 @@ async fn test_coin_merge() {
     assert_eq!(update_coin.value(), GAS_VALUE_FOR_TESTING * 2);
 }
 
+#[tokio::test]
+async fn test_coin_merge_multiple_coins() {
+    let (addr1, key1) = get_key_pair();
+
+    let coin_objects: Vec<_> = (0..4)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let mut objects = coin_objects.clone();
+    objects.push(gas_object.clone());
+    let gateway = create_gateway_state(authority_genesis_objects(4, objects)).await;
+
+    let coins_to_merge = coin_objects[1..].iter().map(|coin| coin.id()).collect();
+    let data = gateway
+        .merge_coins(
+            addr1,
+            coin_objects[0].id(),
+            coins_to_merge,
+            Some(gas_object.id()),
+            Some(GAS_VALUE_FOR_TESTING),
+        )
+        .await
+        .unwrap();
+
+    let signature = key1.sign(&data.to_bytes());
+    let response = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .to_merge_coin_response()
+        .unwrap();
+
+    assert_eq!(
+        (coin_objects[0].id(), coin_objects[0].version().increment()),
+        (response.updated_coin.id(), response.updated_coin.version())
+    );
+    let update_coin = GasCoin::try_from(response.updated_coin.data.try_as_move().unwrap()).unwrap();
+    assert_eq!(update_coin.value(), GAS_VALUE_FOR_TESTING * 4);
+    for coin in &coin_objects[1..] {
+        assert!(matches!(
+            gateway.get_object_info(coin.id()).await.unwrap(),
+            ObjectRead::Deleted(_)
+        ));
+    }
+
+    // At least one coin must be merged.
+    assert!(gateway
+        .merge_coins(
+            addr1,
+            coin_objects[0].id(),
+            vec![],
+            Some(gas_object.id()),
+            Some(GAS_VALUE_FOR_TESTING),
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_merge_all_coins() {
+    let (addr1, key1) = get_key_pair();
+
+    let coin_objects: Vec<_> = (0..5)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let gateway = create_gateway_state(authority_genesis_objects(4, coin_objects)).await;
+
+    let data = gateway
+        .merge_all_coins(addr1, GAS::type_tag(), None, Some(GAS_VALUE_FOR_TESTING))
+        .await
+        .unwrap();
+    let gas_payment = data.gas().0;
+    let signature = key1.sign(&data.to_bytes());
+    let response = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .to_merge_coin_response()
+        .unwrap();
+
+    // All coins but the gas payment are merged into one.
+    let update_coin = GasCoin::try_from(response.updated_coin.data.try_as_move().unwrap()).unwrap();
+    assert_eq!(update_coin.value(), GAS_VALUE_FOR_TESTING * 4);
+    let owned_objects: BTreeSet<_> = gateway
+        .get_owned_objects(addr1)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, _, _)| id)
+        .collect();
+    assert_eq!(
+        owned_objects,
+        BTreeSet::from([response.updated_coin.id(), gas_payment])
+    );
+
+    // Only two coins are left, the other one paying for the merge.
+    assert!(gateway
+        .merge_all_coins(addr1, GAS::type_tag(), None, Some(GAS_VALUE_FOR_TESTING))
+        .await
+        .is_err());
+}
+
 #[tokio::test]
 async fn test_recent_transactions() -> Result<(), anyhow::Error> {
     let (addr1, key1) = get_key_pair();
@@ -327,7 +719,7 @@ async fn test_recent_transactions() -> Result<(), anyhow::Error> {
     let mut digests = vec![];
     for obj_id in [object1.id(), object2.id(), object3.id()] {
         let data = gateway
-            .transfer_coin(addr1, obj_id, Some(gas_object.id()), 50000, addr2)
+            .transfer_coin(addr1, obj_id, Some(gas_object.id()), Some(50000), addr2)
             .await
             .unwrap();
         let signature = key1.sign(&data.to_bytes());
@@ -353,6 +745,280 @@ async fn test_recent_transactions() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_events() -> Result<(), anyhow::Error> {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _) = get_key_pair();
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects = authority_genesis_objects(4, vec![gas_object.clone()]);
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    let call = |function: &'static str, arguments: Vec<serde_json::Value>| {
+        let gateway = &gateway;
+        let key1 = &key1;
+        let gas_object_id = gas_object.id();
+        async move {
+            let data = gateway
+                .move_call(
+                    addr1,
+                    ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+                    ident_str!("ObjectBasics").to_owned(),
+                    Identifier::new(function).unwrap(),
+                    vec![],
+                    arguments
+                        .into_iter()
+                        .map(|arg| SuiJsonValue::new(arg).unwrap())
+                        .collect(),
+                    Some(gas_object_id),
+                    Some(GAS_VALUE_FOR_TESTING),
+                )
+                .await?;
+            let signature = key1.sign(&data.to_bytes());
+            let (cert, effects) = gateway
+                .execute_transaction(Transaction::new(data, signature))
+                .await?
+                .to_effect_response()?;
+            assert!(effects.status.is_ok());
+            Ok::<_, anyhow::Error>((cert, effects))
+        }
+    };
+    let create = |value: u64| {
+        call(
+            "create",
+            vec![
+                serde_json::json!(value),
+                serde_json::json!(format!("{:#x}", addr1)),
+            ],
+        )
+    };
+
+    // Creating objects emits no event.
+    let (_, effects) = create(100).await?;
+    let object1 = effects.created[0].0 .0;
+    let (_, effects) = create(200).await?;
+    let object2 = effects.created[0].0 .0;
+    let page = gateway.get_events(EventFilter::default(), None, 10).await?;
+    assert!(page.data.is_empty());
+    assert_eq!(page.next_cursor, None);
+
+    let mut subscription = gateway.subscribe_events(EventFilter {
+        object: Some(object1),
+        ..Default::default()
+    })?;
+    let update = || {
+        call(
+            "update",
+            vec![
+                serde_json::json!(format!("{:#x}", object1)),
+                serde_json::json!(format!("{:#x}", object2)),
+            ],
+        )
+    };
+    let (cert1, _) = update().await?;
+    let (cert2, _) = update().await?;
+
+    let event_type = StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: ident_str!("ObjectBasics").to_owned(),
+        name: ident_str!("NewValueEvent").to_owned(),
+        type_params: vec![],
+    };
+    let page = gateway.get_events(EventFilter::default(), None, 10).await?;
+    assert_eq!(page.next_cursor, None);
+    assert_eq!(page.data.len(), 2);
+    for (event, cert) in page.data.iter().zip([&cert1, &cert2]) {
+        assert_eq!(event.tx_digest, *cert.digest());
+        assert_eq!(event.sender, addr1);
+        assert_eq!(event.event.type_, event_type);
+    }
+
+    // Each criterion selects the events.
+    let filters = [
+        EventFilter {
+            event_type: Some(event_type.clone()),
+            ..Default::default()
+        },
+        EventFilter {
+            sender: Some(addr1),
+            ..Default::default()
+        },
+        EventFilter {
+            object: Some(object1),
+            ..Default::default()
+        },
+    ];
+    for filter in filters {
+        assert_eq!(gateway.get_events(filter, None, 10).await?.data, page.data);
+    }
+    let filter = EventFilter {
+        transaction: Some(*cert2.digest()),
+        ..Default::default()
+    };
+    assert_eq!(
+        gateway.get_events(filter, None, 10).await?.data,
+        page.data[1..]
+    );
+    let filter = EventFilter {
+        event_type: Some(event_type.clone()),
+        sender: Some(addr2),
+        ..Default::default()
+    };
+    assert!(gateway.get_events(filter, None, 10).await?.data.is_empty());
+
+    // Paginate through the events.
+    let first_page = gateway.get_events(EventFilter::default(), None, 1).await?;
+    assert_eq!(first_page.data, page.data[..1]);
+    assert_eq!(first_page.next_cursor, Some(page.data[1].id));
+    let second_page = gateway
+        .get_events(EventFilter::default(), first_page.next_cursor, 1)
+        .await?;
+    assert_eq!(second_page.data, page.data[1..]);
+    assert_eq!(second_page.next_cursor, None);
+    // Extends max page size allowed.
+    assert!(gateway
+        .get_events(EventFilter::default(), None, 100000)
+        .await
+        .is_err());
+
+    // The subscriber is notified of the events emitted after it subscribed.
+    assert_eq!(subscription.next().await.as_ref(), Some(&page.data[0]));
+    assert_eq!(subscription.next().await.as_ref(), Some(&page.data[1]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transaction_and_object_change_subscriptions() -> Result<(), anyhow::Error> {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _) = get_key_pair();
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    let mut transactions = gateway.subscribe_transactions()?;
+    let mut sender_changes = gateway.subscribe_object_changes(addr1)?;
+    let mut recipient_changes = gateway.subscribe_object_changes(addr2)?;
+
+    let (cert, effects) = transfer_coin(
+        &gateway,
+        addr1,
+        &key1,
+        coin_object.id(),
+        gas_object.id(),
+        addr2,
+    )
+    .await?;
+    let digest = *cert.digest();
+    assert_eq!(transactions.next().await, Some((0, digest)));
+
+    let mut sender_kinds = BTreeMap::new();
+    for _ in 0..2 {
+        let change = sender_changes.next().await.unwrap();
+        assert_eq!(change.tx_seq_number, 0);
+        assert_eq!(change.tx_digest, digest);
+        sender_kinds.insert(change.object_ref.0, change.kind);
+    }
+    assert_eq!(
+        sender_kinds,
+        BTreeMap::from([
+            (coin_object.id(), ObjectChangeKind::Sent),
+            (gas_object.id(), ObjectChangeKind::Mutated),
+        ])
+    );
+
+    let coin_ref = effects.mutated_excluding_gas().next().unwrap().0;
+    assert_eq!(
+        recipient_changes.next().await,
+        Some(ObjectChangeNotification {
+            tx_seq_number: 0,
+            tx_digest: digest,
+            object_ref: coin_ref,
+            kind: ObjectChangeKind::Received,
+        })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transactions_by_address_and_object() -> Result<(), anyhow::Error> {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _) = get_key_pair();
+    let (addr3, _) = get_key_pair();
+    let coin_object1 = Object::with_owner_for_testing(addr1);
+    let coin_object2 = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects = authority_genesis_objects(
+        4,
+        vec![
+            coin_object1.clone(),
+            coin_object2.clone(),
+            gas_object.clone(),
+        ],
+    );
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    let (cert1, _) = transfer_coin(
+        &gateway,
+        addr1,
+        &key1,
+        coin_object1.id(),
+        gas_object.id(),
+        addr2,
+    )
+    .await?;
+    let (cert2, _) = transfer_coin(
+        &gateway,
+        addr1,
+        &key1,
+        coin_object2.id(),
+        gas_object.id(),
+        addr3,
+    )
+    .await?;
+    let tx1 = (0, *cert1.digest());
+    let tx2 = (1, *cert2.digest());
+
+    // The sender sees both transactions, each recipient only the one it received.
+    let page = gateway.get_transactions_by_address(addr1, None, 10).await?;
+    assert_eq!(page.data, vec![tx1, tx2]);
+    assert_eq!(page.next_cursor, None);
+    let page = gateway.get_transactions_by_address(addr2, None, 10).await?;
+    assert_eq!(page.data, vec![tx1]);
+    let page = gateway.get_transactions_by_address(addr3, None, 10).await?;
+    assert_eq!(page.data, vec![tx2]);
+
+    // Paginate through the transactions of the sender.
+    let page = gateway.get_transactions_by_address(addr1, None, 1).await?;
+    assert_eq!(page.data, vec![tx1]);
+    assert_eq!(page.next_cursor, Some(1));
+    let page = gateway
+        .get_transactions_by_address(addr1, page.next_cursor, 1)
+        .await?;
+    assert_eq!(page.data, vec![tx2]);
+    assert_eq!(page.next_cursor, None);
+
+    let page = gateway
+        .get_transactions_by_object(coin_object1.id(), None, 10)
+        .await?;
+    assert_eq!(page.data, vec![tx1]);
+    let page = gateway
+        .get_transactions_by_object(gas_object.id(), None, 10)
+        .await?;
+    assert_eq!(page.data, vec![tx1, tx2]);
+    let page = gateway
+        .get_transactions_by_object(gas_object.id(), Some(1), 10)
+        .await?;
+    assert_eq!(page.data, vec![tx2]);
+
+    assert!(gateway
+        .get_transactions_by_address(addr1, None, MAX_TX_RANGE_SIZE + 1)
+        .await
+        .is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_equivocation_resilient() {
     let (addr1, key1) = get_key_pair();
@@ -372,7 +1038,7 @@ async fn test_equivocation_resilient() {
                 addr1,
                 coin_object.id(),
                 Some(gas_object.id()),
-                GAS_VALUE_FOR_TESTING,
+                Some(GAS_VALUE_FOR_TESTING),
                 recipient,
             )
             .await
@@ -411,10 +1077,10 @@ async fn test_transfer_coin_with_retry() {
         authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
     let mut gateway = create_gateway_state(genesis_objects).await;
     // Make two authorities fail at the end of certificate processing.
-    get_local_client(&mut gateway.authorities, 0)
+    get_local_client(gateway.authorities_mut(), 0)
         .fault_config
         .fail_after_handle_confirmation = true;
-    get_local_client(&mut gateway.authorities, 1)
+    get_local_client(gateway.authorities_mut(), 1)
         .fault_config
         .fail_after_handle_confirmation = true;
 
@@ -447,7 +1113,7 @@ async fn test_transfer_coin_with_retry() {
     );
 
     // Recover one of the authorities.
-    get_local_client(&mut gateway.authorities, 1)
+    get_local_client(gateway.authorities_mut(), 1)
         .fault_config
         .fail_after_handle_confirmation = false;
 
@@ -491,3 +1157,204 @@ async fn test_transfer_coin_with_retry() {
         tx_digest
     );
 }
+
+#[tokio::test]
+async fn test_recover_pending_transaction_executed() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let mut gateway = create_gateway_state(genesis_objects).await;
+    // Make two authorities fail at the end of certificate processing.
+    get_local_client(gateway.authorities_mut(), 0)
+        .fault_config
+        .fail_after_handle_confirmation = true;
+    get_local_client(gateway.authorities_mut(), 1)
+        .fault_config
+        .fail_after_handle_confirmation = true;
+
+    // The transaction is certified, but the gateway does not get its effects, as if it
+    // crashed before the end of the execution.
+    assert!(transfer_coin(
+        &gateway,
+        addr1,
+        &key1,
+        coin_object.id(),
+        gas_object.id(),
+        addr2,
+    )
+    .await
+    .is_err());
+    assert_eq!(gateway.store().pending_transactions().iter().count(), 1);
+    let (tx_digest, _) = gateway
+        .store()
+        .pending_transactions()
+        .iter()
+        .next()
+        .unwrap();
+
+    get_local_client(gateway.authorities_mut(), 1)
+        .fault_config
+        .fail_after_handle_confirmation = false;
+
+    // The recovery finishes the execution of the transaction.
+    gateway.recover_pending_transactions().await.unwrap();
+    assert_eq!(gateway.store().pending_transactions().iter().count(), 0);
+    assert!(gateway.store().effects_exists(&tx_digest).unwrap());
+    assert_eq!(gateway.store().next_sequence_number().unwrap(), 1);
+    assert_eq!(gateway.store().get_account_objects(addr2).unwrap().len(), 1);
+    assert_eq!(
+        gateway
+            .store()
+            .get_object(&coin_object.id())
+            .unwrap()
+            .unwrap()
+            .owner,
+        Owner::AddressOwner(addr2)
+    );
+}
+
+#[tokio::test]
+async fn test_recover_pending_transaction_released() {
+    let (addr1, key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let mut gateway = create_gateway_state(genesis_objects).await;
+
+    // Lock the objects to a transaction on the gateway only, as if it crashed before
+    // sending the transaction to the authorities.
+    let data = gateway
+        .transfer_coin(
+            addr1,
+            coin_object.id(),
+            Some(gas_object.id()),
+            Some(GAS_VALUE_FOR_TESTING),
+            addr2,
+        )
+        .await
+        .unwrap();
+    let signature = key1.sign(&data.to_bytes());
+    let transaction = Transaction::new(data, signature);
+    let tx_digest = *transaction.digest();
+    let coin_ref = coin_object.compute_object_reference();
+    let gas_ref = gas_object.compute_object_reference();
+    gateway
+        .store()
+        .set_transaction_lock(&[coin_ref, gas_ref], transaction.clone())
+        .unwrap();
+
+    // The authorities cannot be reached: the transaction stays pending, with its locks.
+    for i in 0..4 {
+        get_local_client(gateway.authorities_mut(), i)
+            .fault_config
+            .fail_before_handle_transaction = true;
+    }
+    gateway.recover_pending_transactions().await.unwrap();
+    assert_eq!(gateway.store().pending_transactions().iter().count(), 1);
+    assert_eq!(
+        gateway.store().get_transaction_lock(&coin_ref).unwrap(),
+        Some(transaction)
+    );
+
+    // A quorum of authorities locks the objects on a conflicting transaction, so the
+    // pending transaction can never be certified.
+    for i in 0..4 {
+        get_local_client(gateway.authorities_mut(), i)
+            .fault_config
+            .reset();
+    }
+    let conflicting_transaction = transfer_coin_transaction(addr1, &key1, addr1, coin_ref, gas_ref);
+    for i in 0..3 {
+        do_transaction(
+            get_local_client(gateway.authorities_mut(), i),
+            &conflicting_transaction,
+        )
+        .await;
+    }
+
+    // The recovery releases the transaction and its locks.
+    gateway.recover_pending_transactions().await.unwrap();
+    assert_eq!(gateway.store().pending_transactions().iter().count(), 0);
+    assert!(!gateway.store().transaction_exists(&tx_digest).unwrap());
+    assert!(!gateway.store().effects_exists(&tx_digest).unwrap());
+    assert_eq!(
+        gateway.store().get_transaction_lock(&coin_ref).unwrap(),
+        None
+    );
+    assert_eq!(
+        gateway.store().get_transaction_lock(&gas_ref).unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_reconfigure() {
+    let genesis_objects = authority_genesis_objects(4, vec![]);
+    let gateway = create_gateway_state(genesis_objects).await;
+    let committee = gateway.authorities().committee.clone();
+
+    // The gateway moves to the committee of the next epoch, keeping its clients.
+    let next_committee = Committee::new(1, committee.voting_rights.clone());
+    gateway
+        .reconfigure(next_committee.clone(), 2, BTreeMap::new())
+        .unwrap();
+    let authorities = gateway.authorities();
+    assert_eq!(authorities.committee, next_committee);
+    assert_eq!(authorities.authority_clients.len(), 4);
+    assert_eq!(gateway.reference_gas_price(), 2);
+
+    assert!(gateway.reconfigure(committee, 2, BTreeMap::new()).is_err());
+    let next_committee = Committee::new(2, committee.voting_rights.clone());
+    assert!(gateway
+        .reconfigure(next_committee, 0, BTreeMap::new())
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_reference_gas_price() {
+    let (addr1, _key1) = get_key_pair();
+    let (addr2, _key2) = get_key_pair();
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects =
+        authority_genesis_objects(4, vec![coin_object.clone(), gas_object.clone()]);
+    let gateway = create_gateway_state(genesis_objects).await;
+    let committee = gateway.authorities().committee.clone();
+
+    let data = gateway
+        .transfer_coin(addr1, coin_object.id(), None, Some(1000), addr2)
+        .await
+        .unwrap();
+    assert_eq!(data.gas_price, gas::INITIAL_REFERENCE_GAS_PRICE);
+
+    // Transactions are priced at the reference gas price of the current epoch, and the
+    // gas coin must cover the budget at this price.
+    let next_committee = Committee::new(1, committee.voting_rights.clone());
+    gateway
+        .reconfigure(next_committee, 3, BTreeMap::new())
+        .unwrap();
+    let data = gateway
+        .transfer_coin(addr1, coin_object.id(), None, Some(1000), addr2)
+        .await
+        .unwrap();
+    assert_eq!(data.gas_price, 3);
+    let result = gateway
+        .transfer_coin(
+            addr1,
+            coin_object.id(),
+            None,
+            Some(GAS_VALUE_FOR_TESTING / 2),
+            addr2,
+        )
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,148 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::authority::authority_tests::*;
+use std::sync::Arc;
+use sui_adapter::genesis;
+use sui_types::{
+    base_types::ObjectID,
+    batch::UpdateItem,
+    crypto::get_key_pair,
+    error::SuiError,
+    messages::{BatchInfoRequest, ObjectInfoRequest, TransactionInfoRequest},
+};
+
+#[tokio::test]
+async fn test_prune_old_transactions() {
+    let (sender, sender_key) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let authority_state = Arc::new(init_state_with_ids(vec![(sender, gas_object_id)]).await);
+
+    // Make one batch per transaction.
+    let inner_state = authority_state.clone();
+    let _join = tokio::task::spawn(async move {
+        inner_state
+            .run_batch_service(1, Duration::from_millis(10))
+            .await
+    });
+    let mut rx = authority_state.subscribe_batch();
+
+    let effects = create_move_object(&authority_state, &gas_object_id, &sender, &sender_key)
+        .await
+        .unwrap();
+    let object_id = effects.created[0].0 .0;
+    let mut digests = vec![effects.transaction_digest];
+
+    // Mutate the object a few times, so that earlier transactions are no longer
+    // the latest parents of either the object or the gas object.
+    let package = get_genesis_package_by_module(&genesis::clone_genesis_packages(), "ObjectBasics");
+    for value in 0..4u64 {
+        let effects = call_move(
+            &authority_state,
+            &gas_object_id,
+            &sender,
+            &sender_key,
+            &package,
+            "ObjectBasics",
+            "set_value",
+            vec![],
+            vec![object_id],
+            vec![],
+            vec![value.to_le_bytes().to_vec()],
+        )
+        .await
+        .unwrap();
+        digests.push(effects.transaction_digest);
+    }
+
+    // Wait for all transactions to be included in a batch.
+    loop {
+        if let UpdateItem::Batch(signed_batch) = rx.recv().await.unwrap() {
+            if signed_batch.batch.next_sequence_number == digests.len() as u64 {
+                break;
+            }
+        }
+    }
+
+    // Keep the last two transactions only.
+    let num_pruned = authority_state
+        .prune(&RetentionPolicy::Transactions(2))
+        .unwrap();
+    assert_eq!(num_pruned, 3);
+
+    for digest in &digests[..3] {
+        assert!(authority_state
+            .read_certificate(digest)
+            .await
+            .unwrap()
+            .is_none());
+    }
+    for digest in &digests[3..] {
+        assert!(authority_state
+            .read_certificate(digest)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    // Pruned transactions are still known to be executed, by the digest of their effects.
+    for digest in &digests[..3] {
+        let result = authority_state
+            .handle_transaction_info_request(TransactionInfoRequest::from(*digest))
+            .await;
+        assert!(matches!(
+            result,
+            Err(SuiError::TransactionEffectsPruned { digest: pruned, .. }) if pruned == *digest
+        ));
+    }
+
+    // Only the parent entries of the retained versions of the object are left.
+    assert_eq!(
+        authority_state
+            .db()
+            .get_parent_iterator(object_id, None)
+            .unwrap()
+            .count(),
+        2
+    );
+
+    // Batches can no longer be requested from before the watermark.
+    let result = authority_state
+        .handle_batch_info_request(BatchInfoRequest {
+            start: Some(0),
+            length: 10,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(SuiError::PrunedSequenceRangeError { lowest_retained: 3 })
+    ));
+    assert!(authority_state
+        .handle_batch_info_request(BatchInfoRequest {
+            start: Some(3),
+            length: 10,
+        })
+        .await
+        .is_ok());
+
+    // The certificate of the latest version of the object is still served.
+    let response = authority_state
+        .handle_object_info_request(ObjectInfoRequest::latest_object_info_request(
+            object_id, None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        *response.parent_certificate.unwrap().digest(),
+        digests[digests.len() - 1]
+    );
+
+    // Pruning again with the same policy has nothing left to do.
+    assert_eq!(
+        authority_state
+            .prune(&RetentionPolicy::Transactions(2))
+            .unwrap(),
+        0
+    );
+}
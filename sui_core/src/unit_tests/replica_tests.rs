@@ -0,0 +1,186 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::authority::{authority_tests::*, AuthorityState};
+use crate::authority_client::LocalAuthorityClient;
+use std::{env, fs, path::PathBuf};
+use sui_adapter::genesis as adapter_genesis;
+use sui_config::genesis;
+use sui_framework::DEFAULT_FRAMEWORK_PATH;
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    crypto::get_key_pair,
+    messages::TransactionInfoRequest,
+    object::Object,
+};
+
+fn replica_store() -> Arc<ReplicaStore> {
+    let dir = env::temp_dir();
+    let path = dir.join(format!("DB_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+    let mut opts = rocksdb::Options::default();
+    opts.set_max_open_files(max_files_authority_tests());
+    Arc::new(ReplicaStore::open(path, Some(opts)))
+}
+
+async fn init_authority_and_replica(
+    objects: Vec<Object>,
+) -> (Arc<AuthorityState>, ReplicaNode<LocalAuthorityClient>) {
+    let genesis = genesis::Builder::new()
+        .sui_framework(PathBuf::from(DEFAULT_FRAMEWORK_PATH))
+        .move_framework(
+            PathBuf::from(DEFAULT_FRAMEWORK_PATH)
+                .join("deps")
+                .join("move-stdlib"),
+        )
+        .add_objects(objects)
+        .build();
+
+    let (committee, _, authority_key, store) = init_state_parameters();
+    let name = *authority_key.public_key_bytes();
+    let authority_state = Arc::new(
+        AuthorityState::new_with_genesis(
+            committee.clone(),
+            name,
+            Arc::pin(authority_key),
+            store,
+            &genesis,
+        )
+        .await,
+    );
+
+    let client = LocalAuthorityClient {
+        state: authority_state.clone(),
+        fault_config: Default::default(),
+    };
+    let replica = ReplicaNode::new(
+        committee,
+        replica_store(),
+        BTreeMap::from([(name, client)]),
+        &genesis,
+    )
+    .await
+    .unwrap();
+    (authority_state, replica)
+}
+
+#[tokio::test]
+async fn test_replica_follows_authority() {
+    let (sender, sender_key) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, replica) =
+        init_authority_and_replica(vec![Object::with_id_owner_for_testing(
+            gas_object_id,
+            sender,
+        )])
+        .await;
+    let replica = Arc::new(replica);
+
+    let inner_state = authority_state.clone();
+    let _batch_join = tokio::task::spawn(async move {
+        inner_state
+            .run_batch_service(1, Duration::from_millis(10))
+            .await
+    });
+    let name = authority_state.name;
+    let inner_replica = replica.clone();
+    let _follow_join =
+        tokio::task::spawn(async move { inner_replica.follow_authority(name).await });
+
+    let effects = create_move_object(&authority_state, &gas_object_id, &sender, &sender_key)
+        .await
+        .unwrap();
+    let object_id = effects.created[0].0 .0;
+    let package =
+        get_genesis_package_by_module(&adapter_genesis::clone_genesis_packages(), "ObjectBasics");
+    let effects = call_move(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &package,
+        "ObjectBasics",
+        "set_value",
+        vec![],
+        vec![object_id],
+        vec![],
+        vec![42u64.to_le_bytes().to_vec()],
+    )
+    .await
+    .unwrap();
+    let last_digest = effects.transaction_digest;
+
+    // Wait for the replica to catch up with the authority.
+    let store = replica.store();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !store.effects_exists(&last_digest).unwrap() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Replica did not catch up with the authority");
+
+    // It records how far it followed the authority, to resume from there.
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while store.follower_next_sequence_number(&name).unwrap() < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Replica did not record how far it followed the authority");
+
+    // The replica re-executed to the same effects and objects as the authority.
+    assert_eq!(store.get_effects(&last_digest).unwrap(), effects);
+    for id in [object_id, gas_object_id] {
+        assert_eq!(
+            store.get_object(&id).unwrap(),
+            authority_state.get_objects(&[id]).await.unwrap()[0]
+        );
+    }
+
+    // Unlike the authority, it keeps older versions of the objects.
+    let version = store.get_object(&object_id).unwrap().unwrap().version();
+    let previous_version = SequenceNumber::from(version.value() - 1);
+    assert!(store
+        .get_object_by_version(&object_id, previous_version)
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_replica_rejects_mismatching_effects() {
+    let (sender, sender_key) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, replica) =
+        init_authority_and_replica(vec![Object::with_id_owner_for_testing(
+            gas_object_id,
+            sender,
+        )])
+        .await;
+
+    let effects = create_move_object(&authority_state, &gas_object_id, &sender, &sender_key)
+        .await
+        .unwrap();
+    let digest = effects.transaction_digest;
+    let response = authority_state
+        .handle_transaction_info_request(TransactionInfoRequest::from(digest))
+        .await
+        .unwrap();
+    let certificate = response.certified_transaction.unwrap();
+    let mut signed_effects = response.signed_effects.unwrap();
+
+    // Pretend the authority signed effects creating one more object.
+    let mut created = signed_effects.effects.created[0].clone();
+    created.0 .0 = ObjectID::random();
+    signed_effects.effects.created.push(created);
+
+    let result = replica
+        .execute_certificate(&certificate, &signed_effects)
+        .await;
+    assert!(matches!(
+        result,
+        Err(SuiError::ReplicaEffectsMismatch { digest: d, .. }) if d == digest
+    ));
+    assert!(!replica.store().effects_exists(&digest).unwrap());
+}
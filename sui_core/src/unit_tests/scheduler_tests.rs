@@ -0,0 +1,155 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::authority::authority_tests::*;
+use sui_types::{
+    base_types::{SuiAddress, TransactionDigest},
+    crypto::get_key_pair,
+    messages::ExecutionStatus,
+    object::{Object, Owner},
+};
+use typed_store::Map;
+
+#[tokio::test]
+async fn test_execute_independent_certificates() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = SuiAddress::random_for_testing_only();
+    let objects: Vec<_> = (0..8)
+        .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), sender))
+        .collect();
+    let gas_objects: Vec<_> = (0..8)
+        .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), sender))
+        .collect();
+    let authority_state =
+        Arc::new(init_state_with_objects(objects.iter().chain(gas_objects.iter()).cloned()).await);
+
+    let certificates: Vec<_> = objects
+        .iter()
+        .zip(gas_objects.iter())
+        .map(|(object, gas_object)| {
+            init_certified_transfer_transaction(
+                sender,
+                &sender_key,
+                recipient,
+                object.compute_object_reference(),
+                gas_object.compute_object_reference(),
+                &authority_state,
+            )
+        })
+        .collect();
+    let digests: Vec<TransactionDigest> = certificates.iter().map(|c| *c.digest()).collect();
+
+    let results = authority_state
+        .handle_confirmation_transactions(
+            certificates
+                .into_iter()
+                .map(ConfirmationTransaction::new)
+                .collect(),
+        )
+        .await;
+
+    assert_eq!(results.len(), digests.len());
+    for (result, digest) in results.iter().zip(digests.iter()) {
+        let effects = &result
+            .as_ref()
+            .unwrap()
+            .signed_effects
+            .as_ref()
+            .unwrap()
+            .effects;
+        assert_eq!(&effects.transaction_digest, digest);
+        assert!(matches!(effects.status, ExecutionStatus::Success { .. }));
+    }
+    for object in authority_state
+        .get_objects(&objects.iter().map(|o| o.id()).collect::<Vec<_>>())
+        .await
+        .unwrap()
+    {
+        assert_eq!(object.unwrap().owner, Owner::AddressOwner(recipient));
+    }
+
+    // Effects are committed in the order of the list.
+    let sequence: Vec<_> = authority_state
+        ._database
+        .executed_sequence
+        .iter()
+        .map(|(_, digest)| digest)
+        .collect();
+    assert_eq!(sequence, digests);
+}
+
+#[tokio::test]
+async fn test_execute_conflicting_certificates_in_order() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = SuiAddress::random_for_testing_only();
+    let object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let other_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let gas_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let authority_state = Arc::new(
+        init_state_with_objects(vec![
+            object.clone(),
+            other_object.clone(),
+            gas_object.clone(),
+        ])
+        .await,
+    );
+
+    // Both certificates use the same version of the gas object: only the first one of
+    // the list can execute.
+    let first = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    let second = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        other_object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+
+    let results = authority_state
+        .handle_confirmation_transactions(vec![
+            ConfirmationTransaction::new(first.clone()),
+            ConfirmationTransaction::new(second),
+        ])
+        .await;
+    let effects = results[0].as_ref().unwrap().signed_effects.clone();
+    assert!(effects.is_some());
+    assert!(results[1].is_err());
+
+    // Certificates already executed get the same answer.
+    let results = authority_state
+        .handle_confirmation_transactions(vec![ConfirmationTransaction::new(first)])
+        .await;
+    assert_eq!(results[0].as_ref().unwrap().signed_effects, effects);
+}
+
+#[tokio::test]
+async fn test_immutable_inputs_do_not_conflict() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = SuiAddress::random_for_testing_only();
+    let immutable_object = Object::immutable_with_id_for_testing(ObjectID::random());
+    let gas_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let authority_state =
+        Arc::new(init_state_with_objects(vec![immutable_object.clone(), gas_object.clone()]).await);
+
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        immutable_object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    assert_eq!(
+        authority_state.mutable_input_objects(&certificate).unwrap(),
+        vec![gas_object.id()]
+    );
+}
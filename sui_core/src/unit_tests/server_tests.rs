@@ -3,7 +3,9 @@
 
 use super::*;
 use crate::{
-    authority::authority_tests::init_state_with_object_id,
+    authority::authority_tests::{
+        init_certified_transfer_transaction, init_state_with_object_id, init_state_with_objects,
+    },
     authority_client::{
         AuthorityAPI, LocalAuthorityClient, LocalAuthorityClientFaultConfig, NetworkAuthorityClient,
     },
@@ -13,7 +15,8 @@ use std::sync::Arc;
 use sui_types::{
     base_types::{dbg_addr, dbg_object_id, TransactionDigest},
     batch::UpdateItem,
-    object::ObjectFormatOptions,
+    crypto::get_key_pair,
+    object::{Object, ObjectFormatOptions},
 };
 
 use crate::safe_client::SafeClient;
@@ -85,6 +88,57 @@ async fn test_simple_request() {
     client.handle_object_info_request(req).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_concurrent_certificates() {
+    let (sender, sender_key) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let objects: Vec<_> = (0..20)
+        .map(|_| Object::with_owner_for_testing(sender))
+        .collect();
+    let object_refs: Vec<_> = objects
+        .iter()
+        .map(|o| o.compute_object_reference())
+        .collect();
+    let authority_state = Arc::new(init_state_with_objects(objects).await);
+
+    let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+    let server = AuthorityServer::new(
+        "/ip4/127.0.0.1/tcp/0/http".parse().unwrap(),
+        authority_state.clone(),
+        consensus_address,
+        tx_consensus_listener,
+    );
+    let server_handle = server.spawn().await.unwrap();
+    let client = NetworkAuthorityClient::connect(server_handle.address())
+        .await
+        .unwrap();
+
+    // The certificates received together are executed as a batch, each with its own result.
+    let certificates: Vec<_> = object_refs
+        .chunks(2)
+        .map(|refs| {
+            init_certified_transfer_transaction(
+                sender,
+                &sender_key,
+                recipient,
+                refs[0],
+                refs[1],
+                &authority_state,
+            )
+        })
+        .collect();
+    let responses = futures::future::join_all(certificates.iter().map(|certificate| {
+        client.handle_confirmation_transaction(ConfirmationTransaction::new(certificate.clone()))
+    }))
+    .await;
+    for (certificate, response) in certificates.iter().zip(responses) {
+        let effects = response.unwrap().signed_effects.unwrap().effects;
+        assert_eq!(&effects.transaction_digest, certificate.digest());
+        assert!(effects.status.is_ok());
+    }
+}
+
 #[tokio::test]
 async fn test_subscription() {
     let sender = dbg_addr(1);
@@ -287,7 +341,7 @@ async fn test_subscription_safe_client() {
             state: state.clone(),
             fault_config: LocalAuthorityClientFaultConfig::default(),
         },
-        state.committee.clone(),
+        state.committee().as_ref().clone(),
         state.name,
     );
 
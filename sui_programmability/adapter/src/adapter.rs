@@ -65,12 +65,14 @@ pub fn execute<E: Debug, S: ResourceResolver<Error = E> + ModuleResolver<Error =
 ) -> SuiResult<()> {
     let objects = args
         .iter()
-        .filter_map(|arg| match arg {
-            CallArg::Pure(_) => None,
+        .flat_map(|arg| match arg {
+            CallArg::Pure(_) => vec![],
             CallArg::ImmOrOwnedObject((id, _, _)) | CallArg::SharedObject(id) => {
-                Some((*id, state_view.read_object(id)?))
+                vec![*id]
             }
+            CallArg::ObjVec(object_refs) => object_refs.iter().map(|(id, _, _)| *id).collect(),
         })
+        .filter_map(|id| Some((id, state_view.read_object(&id)?)))
         .collect();
     let module = vm.load_module(&module_id, state_view)?;
     let TypeCheckSuccess {
@@ -700,6 +702,69 @@ pub fn resolve_and_type_check(
                 }
                 CallArg::ImmOrOwnedObject(ref_) => InputObjectKind::ImmOrOwnedMoveObject(ref_),
                 CallArg::SharedObject(id) => InputObjectKind::SharedMoveObject(id),
+                CallArg::ObjVec(object_refs) => {
+                    // a vector of objects can only be passed by value, as `vector<T>`
+                    let inner_param_type = match param_type {
+                        SignatureToken::Vector(inner_t)
+                            if matches!(
+                                &**inner_t,
+                                SignatureToken::Struct(_)
+                                    | SignatureToken::StructInstantiation(_, _)
+                                    | SignatureToken::TypeParameter(_)
+                            ) =>
+                        {
+                            &**inner_t
+                        }
+                        t => {
+                            return Err(SuiError::TypeError {
+                                error: format!(
+                                    "Found object vector argument {}, but function expects {:?}",
+                                    idx, t
+                                ),
+                            })
+                        }
+                    };
+                    let mut object_vec_arg = uleb128_bytes(object_refs.len());
+                    for (id, _, _) in object_refs {
+                        let object = match objects.get(&id) {
+                            Some(object) => object.borrow(),
+                            None => {
+                                debug_assert!(
+                                    false,
+                                    "Object map not populated for arg {} with id {}",
+                                    idx, id
+                                );
+                                return Err(SuiError::ExecutionInvariantViolation);
+                            }
+                        };
+                        if !object.is_owned() {
+                            return Err(SuiError::TypeError {
+                                error: format!(
+                                    "Only owned object can be passed in an object vector, \
+                                    violation found in argument {}",
+                                    idx
+                                ),
+                            });
+                        }
+                        let move_object = match &object.data {
+                            Data::Move(m) => m,
+                            Data::Package(_) => {
+                                let error = format!(
+                                    "Found module in object vector argument {}, \
+                                    but function expects {:?}",
+                                    idx, param_type
+                                );
+                                return Err(SuiError::TypeError { error });
+                            }
+                        };
+                        type_check_struct(module, type_args, &move_object.type_, inner_param_type)?;
+                        object_data.insert(id, (object.owner, object.version()));
+                        by_value_objects.insert(id);
+                        object_type_map.insert(id, move_object.type_.module_id());
+                        object_vec_arg.extend_from_slice(move_object.contents());
+                    }
+                    return Ok(object_vec_arg);
+                }
             };
 
             let id = object_kind.object_id();
@@ -803,6 +868,20 @@ pub fn resolve_and_type_check(
     })
 }
 
+/// BCS encoding of the length of a vector, which prefixes the concatenation of its elements.
+fn uleb128_bytes(mut value: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
 /// Check that for each pair of a shared object and a descendant of it (through object ownership),
 /// at least one of the types of the shared object and the descendant must be defined in the
 /// same module as the function being called (somewhat similar to Rust's orphan rule).
@@ -8,6 +8,8 @@ module Sui::Coin {
     use Sui::TxContext::{Self, TxContext};
     use Std::Vector;
 
+    friend Sui::SuiSystem;
+
     /// A coin of type `T` worth `value`. Transferrable but not storable
     struct Coin<phantom T> has key {
         id: VersionedID,
@@ -87,7 +89,7 @@ module Sui::Coin {
         let i = 0;
         let len = Vector::length(&coins);
         while (i < len) {
-            let coin = Vector::remove(&mut coins, i);
+            let coin = Vector::pop_back(&mut coins);
             join(self, coin);
             i = i + 1
         };
@@ -158,6 +160,23 @@ module Sui::Coin {
         cap.total_supply = cap.total_supply - value
     }
 
+    /// Destroy the balance `b` and decrease the total supply in `cap`
+    /// accordingly.
+    public fun burn_balance<T>(b: Balance<T>, cap: &mut TreasuryCap<T>) {
+        let value = Balance::destroy<T>(b);
+        cap.total_supply = cap.total_supply - value
+    }
+
+    /// Can only be called by Sui::SuiSystem.
+    /// Record in `cap` the amounts of T `burned` and `minted` outside of
+    /// Move: the gas fees taken out of the gas coins when transactions are
+    /// charged, and the storage rebates refunded to them.
+    public(friend) fun record_supply_change<T>(
+        cap: &mut TreasuryCap<T>, burned: u64, minted: u64
+    ) {
+        cap.total_supply = cap.total_supply + minted - burned
+    }
+
     /// Return the total number of `T`'s in circulation
     public fun total_supply<T>(cap: &TreasuryCap<T>): u64 {
         cap.total_supply
@@ -0,0 +1,145 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module Sui::GasCharges {
+    use Std::Option::{Self, Option};
+    use Std::Vector;
+
+    friend Sui::SuiSystem;
+
+    #[test_only]
+    friend Sui::GasChargesTests;
+
+    /// The gas fees collected by the validators during an epoch. Each validator only knows
+    /// the fees of the certificates it executed, so at the end of the epoch every validator
+    /// reports its own tally, and the fees distributed are the median of the reports of a
+    /// quorum of the stake.
+    struct GasChargeReports has store {
+        reports: vector<Report>,
+    }
+
+    /// The gas fees collected during the epoch, as reported by a validator.
+    struct Report has store, drop {
+        reporter: address,
+        /// The voting power of the reporter.
+        stake: u64,
+        storage_charge: u64,
+        computation_charge: u64,
+        storage_rebate: u64,
+    }
+
+    public(friend) fun new(): GasChargeReports {
+        GasChargeReports { reports: Vector::empty() }
+    }
+
+    /// Called by `SuiSystem`, when an active validator reports the gas fees it collected.
+    /// Reporting again replaces the previous report of the validator.
+    public(friend) fun report(
+        self: &mut GasChargeReports,
+        reporter: address,
+        stake: u64,
+        storage_charge: u64,
+        computation_charge: u64,
+        storage_rebate: u64,
+    ) {
+        let index = find_report(&self.reports, reporter);
+        if (Option::is_some(&index)) {
+            Vector::remove(&mut self.reports, Option::extract(&mut index));
+        };
+        Vector::push_back(&mut self.reports, Report {
+            reporter,
+            stake,
+            storage_charge,
+            computation_charge,
+            storage_rebate,
+        });
+    }
+
+    /// The sum of the voting power of the validators that reported their fees.
+    public fun reported_stake(self: &GasChargeReports): u64 {
+        let stake = 0;
+        let i = 0;
+        while (i < Vector::length(&self.reports)) {
+            stake = stake + Vector::borrow(&self.reports, i).stake;
+            i = i + 1;
+        };
+        stake
+    }
+
+    /// Called by `SuiSystem` at the end of the epoch, once the validators holding a quorum
+    /// of the stake reported their fees. Returns the stake-weighted median of the storage
+    /// charges, computation charges and storage rebates reported, and clears the reports
+    /// for the next epoch. Since less than a third of the stake is faulty, each median lies
+    /// between the values reported by honest validators.
+    public(friend) fun take_agreed_charges(
+        self: &mut GasChargeReports,
+        quorum_stake_threshold: u64,
+    ): (u64, u64, u64) {
+        let reported_stake = reported_stake(self);
+        assert!(reported_stake >= quorum_stake_threshold, 0);
+        let storage_charges = Vector::empty();
+        let computation_charges = Vector::empty();
+        let storage_rebates = Vector::empty();
+        let stakes = Vector::empty();
+        while (!Vector::is_empty(&self.reports)) {
+            let Report {
+                reporter: _,
+                stake,
+                storage_charge,
+                computation_charge,
+                storage_rebate,
+            } = Vector::pop_back(&mut self.reports);
+            Vector::push_back(&mut storage_charges, storage_charge);
+            Vector::push_back(&mut computation_charges, computation_charge);
+            Vector::push_back(&mut storage_rebates, storage_rebate);
+            Vector::push_back(&mut stakes, stake);
+        };
+        (
+            weighted_median(&storage_charges, &stakes, reported_stake),
+            weighted_median(&computation_charges, &stakes, reported_stake),
+            weighted_median(&storage_rebates, &stakes, reported_stake),
+        )
+    }
+
+    /// The lowest of `values` such that the values lower or equal to it hold at least half
+    /// of `total_stake`.
+    fun weighted_median(values: &vector<u64>, stakes: &vector<u64>, total_stake: u64): u64 {
+        let length = Vector::length(values);
+        let median = Option::none();
+        let i = 0;
+        while (i < length) {
+            let value = *Vector::borrow(values, i);
+            if (Option::is_none(&median) || value < *Option::borrow(&median)) {
+                let stake = 0;
+                let j = 0;
+                while (j < length) {
+                    if (*Vector::borrow(values, j) <= value) {
+                        stake = stake + *Vector::borrow(stakes, j);
+                    };
+                    j = j + 1;
+                };
+                if (stake * 2 >= total_stake) {
+                    median = Option::some(value);
+                };
+            };
+            i = i + 1;
+        };
+        Option::destroy_some(median)
+    }
+
+    fun find_report(reports: &vector<Report>, reporter: address): Option<u64> {
+        let i = 0;
+        while (i < Vector::length(reports)) {
+            if (Vector::borrow(reports, i).reporter == reporter) {
+                return Option::some(i)
+            };
+            i = i + 1;
+        };
+        Option::none()
+    }
+
+    #[test_only]
+    public fun destroy_for_testing(self: GasChargeReports) {
+        let GasChargeReports { reports: _ } = self;
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module Sui::GasSchedule {
+    use Std::Option::{Self, Option};
+    use Std::Vector;
+
+    use Sui::ID::VersionedID;
+    #[test_only]
+    use Sui::ID;
+    #[test_only]
+    use Sui::TxContext::{Self, TxContext};
+
+    friend Sui::SuiSystem;
+
+    #[test_only]
+    friend Sui::GasScheduleTests;
+
+    /// The costs charged for executing transactions, shared by the validators.
+    /// It is created at genesis, and changed when the validators holding a quorum
+    /// of the stake vote for the same schedule.
+    /// The schedule is BCS-encoded, and only decoded by the validators: they load
+    /// the schedule in effect at the start of each epoch.
+    struct GasSchedule has key {
+        id: VersionedID,
+        /// The version of `schedule`, incremented each time a new schedule takes effect.
+        version: u64,
+        /// The cost schedule in effect.
+        schedule: vector<u8>,
+        /// The schedule voted for by the validators, which takes effect at `pending_epoch`.
+        pending_schedule: Option<vector<u8>>,
+        pending_epoch: u64,
+        /// The schedules proposed by the active validators in `proposal_epoch`.
+        /// Votes do not carry over to the next epoch, since the stake of the validators
+        /// changes at epoch boundaries.
+        proposals: vector<Proposal>,
+        proposal_epoch: u64,
+    }
+
+    /// A cost schedule, and the validators that voted for it.
+    struct Proposal has store, drop {
+        schedule: vector<u8>,
+        voters: vector<address>,
+        /// The sum of the voting power of `voters`.
+        stake: u64,
+    }
+
+    /// Called by `SuiSystem`, when an active validator votes for `schedule` in `epoch`.
+    /// A validator votes for one schedule at a time: voting again moves its vote.
+    /// Once the validators that voted for the same schedule hold a quorum of the stake,
+    /// the schedule takes effect at the next epoch, and all the proposals are dropped.
+    public(friend) fun vote(
+        self: &mut GasSchedule,
+        schedule: vector<u8>,
+        voter: address,
+        voting_power: u64,
+        quorum_stake_threshold: u64,
+        epoch: u64,
+    ) {
+        apply_pending_schedule(self, epoch);
+        if (self.proposal_epoch != epoch) {
+            self.proposals = Vector::empty();
+            self.proposal_epoch = epoch;
+        };
+        remove_vote(&mut self.proposals, voter, voting_power);
+
+        let index = find_proposal(&self.proposals, &schedule);
+        if (Option::is_none(&index)) {
+            Vector::push_back(&mut self.proposals, Proposal {
+                schedule,
+                voters: Vector::empty(),
+                stake: 0,
+            });
+            Option::fill(&mut index, Vector::length(&self.proposals) - 1);
+        };
+        let index = Option::extract(&mut index);
+        let proposal = Vector::borrow_mut(&mut self.proposals, index);
+        Vector::push_back(&mut proposal.voters, voter);
+        proposal.stake = proposal.stake + voting_power;
+        let reached_quorum = proposal.stake >= quorum_stake_threshold;
+
+        if (reached_quorum) {
+            let Proposal { schedule, voters: _, stake: _ } = Vector::remove(&mut self.proposals, index);
+            self.pending_schedule = Option::some(schedule);
+            self.pending_epoch = epoch + 1;
+            self.proposals = Vector::empty();
+        }
+    }
+
+    /// Return the version of the schedule in effect.
+    public fun version(self: &GasSchedule): u64 {
+        self.version
+    }
+
+    /// Return the BCS-encoded cost schedule in effect.
+    public fun schedule(self: &GasSchedule): &vector<u8> {
+        &self.schedule
+    }
+
+    /// Return the schedule voted for by the validators, if it has not taken effect yet.
+    public fun pending_schedule(self: &GasSchedule): &Option<vector<u8>> {
+        &self.pending_schedule
+    }
+
+    /// Return the voting power of the validators that voted for `schedule` in the current epoch.
+    public fun proposal_stake(self: &GasSchedule, schedule: &vector<u8>): u64 {
+        let index = find_proposal(&self.proposals, schedule);
+        if (Option::is_some(&index)) {
+            Vector::borrow(&self.proposals, Option::extract(&mut index)).stake
+        } else {
+            0
+        }
+    }
+
+    /// The schedule voted for in a previous epoch is only applied lazily, the next time
+    /// the gas schedule is changed. Validators do the same when they read the schedule.
+    fun apply_pending_schedule(self: &mut GasSchedule, epoch: u64) {
+        if (Option::is_some(&self.pending_schedule) && epoch >= self.pending_epoch) {
+            self.schedule = Option::extract(&mut self.pending_schedule);
+            self.version = self.version + 1;
+        }
+    }
+
+    /// Remove the vote of `voter` from the proposal it voted for, if any.
+    fun remove_vote(proposals: &mut vector<Proposal>, voter: address, voting_power: u64) {
+        let length = Vector::length(proposals);
+        let i = 0;
+        while (i < length) {
+            let proposal = Vector::borrow_mut(proposals, i);
+            let (found, voter_index) = Vector::index_of(&proposal.voters, &voter);
+            if (found) {
+                Vector::remove(&mut proposal.voters, voter_index);
+                proposal.stake = proposal.stake - voting_power;
+                if (Vector::is_empty(&proposal.voters)) {
+                    Vector::remove(proposals, i);
+                };
+                return
+            };
+            i = i + 1;
+        };
+    }
+
+    fun find_proposal(proposals: &vector<Proposal>, schedule: &vector<u8>): Option<u64> {
+        let length = Vector::length(proposals);
+        let i = 0;
+        while (i < length) {
+            if (&Vector::borrow(proposals, i).schedule == schedule) {
+                return Option::some(i)
+            };
+            i = i + 1;
+        };
+        Option::none()
+    }
+
+    #[test_only]
+    public fun new_for_testing(schedule: vector<u8>, ctx: &mut TxContext): GasSchedule {
+        GasSchedule {
+            id: TxContext::new_id(ctx),
+            version: 0,
+            schedule,
+            pending_schedule: Option::none(),
+            pending_epoch: 0,
+            proposals: Vector::empty(),
+            proposal_epoch: 0,
+        }
+    }
+
+    #[test_only]
+    public fun destroy_for_testing(self: GasSchedule) {
+        let GasSchedule {
+            id,
+            version: _,
+            schedule: _,
+            pending_schedule: _,
+            pending_epoch: _,
+            proposals: _,
+            proposal_epoch: _,
+        } = self;
+        ID::delete(id);
+    }
+}
@@ -6,15 +6,14 @@ module Sui::SuiSystem {
     use Sui::Coin::{Self, Coin, TreasuryCap};
     use Sui::Delegation::{Self, Delegation};
     use Sui::EpochRewardRecord::{Self, EpochRewardRecord};
+    use Sui::GasCharges::{Self, GasChargeReports};
+    use Sui::GasSchedule::{Self, GasSchedule};
     use Sui::ID::VersionedID;
     use Sui::SUI::SUI;
-    use Sui::Transfer;
     use Sui::TxContext::{Self, TxContext};
     use Sui::Validator::{Self, Validator};
     use Sui::ValidatorSet::{Self, ValidatorSet};
 
-    friend Sui::Genesis;
-
     /// A list of system config parameters.
     // TDOO: We will likely add more, a few potential ones:
     // - the change in stake across epochs can be at most +/- x%
@@ -32,6 +31,8 @@ module Sui::SuiSystem {
     }
 
     /// The top-level object containing all information of the Sui system.
+    /// It is created at genesis as a shared object, with the validators of the genesis
+    /// config as the committee of the first epoch.
     struct SuiSystemState has key {
         id: VersionedID,
         /// The current epoch ID, starting from 0.
@@ -47,36 +48,14 @@ module Sui::SuiSystem {
         /// The delegation reward pool. All delegation reward goes into this.
         /// Delegation reward claims withdraw from this.
         delegation_reward: Balance<SUI>,
-    }
-
-    // ==== functions that can only be called by Genesis ====
-
-    /// Create a new SuiSystemState object and make it shared.
-    /// This function will be called only once in Genesis.
-    public(friend) fun create(
-        validators: vector<Validator>,
-        treasury_cap: TreasuryCap<SUI>,
-        storage_fund: Balance<SUI>,
-        max_validator_candidate_count: u64,
-        min_validator_stake: u64,
-        max_validator_stake: u64,
-        ctx: &mut TxContext,
-    ) {
-        assert!(min_validator_stake < max_validator_stake, 0);
-        let state = SuiSystemState {
-            id: TxContext::new_id(ctx),
-            epoch: 0,
-            validators: ValidatorSet::new(validators),
-            treasury_cap,
-            storage_fund,
-            parameters: SystemParameters {
-                min_validator_stake,
-                max_validator_stake,
-                max_validator_candidate_count,
-            },
-            delegation_reward: Balance::zero(),
-        };
-        Transfer::share_object(state);
+        /// The minimum gas price of the transactions signed in the current epoch,
+        /// derived from the gas prices announced by the validators.
+        reference_gas_price: u64,
+        /// The gas fees collected during the current epoch, as reported by the validators.
+        gas_charge_reports: GasChargeReports,
+        /// The gas fees collected at the end of each epoch, which pay the rewards of the
+        /// validators and the storage fund.
+        gas_pool: Balance<SUI>,
     }
 
     // ==== entry functions ====
@@ -88,8 +67,10 @@ module Sui::SuiSystem {
     // someone to become a validator?
     public(script) fun request_add_validator(
         self: &mut SuiSystemState,
+        pubkey_bytes: vector<u8>,
         name: vector<u8>,
         net_address: vector<u8>,
+        gas_price: u64,
         stake: Coin<SUI>,
         ctx: &mut TxContext,
     ) {
@@ -105,8 +86,10 @@ module Sui::SuiSystem {
         );
         let validator = Validator::new(
             TxContext::sender(ctx),
+            pubkey_bytes,
             name,
             net_address,
+            gas_price,
             Coin::into_balance(stake)
         );
 
@@ -160,6 +143,61 @@ module Sui::SuiSystem {
         )
     }
 
+    /// An active validator can announce the gas price it is willing to process transactions at.
+    /// It is taken into account when the reference gas price is derived at the end of the epoch.
+    public(script) fun request_set_gas_price(
+        self: &mut SuiSystemState,
+        new_gas_price: u64,
+        ctx: &mut TxContext,
+    ) {
+        ValidatorSet::request_set_gas_price(
+            &mut self.validators,
+            new_gas_price,
+            ctx,
+        )
+    }
+
+    /// An active validator can vote for a new BCS-encoded cost schedule. Once the validators
+    /// that voted for the same schedule hold a quorum of the stake, it takes effect at the
+    /// next epoch.
+    public(script) fun request_set_gas_schedule(
+        self: &mut SuiSystemState,
+        gas_schedule: &mut GasSchedule,
+        schedule: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        let voter = TxContext::sender(ctx);
+        GasSchedule::vote(
+            gas_schedule,
+            schedule,
+            voter,
+            ValidatorSet::voting_power(&self.validators, voter),
+            ValidatorSet::quorum_stake_threshold(&self.validators),
+            self.epoch,
+        )
+    }
+
+    /// At the end of the epoch, each active validator reports the gas fees collected by the
+    /// certificates it executed. The epoch can only advance once a quorum of the stake
+    /// reported its fees.
+    public(script) fun report_gas_charges(
+        self: &mut SuiSystemState,
+        storage_charge: u64,
+        computation_charge: u64,
+        storage_rebate: u64,
+        ctx: &mut TxContext,
+    ) {
+        let reporter = TxContext::sender(ctx);
+        GasCharges::report(
+            &mut self.gas_charge_reports,
+            reporter,
+            ValidatorSet::voting_power(&self.validators, reporter),
+            storage_charge,
+            computation_charge,
+            storage_rebate,
+        )
+    }
+
     public(script) fun request_add_delegation(
         self: &mut SuiSystemState,
         delegate_stake: Coin<SUI>,
@@ -208,23 +246,36 @@ module Sui::SuiSystem {
     }
 
     /// This function should be called at the end of an epoch, and advances the system to the next epoch.
+    /// The charges and rebates are the gas fees collected during the epoch, which were taken out of
+    /// (or refunded to) the gas coins of the transactions. They are agreed on from the reports of
+    /// the validators.
     /// It does the following things:
-    /// 1. Add storage charge to the storage fund.
-    /// 2. Distribute computation charge to validator stake and delegation stake.
-    /// 3. Create reward information records for each validator in this epoch.
-    /// 4. Update all validators.
+    /// 1. Mint the gas fees into the gas pool through the treasury cap.
+    /// 2. Add storage charge to the storage fund, and take the storage rebates out of it.
+    /// 3. Distribute computation charge to validator stake and delegation stake.
+    /// 4. Create reward information records for each validator in this epoch.
+    /// 5. Update all validators.
+    /// 6. Derive the reference gas price of the new epoch.
     public(script) fun advance_epoch(
         self: &mut SuiSystemState,
         new_epoch: u64,
-        storage_charge: u64,
-        computation_charge: u64,
         ctx: &mut TxContext,
     ) {
         // Only an active validator can make a call to this function.
         assert!(ValidatorSet::is_active_validator(&self.validators, TxContext::sender(ctx)), 0);
 
-        let storage_reward = Balance::create_with_value(storage_charge);
-        let computation_reward = Balance::create_with_value(computation_charge);
+        let (storage_charge, computation_charge, storage_rebate) = GasCharges::take_agreed_charges(
+            &mut self.gas_charge_reports,
+            ValidatorSet::quorum_stake_threshold(&self.validators),
+        );
+        // The gas fees were burned when the transactions were charged, and the storage
+        // rebates minted into their gas coins: the treasury cap records both, and mints the
+        // fees back into the gas pool.
+        let gas_charges = storage_charge + computation_charge;
+        Coin::record_supply_change(&mut self.treasury_cap, gas_charges, storage_rebate);
+        Balance::join(&mut self.gas_pool, Coin::mint_balance(gas_charges, &mut self.treasury_cap));
+        let storage_reward = Balance::split(&mut self.gas_pool, storage_charge);
+        let computation_reward = Balance::split(&mut self.gas_pool, computation_charge);
 
         let delegation_stake = ValidatorSet::delegation_stake(&self.validators);
         let validator_stake = ValidatorSet::validator_stake(&self.validators);
@@ -236,6 +287,12 @@ module Sui::SuiSystem {
         Balance::join(&mut self.storage_fund, storage_reward);
         Balance::join(&mut self.delegation_reward, delegator_reward);
 
+        // The storage fund cannot refund more than it holds, which could only happen if the
+        // rebated objects were created before the fund started collecting storage charges.
+        let storage_fund_value = Balance::value(&self.storage_fund);
+        let storage_rebate = if (storage_rebate > storage_fund_value) { storage_fund_value } else { storage_rebate };
+        Coin::burn_balance(Balance::split(&mut self.storage_fund, storage_rebate), &mut self.treasury_cap);
+
         ValidatorSet::create_epoch_records(
             &self.validators,
             self.epoch,
@@ -252,9 +309,10 @@ module Sui::SuiSystem {
             &mut computation_reward,
             ctx,
         );
+        self.reference_gas_price = ValidatorSet::derive_reference_gas_price(&self.validators);
         // Because of precision issues with integer divisions, we expect that there will be some
-        // remaining balance in `computation_reward`. All of these go to the storage fund.
-        Balance::join(&mut self.storage_fund, computation_reward)
+        // remaining balance in `computation_reward`. It stays in the gas pool for the next epoch.
+        Balance::join(&mut self.gas_pool, computation_reward)
     }
 
     /// Return the current epoch number. Useful for applications that need a coarse-grained concept of time,
@@ -263,6 +321,11 @@ module Sui::SuiSystem {
         self.epoch
     }
 
+    /// Return the minimum gas price of the transactions signed in the current epoch.
+    public fun reference_gas_price(self: &SuiSystemState): u64 {
+        self.reference_gas_price
+    }
+
     #[test_only]
     public fun set_epoch_for_testing(self: &mut SuiSystemState, epoch_num: u64) {
         self.epoch = epoch_num
@@ -12,7 +12,6 @@ module Sui::Validator {
     use Sui::Transfer;
     use Sui::TxContext::TxContext;
 
-    friend Sui::Genesis;
     friend Sui::SuiSystem;
     friend Sui::ValidatorSet;
 
@@ -25,10 +24,16 @@ module Sui::Validator {
         /// The Sui Address of the validator. This is the sender that created the Validator object,
         /// and also the address to send validator/coins to during withdraws.
         sui_address: address,
+        /// The public key bytes corresponding to the private key that the validator
+        /// uses to establish TLS connections and sign transactions, i.e. its authority name.
+        pubkey_bytes: vector<u8>,
         /// A unique human-readable name of this validator.
         name: String,
         /// The network address of the validator (could also contain extra info such as port, DNS and etc.).
         net_address: vector<u8>,
+        /// The gas price this validator is willing to process transactions at. The reference
+        /// gas price of each epoch is derived from the prices announced by the validators.
+        gas_price: u64,
         /// The current active stake. This will not change during an epoch. It can only
         /// be updated at the end of epoch.
         stake: Balance<SUI>,
@@ -54,18 +59,23 @@ module Sui::Validator {
 
     public(friend) fun new(
         sui_address: address,
+        pubkey_bytes: vector<u8>,
         name: vector<u8>,
         net_address: vector<u8>,
+        gas_price: u64,
         stake: Balance<SUI>,
     ): Validator {
         assert!(
             Vector::length(&net_address) <= 100 || Vector::length(&name) <= 50,
             0
         );
+        assert!(gas_price > 0, 0);
         Validator {
             sui_address,
+            pubkey_bytes,
             name: ASCII::string(name),
             net_address,
+            gas_price,
             stake,
             delegation: 0,
             pending_stake: Option::none(),
@@ -81,8 +91,10 @@ module Sui::Validator {
     public(friend) fun destroy(self: Validator, ctx: &mut TxContext) {
         let Validator {
             sui_address,
+            pubkey_bytes: _,
             name: _,
             net_address: _,
+            gas_price: _,
             stake,
             delegation: _,
             pending_stake,
@@ -174,6 +186,13 @@ module Sui::Validator {
         self.pending_delegator_withdraw_count = self.pending_delegator_withdraw_count + 1;
     }
 
+    /// Announce a new gas price, which is taken into account in the reference gas price
+    /// from the next epoch on.
+    public(friend) fun request_set_gas_price(self: &mut Validator, new_gas_price: u64) {
+        assert!(new_gas_price > 0, 0);
+        self.gas_price = new_gas_price;
+    }
+
     public(friend) fun deposit_reward(self: &mut Validator, reward: Balance<SUI>) {
         Balance::join(&mut self.stake, reward)
     }
@@ -183,6 +202,14 @@ module Sui::Validator {
         self.sui_address
     }
 
+    public fun pubkey_bytes(self: &Validator): &vector<u8> {
+        &self.pubkey_bytes
+    }
+
+    public fun gas_price(self: &Validator): u64 {
+        self.gas_price
+    }
+
     public fun stake_amount(self: &Validator): u64 {
         Balance::value(&self.stake)
     }
@@ -119,6 +119,18 @@ module Sui::ValidatorSet {
         Validator::request_withdraw_stake(validator, withdraw_amount, min_validator_stake);
     }
 
+    /// Called by `SuiSystem`, to announce the gas price of an active validator.
+    /// The new price only affects the reference gas price of the next epoch.
+    public(friend) fun request_set_gas_price(
+        self: &mut ValidatorSet,
+        new_gas_price: u64,
+        ctx: &TxContext,
+    ) {
+        let validator_address = TxContext::sender(ctx);
+        let validator = get_validator_mut(&mut self.active_validators, validator_address);
+        Validator::request_set_gas_price(validator, new_gas_price);
+    }
+
     public(friend) fun is_active_validator(
         self: &ValidatorSet,
         validator_address: address,
@@ -200,6 +212,48 @@ module Sui::ValidatorSet {
         self.quorum_stake_threshold = quorum_stake_threshold;
     }
 
+    /// Derive the reference gas price of the epoch: the lowest gas price announced by an active
+    /// validator, such that the validators willing to process transactions at this price (i.e.
+    /// that announced the same or a lower price) hold a quorum of the stake.
+    public(friend) fun derive_reference_gas_price(self: &ValidatorSet): u64 {
+        let length = Vector::length(&self.active_validators);
+        let reference_gas_price = 0;
+        let i = 0;
+        while (i < length) {
+            let gas_price = Validator::gas_price(Vector::borrow(&self.active_validators, i));
+            if (reference_gas_price == 0 || gas_price < reference_gas_price) {
+                let stake = 0;
+                let j = 0;
+                while (j < length) {
+                    let v = Vector::borrow(&self.active_validators, j);
+                    if (Validator::gas_price(v) <= gas_price) {
+                        stake = stake + Validator::stake_amount(v) + Validator::delegate_amount(v);
+                    };
+                    j = j + 1;
+                };
+                if (stake >= self.quorum_stake_threshold) {
+                    reference_gas_price = gas_price;
+                };
+            };
+            i = i + 1;
+        };
+        reference_gas_price
+    }
+
+    /// The voting power of an active validator in the current epoch: its stake and the
+    /// stake delegated to it.
+    public(friend) fun voting_power(self: &ValidatorSet, validator_address: address): u64 {
+        let validator_index_opt = find_validator(&self.active_validators, validator_address);
+        assert!(Option::is_some(&validator_index_opt), 0);
+        let validator_index = Option::extract(&mut validator_index_opt);
+        let validator = Vector::borrow(&self.active_validators, validator_index);
+        Validator::stake_amount(validator) + Validator::delegate_amount(validator)
+    }
+
+    public fun quorum_stake_threshold(self: &ValidatorSet): u64 {
+        self.quorum_stake_threshold
+    }
+
     public fun validator_stake(self: &ValidatorSet): u64 {
         self.validator_stake
     }
@@ -7,6 +7,9 @@ module Sui::TestCoin {
     use Sui::Coin;
     use Sui::Balance;
     use Sui::SUI::SUI;
+    use Std::Vector;
+
+    struct TEST_COIN has drop {}
 
     #[test]
     fun type_morphing() {
@@ -33,4 +36,37 @@ module Sui::TestCoin {
         let coin = Coin::from_balance(balance, ctx(test));
         Coin::keep(coin, ctx(test));
     }
+
+    #[test]
+    fun join_vec() {
+        let test = &mut TestScenario::begin(&@0x1);
+
+        let coin = Coin::mint_for_testing<SUI>(10, ctx(test));
+        let coins = Vector::empty();
+        Vector::push_back(&mut coins, Coin::mint_for_testing<SUI>(20, ctx(test)));
+        Vector::push_back(&mut coins, Coin::mint_for_testing<SUI>(30, ctx(test)));
+        Vector::push_back(&mut coins, Coin::mint_for_testing<SUI>(40, ctx(test)));
+        Coin::join_vec(&mut coin, coins);
+
+        assert!(Coin::value(&coin) == 100, 0);
+        Coin::keep(coin, ctx(test));
+    }
+
+    #[test]
+    fun mint_and_burn_balance() {
+        let test = &mut TestScenario::begin(&@0x1);
+
+        let cap = Coin::create_currency(TEST_COIN {}, ctx(test));
+        let balance = Coin::mint_balance(100, &mut cap);
+        assert!(Coin::total_supply(&cap) == 100, 0);
+
+        let burned = Balance::split(&mut balance, 40);
+        Coin::burn_balance(burned, &mut cap);
+        assert!(Coin::total_supply(&cap) == 60, 0);
+        assert!(Balance::value(&balance) == 60, 0);
+
+        let coin = Coin::from_balance(balance, ctx(test));
+        Coin::keep(coin, ctx(test));
+        Coin::transfer_cap(cap, @0x1);
+    }
 }
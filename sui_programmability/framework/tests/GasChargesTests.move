@@ -0,0 +1,44 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module Sui::GasChargesTests {
+    use Sui::GasCharges;
+
+    #[test]
+    fun test_agreed_charges() {
+        let reports = GasCharges::new();
+
+        // Validators 1 to 4 hold a stake of 100, 200, 300 and 400, and the quorum is 667.
+        GasCharges::report(&mut reports, @0x1, 100, 1, 10, 100);
+        GasCharges::report(&mut reports, @0x2, 200, 2, 20, 200);
+        GasCharges::report(&mut reports, @0x4, 400, 4, 40, 400);
+        assert!(GasCharges::reported_stake(&reports) == 700, 0);
+
+        // Reporting again replaces the previous report of the validator.
+        GasCharges::report(&mut reports, @0x1, 100, 9, 90, 900);
+        assert!(GasCharges::reported_stake(&reports) == 700, 0);
+
+        // The median lies where the reports hold half of the reported stake.
+        let (storage_charge, computation_charge, storage_rebate) =
+            GasCharges::take_agreed_charges(&mut reports, 667);
+        assert!(storage_charge == 4, 0);
+        assert!(computation_charge == 40, 0);
+        assert!(storage_rebate == 400, 0);
+
+        // The reports do not carry over to the next epoch.
+        assert!(GasCharges::reported_stake(&reports) == 0, 0);
+
+        GasCharges::destroy_for_testing(reports);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_agreed_charges_without_quorum() {
+        let reports = GasCharges::new();
+        GasCharges::report(&mut reports, @0x3, 300, 3, 30, 300);
+        GasCharges::report(&mut reports, @0x4, 400, 4, 40, 400);
+        GasCharges::take_agreed_charges(&mut reports, 701);
+        GasCharges::destroy_for_testing(reports);
+    }
+}
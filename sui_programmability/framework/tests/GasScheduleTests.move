@@ -0,0 +1,47 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module Sui::GasScheduleTests {
+    use Std::Option;
+
+    use Sui::GasSchedule;
+    use Sui::TxContext;
+
+    #[test]
+    fun test_gas_schedule_vote() {
+        let ctx = TxContext::dummy();
+        let gas_schedule = GasSchedule::new_for_testing(x"01", &mut ctx);
+
+        // Validators 1 to 4 hold a stake of 100, 200, 300 and 400, and the quorum is 667.
+        GasSchedule::vote(&mut gas_schedule, x"02", @0x1, 100, 667, 0);
+        GasSchedule::vote(&mut gas_schedule, x"02", @0x4, 400, 667, 0);
+        GasSchedule::vote(&mut gas_schedule, x"03", @0x2, 200, 667, 0);
+        assert!(GasSchedule::proposal_stake(&gas_schedule, &x"02") == 500, 0);
+        assert!(GasSchedule::proposal_stake(&gas_schedule, &x"03") == 200, 0);
+
+        // Voting again moves the vote of validator 1.
+        GasSchedule::vote(&mut gas_schedule, x"03", @0x1, 100, 667, 0);
+        assert!(GasSchedule::proposal_stake(&gas_schedule, &x"02") == 400, 0);
+        assert!(GasSchedule::proposal_stake(&gas_schedule, &x"03") == 300, 0);
+
+        // Votes do not carry over to the next epoch.
+        GasSchedule::vote(&mut gas_schedule, x"02", @0x3, 300, 667, 1);
+        assert!(GasSchedule::proposal_stake(&gas_schedule, &x"02") == 300, 0);
+        assert!(GasSchedule::proposal_stake(&gas_schedule, &x"03") == 0, 0);
+
+        // Once a quorum is reached, the schedule only takes effect at the next epoch.
+        GasSchedule::vote(&mut gas_schedule, x"02", @0x4, 400, 667, 1);
+        assert!(GasSchedule::proposal_stake(&gas_schedule, &x"02") == 0, 0);
+        assert!(GasSchedule::pending_schedule(&gas_schedule) == &Option::some(x"02"), 0);
+        assert!(GasSchedule::schedule(&gas_schedule) == &x"01", 0);
+        assert!(GasSchedule::version(&gas_schedule) == 0, 0);
+
+        GasSchedule::vote(&mut gas_schedule, x"03", @0x1, 100, 667, 2);
+        assert!(Option::is_none(GasSchedule::pending_schedule(&gas_schedule)), 0);
+        assert!(GasSchedule::schedule(&gas_schedule) == &x"02", 0);
+        assert!(GasSchedule::version(&gas_schedule) == 1, 0);
+
+        GasSchedule::destroy_for_testing(gas_schedule);
+    }
+}
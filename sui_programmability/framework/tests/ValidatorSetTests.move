@@ -80,6 +80,26 @@ module Sui::ValidatorSetTests {
         Balance::destroy_zero(reward);
     }
 
+    #[test]
+    public(script) fun test_reference_gas_price() {
+        // Create 4 validators, with stake 100, 200, 300, 400 and gas price 4, 3, 2, 1.
+        let (ctx1, validator1) = create_validator(@0x1, 1);
+        let (_ctx2, validator2) = create_validator(@0x2, 2);
+        let (_ctx3, validator3) = create_validator(@0x3, 3);
+        let (ctx4, validator4) = create_validator(@0x4, 4);
+        let validator_set = ValidatorSet::new(vector[validator1, validator2, validator3, validator4]);
+        // Validators 3 and 4 announce a price of at most 2, with 700 out of 1000 stake.
+        assert!(ValidatorSet::derive_reference_gas_price(&validator_set) == 2, 0);
+
+        // The validator with the most stake raises its price. The other ones hold
+        // only 600 out of 1000 stake, so its new price becomes the reference.
+        ValidatorSet::request_set_gas_price(&mut validator_set, 5, &ctx4);
+        assert!(ValidatorSet::derive_reference_gas_price(&validator_set) == 5, 0);
+
+        ValidatorSet::destroy_for_testing(validator_set, &mut ctx1);
+    }
+
+    /// The validator with the most stake announces the lowest gas price.
     fun create_validator(addr: address, hint: u8): (TxContext, Validator) {
         let stake_value = (hint as u64) * 100;
         let ctx = TxContext::new_from_address(addr, hint);
@@ -89,6 +109,8 @@ module Sui::ValidatorSetTests {
             addr,
             vector[hint],
             vector[hint],
+            vector[hint],
+            5 - (hint as u64),
             init_stake,
         );
         (ctx, validator)
@@ -18,8 +18,10 @@ module Sui::ValidatorTests {
             let init_stake = Coin::into_balance(Coin::mint_for_testing(10, ctx));
             let validator = Validator::new(
                 sender,
+                x"FF",
                 b"Validator1",
                 x"FFFF",
+                1,
                 init_stake,
             );
             assert!(Validator::stake_amount(&validator) == 10, 0);
@@ -45,8 +47,10 @@ module Sui::ValidatorTests {
         let init_stake = Coin::into_balance(Coin::mint_for_testing(10, ctx));
         let validator = Validator::new(
             sender,
+            x"FF",
             b"Validator1",
             x"FFFF",
+            1,
             init_stake,
         );
 
@@ -431,7 +431,8 @@ impl<'a> SuiTestAdapter<'a> {
         transaction: Transaction,
         gas_budget: u64,
     ) -> anyhow::Result<TxnSummary> {
-        let gas_status = gas::start_gas_metering(gas_budget, 1, 1).unwrap();
+        let gas_status =
+            gas::start_gas_metering(gas_budget, 1, 1, &gas::INITIAL_SUI_COST_SCHEDULE).unwrap();
         let transaction_digest = TransactionDigest::new(self.rng.gen());
         let objects_by_kind = transaction
             .data
@@ -4,6 +4,7 @@
 use crate::base_types::{AuthorityName, TransactionDigest};
 use crate::crypto::{sha3_hash, AuthoritySignature, BcsSignable};
 use crate::error::SuiError;
+use crate::state_commitment::StateDigest;
 use serde::{Deserialize, Serialize};
 
 pub type TxSequenceNumber = u64;
@@ -38,6 +39,10 @@ pub struct AuthorityBatch {
 
     /// The digest of all transactions digests in this batch
     pub transactions_digest: [u8; 32],
+
+    /// The root of the state commitment over the live objects after the execution of
+    /// the transactions of this batch, if the authority computed it.
+    pub state_root: Option<StateDigest>,
 }
 
 impl BcsSignable for AuthorityBatch {}
@@ -59,6 +64,7 @@ impl AuthorityBatch {
             size: 0,
             previous_digest: None,
             transactions_digest,
+            state_root: None,
         }
     }
 
@@ -87,6 +93,7 @@ impl AuthorityBatch {
             size: transactions.len() as u64,
             previous_digest: Some(previous_batch.digest()),
             transactions_digest,
+            state_root: None,
         })
     }
 }
@@ -18,6 +18,7 @@ use crate::{
 pub const COIN_MODULE_NAME: &IdentStr = ident_str!("Coin");
 pub const COIN_STRUCT_NAME: &IdentStr = COIN_MODULE_NAME;
 pub const COIN_JOIN_FUNC_NAME: &IdentStr = ident_str!("join_");
+pub const COIN_JOIN_VEC_FUNC_NAME: &IdentStr = ident_str!("join_vec_");
 pub const COIN_SPLIT_VEC_FUNC_NAME: &IdentStr = ident_str!("split_vec");
 
 // Rust version of the Move Sui::Coin::Coin type
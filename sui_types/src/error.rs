@@ -79,6 +79,12 @@ pub enum SuiError {
     // Certificate verification
     #[error("Signature or certificate from wrong epoch, expected {expected_epoch}")]
     WrongEpoch { expected_epoch: EpochId },
+    #[error("Invalid committee: {error}")]
+    InvalidCommittee { error: String },
+    #[error("Validator is halted at the end of epoch {epoch}, and only accepts the epoch change")]
+    ValidatorHaltedAtEpochEnd { epoch: EpochId },
+    #[error("Invalid epoch change: {error}")]
+    InvalidEpochChange { error: String },
     #[error("Signatures in a certificate must form a quorum")]
     CertificateRequiresQuorum,
     #[error(
@@ -93,6 +99,11 @@ pub enum SuiError {
     ConflictingTransaction {
         pending_transaction: TransactionDigest,
     },
+    #[error("Object {obj_ref:?} is locked on conflicting transactions, none of which can be certified: {pending_transactions:?}")]
+    ObjectLockConflict {
+        obj_ref: ObjectRef,
+        pending_transactions: Vec<TransactionDigest>,
+    },
     #[error("Transaction was processed but no signature was produced by authority")]
     ErrorWhileProcessingTransaction,
     #[error("Transaction transaction processing failed: {err}")]
@@ -177,12 +188,23 @@ pub enum SuiError {
     InvalidSequenceRangeError,
     #[error("No batches matched the range requested.")]
     NoBatchesFoundError,
+    #[error("The range requested has been pruned, the lowest retained sequence number is {lowest_retained}.")]
+    PrunedSequenceRangeError { lowest_retained: u64 },
+    #[error("The effects of transaction {digest:?} have been pruned, their digest was {effects_digest:?}.")]
+    TransactionEffectsPruned {
+        digest: TransactionDigest,
+        effects_digest: [u8; 32],
+    },
     #[error("The channel to repond to the client returned an error.")]
     CannotSendClientMessageError,
     #[error("Subscription service had to drop {0} items")]
     SubscriptionItemsDroppedError(u64),
     #[error("Subscription service closed.")]
     SubscriptionServiceClosed,
+    #[error("State snapshot error: {error}")]
+    StateSnapshotError { error: String },
+    #[error("Invalid state proof for object {object_id:?}")]
+    InvalidStateProof { object_id: ObjectID },
 
     // Move module publishing related errors
     #[error("Failed to load the Move module, reason: {error:?}.")]
@@ -233,6 +255,36 @@ pub enum SuiError {
     GasBudgetTooHigh { error: String },
     #[error("Insufficient gas: {error:?}.")]
     InsufficientGas { error: String },
+    #[error("Gas price {gas_price} is under the reference gas price {reference_gas_price} of the epoch.")]
+    GasPriceUnderReference {
+        gas_price: u64,
+        reference_gas_price: u64,
+    },
+    #[error(
+        "Gas coin {gas_object_id} has balance {balance}, not enough to cover the estimated gas budget {gas_budget}."
+    )]
+    InsufficientGasForEstimatedBudget {
+        gas_object_id: ObjectID,
+        balance: u64,
+        gas_budget: u64,
+    },
+    #[error(
+        "No gas coin of {address} covers the gas budget {gas_budget}, but merging its coins would."
+    )]
+    GasCoinMergeRequired {
+        address: SuiAddress,
+        gas_budget: u64,
+    },
+    #[error(
+        "The gas coins of {address} hold {total_balance} in total, not enough to cover the gas budget {gas_budget}."
+    )]
+    InsufficientGasCoins {
+        address: SuiAddress,
+        gas_budget: u64,
+        total_balance: u64,
+    },
+    #[error("Invalid cost schedule: {error:?}.")]
+    InvalidCostSchedule { error: String },
 
     // Internal state errors
     #[error("Attempt to update state of TxContext from a different instance than original.")]
@@ -274,6 +326,13 @@ pub enum SuiError {
         tx_digest: TransactionDigest,
         error: Box<SuiError>,
     },
+    #[error(
+        "Re-executing transaction {digest:?} produced different effects than those signed by authority {authority:?}."
+    )]
+    ReplicaEffectsMismatch {
+        authority: AuthorityName,
+        digest: TransactionDigest,
+    },
     #[error("Storage error")]
     StorageError(
         #[from]
@@ -1,7 +1,13 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::json_schema;
+use std::collections::BTreeSet;
+
+use crate::{
+    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    batch::TxSequenceNumber,
+    json_schema,
+};
 use move_core_types::language_storage::StructTag;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -24,3 +30,77 @@ impl Event {
         Event { type_, contents }
     }
 }
+
+/// Position of an event in the sequence of transactions executed by a store: the
+/// sequence number of the transaction which emitted it, and the index of the event
+/// in the transaction effects.
+pub type EventID = (TxSequenceNumber, u64);
+
+/// An event, along with the transaction which emitted it.
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct EventEnvelope {
+    pub id: EventID,
+    pub tx_digest: TransactionDigest,
+    /// The sender of the transaction which emitted the event
+    pub sender: SuiAddress,
+    pub event: Event,
+}
+
+impl EventEnvelope {
+    /// Wrap the events emitted by a transaction, executed at sequence number `seq`.
+    pub fn from_transaction(
+        seq: TxSequenceNumber,
+        tx_digest: TransactionDigest,
+        sender: SuiAddress,
+        events: &[Event],
+    ) -> Vec<Self> {
+        events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| Self {
+                id: (seq, index as u64),
+                tx_digest,
+                sender,
+                event: event.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Criteria selecting events. An event matches a filter if it matches all the criteria set.
+#[derive(Eq, PartialEq, Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct EventFilter {
+    /// The Move type of the event
+    #[schemars(with = "Option<json_schema::StructTag>")]
+    pub event_type: Option<StructTag>,
+    /// The sender of the transaction which emitted the event
+    pub sender: Option<SuiAddress>,
+    /// The transaction which emitted the event
+    pub transaction: Option<TransactionDigest>,
+    /// An object created, mutated, unwrapped, deleted or wrapped by the transaction
+    /// which emitted the event
+    pub object: Option<ObjectID>,
+}
+
+impl EventFilter {
+    /// Returns whether `event` matches the criteria on the event type, the sender and
+    /// the transaction. The criterion on objects is not checked.
+    pub fn matches_event(&self, event: &EventEnvelope) -> bool {
+        self.event_type
+            .as_ref()
+            .map_or(true, |event_type| event_type == &event.event.type_)
+            && self.sender.map_or(true, |sender| sender == event.sender)
+            && self
+                .transaction
+                .map_or(true, |digest| digest == event.tx_digest)
+    }
+
+    /// Returns whether `event`, emitted by a transaction which touched `object_ids`,
+    /// matches the filter.
+    pub fn matches(&self, event: &EventEnvelope, object_ids: &BTreeSet<ObjectID>) -> bool {
+        self.matches_event(event)
+            && self
+                .object
+                .map_or(true, |object_id| object_ids.contains(&object_id))
+    }
+}
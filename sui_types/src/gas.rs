@@ -8,7 +8,7 @@ use crate::{
     object::Object,
 };
 use move_core_types::gas_schedule::{
-    AbstractMemorySize, GasAlgebra, GasCarrier, GasPrice, GasUnits, InternalGasUnits,
+    AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasPrice, GasUnits, InternalGasUnits,
 };
 use move_vm_types::gas_schedule::{GasStatus, INITIAL_COST_SCHEDULE};
 use once_cell::sync::Lazy;
@@ -26,7 +26,7 @@ macro_rules! ok_or_gas_error {
     };
 }
 
-#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct GasCostSummary {
     pub computation_cost: u64,
     pub storage_cost: u64,
@@ -37,13 +37,21 @@ impl GasCostSummary {
     pub fn gas_used(&self) -> u64 {
         self.computation_cost + self.storage_cost
     }
+
+    /// Adds the costs and rebates of another transaction to this summary.
+    pub fn accumulate(&mut self, other: &GasCostSummary) {
+        self.computation_cost += other.computation_cost;
+        self.storage_cost += other.storage_cost;
+        self.storage_rebate += other.storage_rebate;
+    }
 }
 
 /// ComputationCost is a newtype wrapper of InternalGasUnits
 /// to ensure a value of this type is used specifically for computation cost.
 /// Anything that does not change the amount of bytes stored in the authority data store
 /// will charge ComputationCost.
-struct ComputationCost(InternalGasUnits<GasCarrier>);
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ComputationCost(pub InternalGasUnits<GasCarrier>);
 
 impl ComputationCost {
     /// Some computations are also linear to the size of data it operates on.
@@ -56,7 +64,8 @@ impl ComputationCost {
 /// to ensure a value of this type is used specifically for storage cost.
 /// Anything that changes the amount of bytes stored in the authority data store
 /// will charge StorageCost.
-struct StorageCost(InternalGasUnits<GasCarrier>);
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StorageCost(pub InternalGasUnits<GasCarrier>);
 
 impl StorageCost {
     pub fn with_size(&self, size: usize) -> Self {
@@ -65,7 +74,8 @@ impl StorageCost {
 }
 
 /// A list of constant costs of various operations in Sui.
-struct SuiCostTable {
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SuiCostTable {
     /// A flat fee charged for every transaction. This is also the mimmum amount of
     /// gas charged for a transaction.
     pub min_transaction_cost: ComputationCost,
@@ -102,6 +112,39 @@ static INIT_SUI_COST_TABLE: Lazy<SuiCostTable> = Lazy::new(|| SuiCostTable {
     storage_per_byte_cost: StorageCost(InternalGasUnits::new(100)),
 });
 
+/// All the costs charged for a transaction: the costs of the Move instructions and
+/// native functions charged by the VM, and the costs of the operations Sui does around it.
+/// It is stored BCS-encoded in the on-chain gas schedule, so that it can be changed by
+/// the validators without upgrading their binaries.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SuiCostSchedule {
+    pub move_cost_table: CostTable,
+    pub sui_cost_table: SuiCostTable,
+}
+
+impl SuiCostSchedule {
+    pub fn from_bytes(bytes: &[u8]) -> SuiResult<Self> {
+        bcs::from_bytes(bytes).map_err(|e| SuiError::InvalidCostSchedule {
+            error: e.to_string(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Serializing a cost schedule cannot fail")
+    }
+}
+
+/// The cost schedule of the genesis gas schedule, in effect until the validators vote
+/// for a new one.
+pub static INITIAL_SUI_COST_SCHEDULE: Lazy<SuiCostSchedule> = Lazy::new(|| SuiCostSchedule {
+    move_cost_table: INITIAL_COST_SCHEDULE.clone(),
+    sui_cost_table: INIT_SUI_COST_TABLE.clone(),
+});
+
+/// The reference gas price of the first epoch, announced by the genesis validators.
+/// Transactions are priced at the reference gas price unless their sender sets a higher one.
+pub const INITIAL_REFERENCE_GAS_PRICE: u64 = 1;
+
 pub static MAX_GAS_BUDGET: Lazy<u64> =
     Lazy::new(|| to_external(InternalGasUnits::new(u64::MAX)).get());
 
@@ -113,13 +156,9 @@ fn to_external(internal_units: InternalGasUnits<GasCarrier>) -> GasUnits<GasCarr
     consts.to_external_units(internal_units)
 }
 
-fn to_internal(external_units: GasUnits<GasCarrier>) -> InternalGasUnits<GasCarrier> {
-    let consts = &INITIAL_COST_SCHEDULE.gas_constants;
-    consts.to_internal_units(external_units)
-}
-
 pub struct SuiGasStatus<'a> {
     gas_status: GasStatus<'a>,
+    cost_schedule: &'a SuiCostSchedule,
     init_budget: GasUnits<GasCarrier>,
     computation_gas_unit_price: GasPrice<GasCarrier>,
     storage_gas_unit_price: GasPrice<GasCarrier>,
@@ -134,13 +173,17 @@ pub struct SuiGasStatus<'a> {
 }
 
 impl<'a> SuiGasStatus<'a> {
+    /// Meter a transaction with the costs of `cost_schedule`, which is the schedule in
+    /// effect in the epoch the transaction belongs to.
     pub fn new_with_budget(
         gas_budget: u64,
         computation_gas_unit_price: GasCarrier,
         storage_gas_unit_price: GasCarrier,
+        cost_schedule: &'a SuiCostSchedule,
     ) -> SuiGasStatus<'a> {
         Self::new(
-            GasStatus::new(&INITIAL_COST_SCHEDULE, GasUnits::new(gas_budget)),
+            GasStatus::new(&cost_schedule.move_cost_table, GasUnits::new(gas_budget)),
+            cost_schedule,
             gas_budget,
             computation_gas_unit_price,
             storage_gas_unit_price,
@@ -148,7 +191,13 @@ impl<'a> SuiGasStatus<'a> {
     }
 
     pub fn new_unmetered() -> SuiGasStatus<'a> {
-        Self::new(GasStatus::new_unmetered(), 0, 0, 0)
+        Self::new(
+            GasStatus::new_unmetered(),
+            &INITIAL_SUI_COST_SCHEDULE,
+            0,
+            0,
+            0,
+        )
     }
 
     pub fn get_move_gas_status(&mut self) -> &mut GasStatus<'a> {
@@ -156,22 +205,26 @@ impl<'a> SuiGasStatus<'a> {
     }
 
     pub fn charge_min_tx_gas(&mut self) -> SuiResult {
-        self.deduct_computation_cost(&INIT_SUI_COST_TABLE.min_transaction_cost)
+        self.deduct_computation_cost(&self.cost_schedule.sui_cost_table.min_transaction_cost)
     }
 
     pub fn charge_consensus(&mut self) -> SuiResult {
-        self.deduct_computation_cost(&INIT_SUI_COST_TABLE.consensus_cost)
+        self.deduct_computation_cost(&self.cost_schedule.sui_cost_table.consensus_cost)
     }
 
     pub fn charge_publish_package(&mut self, size: usize) -> SuiResult {
-        let computation_cost = INIT_SUI_COST_TABLE
+        let computation_cost = self
+            .cost_schedule
+            .sui_cost_table
             .package_publish_per_byte_cost
             .with_size(size);
         self.deduct_computation_cost(&computation_cost)
     }
 
     pub fn charge_storage_read(&mut self, size: usize) -> SuiResult {
-        let cost = INIT_SUI_COST_TABLE
+        let cost = self
+            .cost_schedule
+            .sui_cost_table
             .object_read_per_byte_cost
             .with_size(size);
         self.deduct_computation_cost(&cost)
@@ -186,14 +239,18 @@ impl<'a> SuiGasStatus<'a> {
         // Computation cost of a mutation is charged based on the sum of the old and new size.
         // This is because to update an object in the store, we have to erase the old one and
         // write a new one.
-        let cost = INIT_SUI_COST_TABLE
+        let cost = self
+            .cost_schedule
+            .sui_cost_table
             .object_mutation_per_byte_cost
             .with_size(old_size + new_size);
         self.deduct_computation_cost(&cost)?;
 
         self.storage_rebate += storage_rebate;
 
-        let storage_cost = INIT_SUI_COST_TABLE
+        let storage_cost = self
+            .cost_schedule
+            .sui_cost_table
             .storage_per_byte_cost
             .with_size(new_size);
         self.deduct_storage_cost(&storage_cost)
@@ -233,12 +290,14 @@ impl<'a> SuiGasStatus<'a> {
 
     fn new(
         move_gas_status: GasStatus<'a>,
+        cost_schedule: &'a SuiCostSchedule,
         gas_budget: u64,
         computation_gas_unit_price: GasCarrier,
         storage_gas_unit_price: u64,
     ) -> SuiGasStatus<'a> {
         SuiGasStatus {
             gas_status: move_gas_status,
+            cost_schedule,
             init_budget: GasUnits::new(gas_budget),
             computation_gas_unit_price: GasPrice::new(computation_gas_unit_price),
             storage_gas_unit_price: GasPrice::new(storage_gas_unit_price),
@@ -258,8 +317,9 @@ impl<'a> SuiGasStatus<'a> {
     }
 
     fn deduct_storage_cost(&mut self, cost: &StorageCost) -> SuiResult<GasCarrier> {
-        let ext_cost = to_external(cost.0);
-        let charge_amount = to_internal(ext_cost);
+        let gas_constants = &self.cost_schedule.move_cost_table.gas_constants;
+        let ext_cost = gas_constants.to_external_units(cost.0);
+        let charge_amount = gas_constants.to_internal_units(ext_cost);
         let remaining_gas = self.gas_status.remaining_gas();
         if self.gas_status.deduct_gas(charge_amount).is_err() {
             debug_assert_eq!(self.gas_status.remaining_gas().get(), 0);
@@ -281,8 +341,8 @@ impl<'a> SuiGasStatus<'a> {
 /// 1. If the gas object is owned.
 /// 2. If it's enough to pay the flat minimum transaction fee
 /// 3. If it's less than the max gas budget allowed
-/// 4. If the gas_object actually has enough balance to pay for the budget.
-pub fn check_gas_balance(gas_object: &Object, gas_budget: u64) -> SuiResult {
+/// 4. If the gas_object actually has enough balance to pay for the budget at `gas_price`.
+pub fn check_gas_balance(gas_object: &Object, gas_budget: u64, gas_price: u64) -> SuiResult {
     ok_or_gas_error!(
         gas_object.is_owned(),
         "Gas object must be owned Move object".to_owned()
@@ -300,9 +360,10 @@ pub fn check_gas_balance(gas_object: &Object, gas_budget: u64) -> SuiResult {
     )?;
 
     let balance = get_gas_balance(gas_object)?;
+    let gas_cost = gas_budget.saturating_mul(gas_price);
     ok_or_gas_error!(
-        balance >= gas_budget,
-        format!("Gas balance is {balance}, not enough to pay {gas_budget}")
+        balance >= gas_cost,
+        format!("Gas balance is {balance}, not enough to pay {gas_cost}")
     )
 }
 
@@ -311,11 +372,13 @@ pub fn start_gas_metering(
     gas_budget: u64,
     computation_gas_unit_price: u64,
     storage_gas_unit_price: u64,
-) -> SuiResult<SuiGasStatus<'static>> {
+    cost_schedule: &SuiCostSchedule,
+) -> SuiResult<SuiGasStatus<'_>> {
     let mut gas_status = SuiGasStatus::new_with_budget(
         gas_budget,
         computation_gas_unit_price,
         storage_gas_unit_price,
+        cost_schedule,
     );
     // Charge the flat transaction fee.
     gas_status.charge_min_tx_gas()?;
@@ -27,7 +27,10 @@ pub mod move_package;
 pub mod object;
 pub mod readable_serde;
 pub mod signature_seed;
+pub mod snapshot;
+pub mod state_commitment;
 pub mod storage;
+pub mod sui_system_state;
 
 /// 0x1-- account address where Move stdlib modules are stored
 /// Same as the ObjectID
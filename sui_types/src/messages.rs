@@ -8,17 +8,23 @@ use crate::crypto::{
     sha3_hash, AuthorityQuorumSignInfo, AuthoritySignInfo, AuthoritySignature, BcsSignable,
     EmptySignInfo, Signable, Signature, VerificationObligation,
 };
-use crate::gas::GasCostSummary;
+use crate::gas::{GasCostSummary, INITIAL_REFERENCE_GAS_PRICE};
 use crate::json_schema;
 use crate::object::{Object, ObjectFormatOptions, Owner, OBJECT_START_VERSION};
 use crate::readable_serde::encoding::Base64;
 use crate::readable_serde::Readable;
+use crate::sui_system_state::{
+    ADVANCE_EPOCH_FUNCTION_NAME, REPORT_GAS_CHARGES_FUNCTION_NAME, SUI_SYSTEM_MODULE_NAME,
+};
+use crate::SUI_FRAMEWORK_ADDRESS;
 use base64ct::Encoding;
 use itertools::Either;
 use move_binary_format::access::ModuleAccess;
 use move_binary_format::CompiledModule;
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::TypeTag,
+    account_address::AccountAddress,
+    identifier::{IdentStr, Identifier},
+    language_storage::TypeTag,
     value::MoveStructLayout,
 };
 use name_variant::NamedVariant;
@@ -42,11 +48,12 @@ mod messages_tests;
 pub enum CallArg {
     // contains no structs or objects
     Pure(Vec<u8>),
-    // TODO support more than one object (object vector of some sort)
     // A Move object, either immutable, or owned mutable.
     ImmOrOwnedObject(ObjectRef),
     // A Move object that's shared and mutable.
     SharedObject(ObjectID),
+    // A vector of owned Move objects, passed by value.
+    ObjVec(Vec<ObjectRef>),
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, JsonSchema)]
@@ -96,11 +103,38 @@ impl SingleTransactionKind {
         self.shared_input_objects().next().is_some()
     }
 
+    /// Whether this is the system call advancing the Sui system state to the next epoch.
+    pub fn is_change_epoch(&self) -> bool {
+        self.is_sui_system_call(ADVANCE_EPOCH_FUNCTION_NAME)
+    }
+
+    /// Whether this is the system call reporting the gas fees a validator collected
+    /// during the epoch.
+    pub fn is_report_gas_charges(&self) -> bool {
+        self.is_sui_system_call(REPORT_GAS_CHARGES_FUNCTION_NAME)
+    }
+
+    fn is_sui_system_call(&self, function_name: &IdentStr) -> bool {
+        match self {
+            Self::Call(MoveCall {
+                package,
+                module,
+                function,
+                ..
+            }) => {
+                package.0 == ObjectID::from(SUI_FRAMEWORK_ADDRESS)
+                    && module.as_ident_str() == SUI_SYSTEM_MODULE_NAME
+                    && function.as_ident_str() == function_name
+            }
+            _ => false,
+        }
+    }
+
     pub fn shared_input_objects(&self) -> impl Iterator<Item = &ObjectID> {
         match &self {
             Self::Call(MoveCall { arguments, .. }) => {
                 Either::Left(arguments.iter().filter_map(|arg| match arg {
-                    CallArg::Pure(_) | CallArg::ImmOrOwnedObject(_) => None,
+                    CallArg::Pure(_) | CallArg::ImmOrOwnedObject(_) | CallArg::ObjVec(_) => None,
                     CallArg::SharedObject(id) => Some(id),
                 }))
             }
@@ -121,12 +155,16 @@ impl SingleTransactionKind {
                 arguments, package, ..
             }) => arguments
                 .iter()
-                .filter_map(|arg| match arg {
-                    CallArg::Pure(_) => None,
+                .flat_map(|arg| match arg {
+                    CallArg::Pure(_) => vec![],
                     CallArg::ImmOrOwnedObject(object_ref) => {
-                        Some(InputObjectKind::ImmOrOwnedMoveObject(*object_ref))
+                        vec![InputObjectKind::ImmOrOwnedMoveObject(*object_ref)]
                     }
-                    CallArg::SharedObject(id) => Some(InputObjectKind::SharedMoveObject(*id)),
+                    CallArg::SharedObject(id) => vec![InputObjectKind::SharedMoveObject(*id)],
+                    CallArg::ObjVec(object_refs) => object_refs
+                        .iter()
+                        .map(|object_ref| InputObjectKind::ImmOrOwnedMoveObject(*object_ref))
+                        .collect(),
                 })
                 .chain([InputObjectKind::MovePackage(package.0)])
                 .collect(),
@@ -250,13 +288,19 @@ pub struct TransactionData {
     pub kind: TransactionKind,
     sender: SuiAddress,
     gas_payment: ObjectRef,
+    /// The maximum number of gas units the transaction can use.
     pub gas_budget: u64,
+    /// The price of each gas unit used, which must be at least the reference gas price
+    /// of the epoch for authorities to sign the transaction.
+    pub gas_price: u64,
 }
 
 impl TransactionData
 where
     Self: BcsSignable,
 {
+    /// The transaction is priced at the initial reference gas price, which the sender can
+    /// raise by setting `gas_price`.
     pub fn new(
         kind: TransactionKind,
         sender: SuiAddress,
@@ -268,6 +312,7 @@ where
             sender,
             gas_payment,
             gas_budget,
+            gas_price: INITIAL_REFERENCE_GAS_PRICE,
         }
     }
 
@@ -317,6 +362,77 @@ where
         Self::new(kind, sender, gas_payment, gas_budget)
     }
 
+    /// The system transaction ending `new_epoch - 1`: it calls `SuiSystem::advance_epoch`
+    /// on the shared system state, which must be signed by an active validator. The gas
+    /// fees the validators reported for the epoch are distributed as staking rewards.
+    pub fn new_change_epoch(
+        sender: SuiAddress,
+        framework: ObjectRef,
+        system_state: ObjectID,
+        gas_payment: ObjectRef,
+        new_epoch: EpochId,
+        gas_budget: u64,
+    ) -> Self {
+        Self::new_move_call(
+            sender,
+            framework,
+            SUI_SYSTEM_MODULE_NAME.to_owned(),
+            ADVANCE_EPOCH_FUNCTION_NAME.to_owned(),
+            vec![],
+            gas_payment,
+            vec![
+                CallArg::SharedObject(system_state),
+                CallArg::Pure(bcs::to_bytes(&new_epoch).unwrap()),
+            ],
+            gas_budget,
+        )
+    }
+
+    /// The system transaction reporting the gas fees collected by the validator `sender`
+    /// during the epoch, by calling `SuiSystem::report_gas_charges` on the shared system
+    /// state.
+    pub fn new_report_gas_charges(
+        sender: SuiAddress,
+        framework: ObjectRef,
+        system_state: ObjectID,
+        gas_payment: ObjectRef,
+        gas_charges: &GasCostSummary,
+        gas_budget: u64,
+    ) -> Self {
+        Self::new_move_call(
+            sender,
+            framework,
+            SUI_SYSTEM_MODULE_NAME.to_owned(),
+            REPORT_GAS_CHARGES_FUNCTION_NAME.to_owned(),
+            vec![],
+            gas_payment,
+            vec![
+                CallArg::SharedObject(system_state),
+                CallArg::Pure(bcs::to_bytes(&gas_charges.storage_cost).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&gas_charges.computation_cost).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&gas_charges.storage_rebate).unwrap()),
+            ],
+            gas_budget,
+        )
+    }
+
+    /// Whether this is the (non-batched) system transaction changing the epoch.
+    pub fn is_change_epoch(&self) -> bool {
+        match &self.kind {
+            TransactionKind::Single(s) => s.is_change_epoch(),
+            TransactionKind::Batch(_) => false,
+        }
+    }
+
+    /// Whether this is the (non-batched) system transaction reporting the gas fees of
+    /// the epoch.
+    pub fn is_report_gas_charges(&self) -> bool {
+        match &self.kind {
+            TransactionKind::Single(s) => s.is_report_gas_charges(),
+            TransactionKind::Batch(_) => false,
+        }
+    }
+
     /// Returns the transaction kind as a &str (variant name, no fields)
     pub fn kind_as_str(&self) -> &'static str {
         self.kind.variant_name()
@@ -344,6 +460,11 @@ where
         &self.gas_payment
     }
 
+    /// The digest of any transaction carrying this data.
+    pub fn digest(&self) -> TransactionDigest {
+        TransactionDigest::new(sha3_hash(self))
+    }
+
     pub fn input_objects(&self) -> SuiResult<Vec<InputObjectKind>> {
         let mut inputs = match &self.kind {
             TransactionKind::Single(s) => s.input_objects()?,
@@ -451,8 +572,7 @@ impl<S> TransactionEnvelope<S> {
 
     /// Get the transaction digest and write it to the cache
     pub fn digest(&self) -> &TransactionDigest {
-        self.transaction_digest
-            .get_or_init(|| TransactionDigest::new(sha3_hash(&self.data)))
+        self.transaction_digest.get_or_init(|| self.data.digest())
     }
 
     pub fn input_objects_in_compiled_modules(
@@ -711,8 +831,9 @@ pub struct ObjectInfoResponse {
     /// The full reference created by the above certificate
     pub requested_object_reference: Option<ObjectRef>,
 
-    /// The object and its current lock, returned only if we are requesting
-    /// the latest state of an object.
+    /// The object and its current lock if we are requesting the latest state of an object.
+    /// For past versions this holds the object contents at that version, without a lock,
+    /// if the authority keeps historical object versions (or the version is the latest one).
     /// If the object does not exist this is also None.
     pub object_and_lock: Option<ObjectResponse>,
 }
@@ -748,6 +869,18 @@ pub struct TransactionInfoResponse {
     pub signed_effects: Option<SignedTransactionEffects>,
 }
 
+/// The outcome of executing a transaction against the current state of an authority,
+/// without committing it.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DryRunTransactionResponse {
+    // The effects the transaction would have, if executed now
+    pub effects: TransactionEffects,
+    // The events emitted during execution
+    pub events: Vec<Event>,
+    // The gas the transaction would be charged
+    pub gas_cost_summary: GasCostSummary,
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum CallResult {
     Bool(bool),
@@ -877,6 +1010,17 @@ impl TransactionEffects {
         self.mutated.iter().filter(|o| *o != &self.gas_object)
     }
 
+    /// Return the IDs of all objects created, mutated, unwrapped, deleted or wrapped
+    /// by the transaction.
+    pub fn touched_object_ids(&self) -> BTreeSet<ObjectID> {
+        self.mutated_and_created()
+            .chain(self.unwrapped.iter())
+            .map(|((id, _, _), _)| *id)
+            .chain(self.deleted.iter().map(|(id, _, _)| *id))
+            .chain(self.wrapped.iter().map(|(id, _, _)| *id))
+            .collect()
+    }
+
     pub fn is_object_mutated_here(&self, obj_ref: ObjectRef) -> bool {
         // The mutated or created case
         if self.mutated_and_created().any(|(oref, _)| *oref == obj_ref) {
@@ -1239,13 +1383,68 @@ pub struct ConsensusSync {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum ConsensusTransaction {
     UserTransaction(CertifiedTransaction),
-    // NOTE: Other data types (e.g., for reconfiguration) go here
+    /// Certificates without shared objects that an authority executed outside of consensus
+    /// during the epoch, in the order it executed them. Sequencing them at the end of the
+    /// epoch makes every authority execute them before the epoch changes.
+    EpochCertificates(Vec<CertifiedTransaction>),
+    /// An authority announcing it sequenced all the certificates it executed outside of
+    /// consensus during the epoch.
+    EndOfEpoch(SignedEndOfEpoch),
 }
 
 impl ConsensusTransaction {
     pub fn verify(&self, committee: &Committee) -> SuiResult<()> {
         match self {
             Self::UserTransaction(certificate) => certificate.verify(committee),
+            Self::EpochCertificates(certificates) => certificates
+                .iter()
+                .try_for_each(|certificate| certificate.verify(committee)),
+            Self::EndOfEpoch(end_of_epoch) => end_of_epoch.verify(committee),
         }
     }
 }
+
+/// The end of an epoch, as seen by one authority.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EndOfEpoch {
+    pub epoch: EpochId,
+    pub authority: AuthorityName,
+}
+
+impl BcsSignable for EndOfEpoch {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignedEndOfEpoch {
+    pub end_of_epoch: EndOfEpoch,
+    pub signature: AuthoritySignature,
+}
+
+impl SignedEndOfEpoch {
+    pub fn new(
+        epoch: EpochId,
+        authority: AuthorityName,
+        secret: &dyn signature::Signer<AuthoritySignature>,
+    ) -> Self {
+        let end_of_epoch = EndOfEpoch { epoch, authority };
+        Self {
+            signature: AuthoritySignature::new(&end_of_epoch, secret),
+            end_of_epoch,
+        }
+    }
+
+    /// Check that the end of the current epoch is signed by a member of its committee.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        fp_ensure!(
+            self.end_of_epoch.epoch == committee.epoch,
+            SuiError::WrongEpoch {
+                expected_epoch: committee.epoch
+            }
+        );
+        fp_ensure!(
+            committee.weight(&self.end_of_epoch.authority) > 0,
+            SuiError::UnknownSigner
+        );
+        self.signature
+            .verify(&self.end_of_epoch, self.end_of_epoch.authority)
+    }
+}
@@ -389,6 +389,16 @@ impl Object {
         }
     }
 
+    /// A SUI coin of `value` owned by `owner`, minted at genesis.
+    pub fn new_gas_coin(id: ObjectID, owner: SuiAddress, value: u64) -> Self {
+        let coin = GasCoin::new(id, SequenceNumber::new(), value);
+        Self::new_move(
+            MoveObject::new(GasCoin::type_(), coin.to_bcs_bytes()),
+            Owner::AddressOwner(owner),
+            TransactionDigest::genesis(),
+        )
+    }
+
     pub fn is_immutable(&self) -> bool {
         self.owner.is_immutable()
     }
@@ -563,7 +573,12 @@ impl Object {
                     error: e.to_string(),
                 }
             })?;
-        Ok(json!({ "contents": contents, "owner": owner, "tx_digest": previous_transaction }))
+        Ok(json!({
+            "contents": contents,
+            "owner": owner,
+            "tx_digest": previous_transaction,
+            "storage_rebate": self.storage_rebate,
+        }))
     }
 
     /// Treat the object type as a Move struct with one type parameter,
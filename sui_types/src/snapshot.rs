@@ -0,0 +1,177 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::base_types::{ObjectID, ObjectRef, SequenceNumber};
+use crate::batch::{BatchDigest, SignedBatch};
+use crate::committee::Committee;
+use crate::crypto::{sha3_hash, BcsSignable};
+use crate::error::{SuiError, SuiResult};
+use crate::messages::CertifiedTransaction;
+use crate::object::Object;
+use crate::state_commitment::{update_state_tree, StateDigest};
+use narwhal_executor::ExecutionIndices;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub type SnapshotDigest = [u8; 32];
+
+/// The set of live objects held by an authority once it has executed all the
+/// transactions up to the end of a signed batch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    /// The batch the snapshot was taken at. It must be signed with its state root.
+    pub batch: SignedBatch,
+    /// All live objects, sorted by object ID.
+    pub objects: Vec<Object>,
+    /// The certificates that created the current version of the objects, so that
+    /// a store bootstrapped from the snapshot can still serve them to clients.
+    pub parent_certificates: Vec<CertifiedTransaction>,
+    /// The next version consensus assigns to each shared object.
+    pub schedule: Vec<(ObjectID, SequenceNumber)>,
+    /// The index of the last consensus output processed, where the consensus client resumes.
+    pub last_consensus_index: ExecutionIndices,
+    /// Batches of other authorities signed with the same state root, so that the state
+    /// root is signed by a quorum of the committee.
+    pub attestations: Vec<SignedBatch>,
+    /// Commitment to the batch, the object references of all objects and the consensus
+    /// state.
+    pub digest: SnapshotDigest,
+}
+
+/// The content the snapshot digest commits to.
+#[derive(Serialize, Deserialize)]
+struct SnapshotCommitment<'a> {
+    batch_digest: BatchDigest,
+    object_refs: Vec<ObjectRef>,
+    schedule: &'a [(ObjectID, SequenceNumber)],
+    last_consensus_index: &'a ExecutionIndices,
+}
+
+impl BcsSignable for SnapshotCommitment<'_> {}
+
+impl StateSnapshot {
+    pub fn new(
+        batch: SignedBatch,
+        objects: Vec<Object>,
+        parent_certificates: Vec<CertifiedTransaction>,
+        schedule: Vec<(ObjectID, SequenceNumber)>,
+        last_consensus_index: ExecutionIndices,
+    ) -> Self {
+        let digest = Self::compute_digest(&batch, &objects, &schedule, &last_consensus_index);
+        StateSnapshot {
+            batch,
+            objects,
+            parent_certificates,
+            schedule,
+            last_consensus_index,
+            attestations: Vec::new(),
+            digest,
+        }
+    }
+
+    fn compute_digest(
+        batch: &SignedBatch,
+        objects: &[Object],
+        schedule: &[(ObjectID, SequenceNumber)],
+        last_consensus_index: &ExecutionIndices,
+    ) -> SnapshotDigest {
+        sha3_hash(&SnapshotCommitment {
+            batch_digest: batch.batch.digest(),
+            object_refs: objects
+                .iter()
+                .map(|object| object.compute_object_reference())
+                .collect(),
+            schedule,
+            last_consensus_index,
+        })
+    }
+
+    /// The state root the snapshot batch is signed with.
+    pub fn signed_state_root(&self) -> SuiResult<StateDigest> {
+        self.batch
+            .batch
+            .state_root
+            .ok_or_else(|| SuiError::StateSnapshotError {
+                error: "The snapshot batch is not signed with a state root".to_string(),
+            })
+    }
+
+    /// The state root of the snapshot objects, computed in memory from an empty tree.
+    pub fn compute_state_root(&self) -> SuiResult<StateDigest> {
+        let (state_root, _) = update_state_tree(
+            |_| Ok(None),
+            self.objects.iter().map(|object| {
+                let object_ref = object.compute_object_reference();
+                (object_ref.0, Some(object_ref))
+            }),
+        )?;
+        Ok(state_root)
+    }
+
+    /// Adds the batch of another authority, signed with the state root of the snapshot.
+    pub fn add_attestation(&mut self, batch: SignedBatch) -> SuiResult {
+        fp_ensure!(
+            batch.batch.state_root == Some(self.signed_state_root()?),
+            SuiError::StateSnapshotError {
+                error: "The batch is not signed with the state root of the snapshot".to_string(),
+            }
+        );
+        self.attestations.push(batch);
+        Ok(())
+    }
+
+    /// Checks the digest commits to the content of the snapshot, that its state root is
+    /// signed by authorities holding a quorum of the stake, and that the objects match
+    /// it. Snapshots of batches without a state root are rejected.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        let state_root = self.signed_state_root()?;
+        fp_ensure!(
+            Self::compute_digest(
+                &self.batch,
+                &self.objects,
+                &self.schedule,
+                &self.last_consensus_index
+            ) == self.digest,
+            SuiError::StateSnapshotError {
+                error: "Snapshot digest does not match its content".to_string(),
+            }
+        );
+
+        let mut signers = HashSet::new();
+        let mut weight = 0;
+        for batch in std::iter::once(&self.batch).chain(&self.attestations) {
+            fp_ensure!(
+                batch.batch.state_root == Some(state_root),
+                SuiError::StateSnapshotError {
+                    error: "An attestation is not signed with the state root of the snapshot"
+                        .to_string(),
+                }
+            );
+            fp_ensure!(
+                signers.insert(batch.authority),
+                SuiError::CertificateAuthorityReuse
+            );
+            let signer_weight = committee.weight(&batch.authority);
+            fp_ensure!(signer_weight > 0, SuiError::UnknownSigner);
+            batch.signature.verify(&batch.batch, batch.authority)?;
+            weight += signer_weight;
+        }
+        fp_ensure!(
+            weight >= committee.quorum_threshold(),
+            SuiError::StateSnapshotError {
+                error: "The state root is not signed by a quorum".to_string(),
+            }
+        );
+
+        fp_ensure!(
+            self.compute_state_root()? == state_root,
+            SuiError::StateSnapshotError {
+                error: "Snapshot objects do not match the state root of the batch".to_string(),
+            }
+        );
+        for certificate in &self.parent_certificates {
+            certificate.verify(committee)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,372 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::base_types::{ObjectID, ObjectRef};
+use crate::error::{SuiError, SuiResult};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+#[path = "unit_tests/state_commitment_tests.rs"]
+mod state_commitment_tests;
+
+/*
+
+The state commitment is a sparse Merkle tree mapping the ID of every live object to its
+object reference. Its root commits to the whole live object set of an authority, and a
+proof shows that an object is (or is not) part of that set.
+
+As in the Jellyfish Merkle tree, the tree is compressed: a subtree holding a single object
+is replaced by the leaf of that object, and empty subtrees are not stored. An object sits
+just below the longest prefix it shares with another object, so for random object IDs the
+tree and the proofs have about log2(n) levels for n objects, instead of one level per bit
+of the object ID.
+
+*/
+
+pub type StateDigest = [u8; 32];
+
+/// The digest of an empty subtree, and so the root of the empty tree.
+pub const EMPTY_STATE_DIGEST: StateDigest = [0; 32];
+
+/// The maximum number of levels below the root, one per bit of the object ID.
+pub const STATE_TREE_DEPTH: usize = ObjectID::LENGTH * 8;
+
+const LEAF_TAG: u8 = 0;
+const NODE_TAG: u8 = 1;
+
+/// A node is identified by its depth (0 for the root), and the ID of the leftmost
+/// object below it, i.e. the common prefix of the IDs below it followed by zeros.
+pub type StateNodeKey = (u16, ObjectID);
+
+pub fn root_key() -> StateNodeKey {
+    (0, ObjectID::ZERO)
+}
+
+/// A stored node of the tree.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StateNode {
+    /// A subtree holding a single object.
+    Leaf(ObjectRef),
+    /// A subtree holding at least two objects, with the digests of its children.
+    Internal {
+        left: StateDigest,
+        right: StateDigest,
+    },
+}
+
+impl StateNode {
+    pub fn digest(&self) -> StateDigest {
+        match self {
+            StateNode::Leaf(object_ref) => leaf_digest(object_ref),
+            StateNode::Internal { left, right } => node_digest(left, right),
+        }
+    }
+}
+
+fn leaf_digest(object_ref: &ObjectRef) -> StateDigest {
+    let mut hasher = Sha3_256::default();
+    hasher.update([LEAF_TAG]);
+    hasher.update(bcs::to_bytes(object_ref).expect("Serialization of object refs cannot fail"));
+    hasher.finalize().into()
+}
+
+fn node_digest(left: &StateDigest, right: &StateDigest) -> StateDigest {
+    let mut hasher = Sha3_256::default();
+    hasher.update([NODE_TAG]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn digest_of(node: &Option<StateNode>) -> StateDigest {
+    node.as_ref()
+        .map(StateNode::digest)
+        .unwrap_or(EMPTY_STATE_DIGEST)
+}
+
+/// Returns the bit of the object ID that decides in which child of the node at
+/// `depth` the object is.
+fn bit(object_id: &ObjectID, depth: usize) -> bool {
+    object_id.as_ref()[depth / 8] & (0x80 >> (depth % 8)) != 0
+}
+
+/// Returns the key of the node at `depth` above the object.
+fn node_key(object_id: &ObjectID, depth: usize) -> StateNodeKey {
+    let mut bytes = [0u8; ObjectID::LENGTH];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        let bits = depth.saturating_sub(index * 8).min(8);
+        // A shift by 8 would overflow, so mask whole bytes separately.
+        let mask = if bits == 8 { 0xff } else { !(0xffu8 >> bits) };
+        *byte = object_id.as_ref()[index] & mask;
+    }
+    (depth as u16, ObjectID::new(bytes))
+}
+
+/// Returns the key of the left or right child of the node at `depth` above the object.
+fn child_key(object_id: &ObjectID, depth: usize, right: bool) -> StateNodeKey {
+    let (_, prefix) = node_key(object_id, depth);
+    let mut bytes = [0u8; ObjectID::LENGTH];
+    bytes.copy_from_slice(prefix.as_ref());
+    if right {
+        bytes[depth / 8] |= 0x80 >> (depth % 8);
+    }
+    ((depth + 1) as u16, ObjectID::new(bytes))
+}
+
+/// Computes the nodes of the tree that change when the given objects are inserted,
+/// updated (with their new reference) or removed (with `None`); the last update of an
+/// object wins. Nodes are read through `get_node`, which returns `None` for empty subtrees.
+/// Returns the new root, and the changed nodes, where `None` means the node must be removed.
+pub fn update_state_tree<F>(
+    get_node: F,
+    updates: impl IntoIterator<Item = (ObjectID, Option<ObjectRef>)>,
+) -> SuiResult<(StateDigest, BTreeMap<StateNodeKey, Option<StateNode>>)>
+where
+    F: Fn(&StateNodeKey) -> SuiResult<Option<StateNode>>,
+{
+    let updates: BTreeMap<_, _> = updates.into_iter().collect();
+    let updates: Vec<_> = updates.into_iter().collect();
+    let mut changes = BTreeMap::new();
+    let root = update_subtree(&get_node, 0, get_node(&root_key())?, &updates, &mut changes)?;
+    let state_root = digest_of(&root);
+    if !updates.is_empty() {
+        changes.insert(root_key(), root);
+    }
+    Ok((state_root, changes))
+}
+
+/// Returns the node that replaces `node`, at `depth` above the updated objects, which are
+/// sorted by ID. The changed nodes below it are added to `changes`, but the node itself is
+/// written by the caller, since it moves up when it becomes a leaf.
+fn update_subtree<F>(
+    get_node: &F,
+    depth: usize,
+    node: Option<StateNode>,
+    updates: &[(ObjectID, Option<ObjectRef>)],
+    changes: &mut BTreeMap<StateNodeKey, Option<StateNode>>,
+) -> SuiResult<Option<StateNode>>
+where
+    F: Fn(&StateNodeKey) -> SuiResult<Option<StateNode>>,
+{
+    let (left, right) = match (node, updates.first()) {
+        (node, None) => return Ok(node),
+        (Some(StateNode::Internal { left, right }), Some(_)) => (left, right),
+        (node, Some(_)) => {
+            // The subtree held at most one object: rebuild it from the objects it now holds.
+            let mut objects = BTreeMap::new();
+            if let Some(StateNode::Leaf(object_ref)) = node {
+                objects.insert(object_ref.0, object_ref);
+            }
+            for (object_id, object_ref) in updates {
+                match object_ref {
+                    Some(object_ref) => objects.insert(*object_id, *object_ref),
+                    None => objects.remove(object_id),
+                };
+            }
+            let objects: Vec<_> = objects.into_values().collect();
+            return Ok(build_subtree(depth, &objects, changes));
+        }
+    };
+
+    let object_id = updates[0].0;
+    let split = updates.partition_point(|(object_id, _)| !bit(object_id, depth));
+    let mut children = Vec::with_capacity(2);
+    for (is_right, digest, child_updates) in [
+        (false, left, &updates[..split]),
+        (true, right, &updates[split..]),
+    ] {
+        let key = child_key(&object_id, depth, is_right);
+        let child = if child_updates.is_empty() {
+            None
+        } else {
+            let old_child = if digest == EMPTY_STATE_DIGEST {
+                None
+            } else {
+                get_node(&key)?
+            };
+            Some(update_subtree(
+                get_node,
+                depth + 1,
+                old_child,
+                child_updates,
+                changes,
+            )?)
+        };
+        let digest = child.as_ref().map(digest_of).unwrap_or(digest);
+        children.push((key, digest, child));
+    }
+
+    // A node with a single object below it is replaced by the leaf of that object.
+    let non_empty: Vec<_> = children
+        .iter()
+        .filter(|(_, digest, _)| *digest != EMPTY_STATE_DIGEST)
+        .collect();
+    let replacement = match non_empty[..] {
+        [] => Some(None),
+        [(key, _, child)] => {
+            let child = match child {
+                Some(child) => child.clone(),
+                None => get_node(key)?,
+            };
+            match child {
+                Some(StateNode::Leaf(object_ref)) => Some(Some(StateNode::Leaf(object_ref))),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    if let Some(node) = replacement {
+        for (key, digest, child) in children {
+            if digest != EMPTY_STATE_DIGEST || child.is_some() {
+                changes.insert(key, None);
+            }
+        }
+        return Ok(node);
+    }
+
+    let node = StateNode::Internal {
+        left: children[0].1,
+        right: children[1].1,
+    };
+    for (key, _, child) in children {
+        if let Some(child) = child {
+            changes.insert(key, child);
+        }
+    }
+    Ok(Some(node))
+}
+
+/// Builds the subtree at `depth` above the given objects, sorted by ID, of a position which
+/// held at most one object, so that no node is stored below it. Returns the node of the
+/// subtree, and adds the nodes below it to `changes`.
+fn build_subtree(
+    depth: usize,
+    objects: &[ObjectRef],
+    changes: &mut BTreeMap<StateNodeKey, Option<StateNode>>,
+) -> Option<StateNode> {
+    match objects {
+        [] => None,
+        [object_ref] => Some(StateNode::Leaf(*object_ref)),
+        _ => {
+            let split = objects.partition_point(|object_ref| !bit(&object_ref.0, depth));
+            let mut digests = [EMPTY_STATE_DIGEST; 2];
+            for (index, side) in [&objects[..split], &objects[split..]].iter().enumerate() {
+                if let Some(child) = build_subtree(depth + 1, side, changes) {
+                    digests[index] = child.digest();
+                    changes.insert(child_key(&objects[0].0, depth, index == 1), Some(child));
+                }
+            }
+            Some(StateNode::Internal {
+                left: digests[0],
+                right: digests[1],
+            })
+        }
+    }
+}
+
+/// A proof that an object is part of the state with the given reference, or that
+/// it is not part of the state if `object_ref` is `None`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectProof {
+    pub object_id: ObjectID,
+    pub object_ref: Option<ObjectRef>,
+    /// For a proof that the object is not part of the state, the object whose leaf is where
+    /// the object would be, if that position is not empty.
+    pub other_object: Option<ObjectRef>,
+    /// The digests of the siblings of the nodes on the path from the position of the
+    /// object to the root, starting at the bottom. Empty subtrees are `EMPTY_STATE_DIGEST`.
+    pub siblings: Vec<StateDigest>,
+}
+
+impl ObjectProof {
+    /// Makes the proof for an object, reading the nodes of the tree through `get_node`.
+    pub fn new<F>(get_node: F, object_id: ObjectID) -> SuiResult<Self>
+    where
+        F: Fn(&StateNodeKey) -> SuiResult<Option<StateNode>>,
+    {
+        let mut siblings = Vec::new();
+        let mut node = get_node(&root_key())?;
+        let mut depth = 0;
+        let leaf = loop {
+            match node {
+                Some(StateNode::Internal { left, right }) => {
+                    let is_right = bit(&object_id, depth);
+                    let (child, sibling) = if is_right {
+                        (right, left)
+                    } else {
+                        (left, right)
+                    };
+                    siblings.push(sibling);
+                    node = if child == EMPTY_STATE_DIGEST {
+                        None
+                    } else {
+                        get_node(&child_key(&object_id, depth, is_right))?
+                    };
+                    depth += 1;
+                }
+                Some(StateNode::Leaf(object_ref)) => break Some(object_ref),
+                None => break None,
+            }
+        };
+        siblings.reverse();
+
+        let (object_ref, other_object) = match leaf {
+            Some(object_ref) if object_ref.0 == object_id => (Some(object_ref), None),
+            other_object => (None, other_object),
+        };
+        Ok(ObjectProof {
+            object_id,
+            object_ref,
+            other_object,
+            siblings,
+        })
+    }
+
+    /// Recomputes the root of the tree from the object and the siblings.
+    pub fn root(&self) -> SuiResult<StateDigest> {
+        let invalid = || SuiError::InvalidStateProof {
+            object_id: self.object_id,
+        };
+        let depth = self.siblings.len();
+        fp_ensure!(depth <= STATE_TREE_DEPTH, invalid());
+
+        let mut digest = match (&self.object_ref, &self.other_object) {
+            (Some(object_ref), None) => {
+                fp_ensure!(object_ref.0 == self.object_id, invalid());
+                leaf_digest(object_ref)
+            }
+            (None, Some(other_object)) => {
+                // The other object must be on the path of the object, at its position.
+                fp_ensure!(
+                    other_object.0 != self.object_id
+                        && (0..depth).all(|d| bit(&other_object.0, d) == bit(&self.object_id, d)),
+                    invalid()
+                );
+                leaf_digest(other_object)
+            }
+            (None, None) => EMPTY_STATE_DIGEST,
+            (Some(_), Some(_)) => return Err(invalid()),
+        };
+        for (sibling, depth) in self.siblings.iter().zip((1..=depth).rev()) {
+            digest = if bit(&self.object_id, depth - 1) {
+                node_digest(sibling, &digest)
+            } else {
+                node_digest(&digest, sibling)
+            };
+        }
+        Ok(digest)
+    }
+
+    /// Checks the proof against a state root, for instance the one of a signed batch.
+    pub fn verify(&self, root: &StateDigest) -> SuiResult {
+        fp_ensure!(
+            &self.root()? == root,
+            SuiError::InvalidStateProof {
+                object_id: self.object_id
+            }
+        );
+        Ok(())
+    }
+}
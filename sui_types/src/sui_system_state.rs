@@ -0,0 +1,408 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::StructTag};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    base_types::{AuthorityName, ObjectID, SuiAddress, TransactionDigest},
+    committee::{Committee, EpochId},
+    crypto::PublicKeyBytes,
+    error::{SuiError, SuiResult},
+    gas::{SuiCostSchedule, INITIAL_SUI_COST_SCHEDULE},
+    id::VersionedID,
+    object::{MoveObject, Object, Owner, OBJECT_START_VERSION},
+    SUI_FRAMEWORK_ADDRESS,
+};
+
+pub const SUI_SYSTEM_MODULE_NAME: &IdentStr = ident_str!("SuiSystem");
+pub const SUI_SYSTEM_STATE_STRUCT_NAME: &IdentStr = ident_str!("SuiSystemState");
+pub const ADVANCE_EPOCH_FUNCTION_NAME: &IdentStr = ident_str!("advance_epoch");
+pub const REPORT_GAS_CHARGES_FUNCTION_NAME: &IdentStr = ident_str!("report_gas_charges");
+pub const GAS_SCHEDULE_MODULE_NAME: &IdentStr = ident_str!("GasSchedule");
+pub const GAS_SCHEDULE_STRUCT_NAME: &IdentStr = ident_str!("GasSchedule");
+
+/// 0x5-- the ID of the gas schedule, created at genesis.
+pub const SUI_GAS_SCHEDULE_OBJECT_ID: ObjectID = get_system_object_id(5);
+/// 0x6-- the ID of the Sui system state, created at genesis.
+pub const SUI_SYSTEM_STATE_OBJECT_ID: ObjectID = get_system_object_id(6);
+
+/// The initial amount of SUI locked in the storage fund.
+/// 10^14, an arbitrary number.
+const INIT_STORAGE_FUND: u64 = 100000000000000;
+/// Initial value of the lower-bound on the amount of stake required to become a validator.
+const INIT_MIN_VALIDATOR_STAKE: u64 = 100000000000000;
+/// Initial value of the upper-bound on the amount of stake allowed to become a validator.
+const INIT_MAX_VALIDATOR_STAKE: u64 = 100000000000000000;
+/// Initial value of the upper-bound on the number of validators.
+const INIT_MAX_VALIDATOR_COUNT: u64 = 100;
+
+const fn get_system_object_id(last_byte: u8) -> ObjectID {
+    let mut id = [0u8; ObjectID::LENGTH];
+    id[ObjectID::LENGTH - 1] = last_byte;
+    ObjectID::new(id)
+}
+
+/// Rust version of the Move Sui::Balance::Balance type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Balance {
+    pub value: u64,
+}
+
+/// Rust version of the Move Sui::Coin::TreasuryCap type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreasuryCap {
+    pub id: VersionedID,
+    pub total_supply: u64,
+}
+
+/// Rust version of the Move Sui::SuiSystem::SystemParameters type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SystemParameters {
+    pub min_validator_stake: u64,
+    pub max_validator_stake: u64,
+    pub max_validator_candidate_count: u64,
+}
+
+/// Rust version of the Move Sui::Validator::Validator type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Validator {
+    pub sui_address: SuiAddress,
+    pub pubkey_bytes: Vec<u8>,
+    pub name: Vec<u8>,
+    pub net_address: Vec<u8>,
+    pub gas_price: u64,
+    pub stake: Balance,
+    pub delegation: u64,
+    /// A Move Option, which is encoded as a vector of at most one element.
+    pub pending_stake: Vec<Balance>,
+    pub pending_withdraw: u64,
+    pub pending_delegation: u64,
+    pub pending_delegation_withdraw: u64,
+    pub delegator_count: u64,
+    pub pending_delegator_count: u64,
+    pub pending_delegator_withdraw_count: u64,
+}
+
+impl Validator {
+    /// A validator of the genesis committee, mirroring the Move Sui::Validator::new.
+    pub fn new(
+        sui_address: SuiAddress,
+        name: AuthorityName,
+        display_name: Vec<u8>,
+        net_address: Vec<u8>,
+        gas_price: u64,
+        stake: u64,
+    ) -> Self {
+        Validator {
+            sui_address,
+            pubkey_bytes: name.as_ref().to_vec(),
+            name: display_name,
+            net_address,
+            gas_price,
+            stake: Balance { value: stake },
+            delegation: 0,
+            pending_stake: vec![],
+            pending_withdraw: 0,
+            pending_delegation: 0,
+            pending_delegation_withdraw: 0,
+            delegator_count: 0,
+            pending_delegator_count: 0,
+            pending_delegator_withdraw_count: 0,
+        }
+    }
+
+    /// The name this validator signs with, as an authority.
+    pub fn authority_name(&self) -> SuiResult<AuthorityName> {
+        let name = PublicKeyBytes::try_from(self.pubkey_bytes.as_slice())?;
+        // Make sure the key is a valid point, which the committee relies on.
+        let _: ed25519_dalek::PublicKey = name.try_into()?;
+        Ok(name)
+    }
+
+    /// The voting power of this validator, in the committee of the current epoch.
+    pub fn voting_power(&self) -> usize {
+        (self.stake.value + self.delegation) as usize
+    }
+}
+
+/// Rust version of the Move Sui::ValidatorSet::ValidatorSet type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    pub validator_stake: u64,
+    pub delegation_stake: u64,
+    pub quorum_stake_threshold: u64,
+    pub active_validators: Vec<Validator>,
+    pub pending_validators: Vec<Validator>,
+    pub pending_removals: Vec<u64>,
+}
+
+impl ValidatorSet {
+    /// The set of the genesis validators, mirroring the Move Sui::ValidatorSet::new.
+    pub fn new(active_validators: Vec<Validator>) -> Self {
+        let validator_stake: u64 = active_validators.iter().map(|v| v.stake.value).sum();
+        let delegation_stake: u64 = active_validators.iter().map(|v| v.delegation).sum();
+        let total_stake = validator_stake + delegation_stake;
+        ValidatorSet {
+            validator_stake,
+            delegation_stake,
+            quorum_stake_threshold: (total_stake + 1) * 2 / 3,
+            active_validators,
+            pending_validators: vec![],
+            pending_removals: vec![],
+        }
+    }
+
+    /// The lowest gas price announced by validators holding a quorum of the stake, mirroring
+    /// the Move Sui::ValidatorSet::derive_reference_gas_price.
+    pub fn derive_reference_gas_price(&self) -> u64 {
+        let mut reference_gas_price = 0;
+        for validator in &self.active_validators {
+            let gas_price = validator.gas_price;
+            if reference_gas_price == 0 || gas_price < reference_gas_price {
+                let stake: u64 = self
+                    .active_validators
+                    .iter()
+                    .filter(|v| v.gas_price <= gas_price)
+                    .map(|v| v.stake.value + v.delegation)
+                    .sum();
+                if stake >= self.quorum_stake_threshold {
+                    reference_gas_price = gas_price;
+                }
+            }
+        }
+        reference_gas_price
+    }
+}
+
+/// Rust version of the Move Sui::GasCharges::Report type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GasChargeReport {
+    pub reporter: SuiAddress,
+    pub stake: u64,
+    pub storage_charge: u64,
+    pub computation_charge: u64,
+    pub storage_rebate: u64,
+}
+
+/// Rust version of the Move Sui::GasCharges::GasChargeReports type
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GasChargeReports {
+    pub reports: Vec<GasChargeReport>,
+}
+
+impl GasChargeReports {
+    /// The sum of the voting power of the validators that reported their fees.
+    pub fn reported_stake(&self) -> u64 {
+        self.reports.iter().map(|report| report.stake).sum()
+    }
+
+    pub fn has_reported(&self, reporter: SuiAddress) -> bool {
+        self.reports
+            .iter()
+            .any(|report| report.reporter == reporter)
+    }
+}
+
+/// Rust version of the Move Sui::SuiSystem::SuiSystemState type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuiSystemState {
+    pub id: VersionedID,
+    pub epoch: EpochId,
+    pub validators: ValidatorSet,
+    pub treasury_cap: TreasuryCap,
+    pub storage_fund: Balance,
+    pub parameters: SystemParameters,
+    pub delegation_reward: Balance,
+    pub reference_gas_price: u64,
+    pub gas_charge_reports: GasChargeReports,
+    pub gas_pool: Balance,
+}
+
+impl SuiSystemState {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: SUI_SYSTEM_MODULE_NAME.to_owned(),
+            name: SUI_SYSTEM_STATE_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    /// The system state created at genesis, with the given validators as the committee of
+    /// the first epoch. All the SUI in existence is minted by the treasury cap, so its
+    /// supply covers the storage fund, the stake of the validators, and the `coin_supply`
+    /// of the coins created at genesis.
+    pub fn genesis(
+        validators: Vec<Validator>,
+        treasury_cap_id: ObjectID,
+        coin_supply: u64,
+    ) -> Self {
+        let validators = ValidatorSet::new(validators);
+        let reference_gas_price = validators.derive_reference_gas_price();
+        SuiSystemState {
+            id: VersionedID::new(SUI_SYSTEM_STATE_OBJECT_ID, OBJECT_START_VERSION),
+            epoch: 0,
+            treasury_cap: TreasuryCap {
+                id: VersionedID::new(treasury_cap_id, OBJECT_START_VERSION),
+                total_supply: INIT_STORAGE_FUND + validators.validator_stake + coin_supply,
+            },
+            validators,
+            storage_fund: Balance {
+                value: INIT_STORAGE_FUND,
+            },
+            parameters: SystemParameters {
+                min_validator_stake: INIT_MIN_VALIDATOR_STAKE,
+                max_validator_stake: INIT_MAX_VALIDATOR_STAKE,
+                max_validator_candidate_count: INIT_MAX_VALIDATOR_COUNT,
+            },
+            delegation_reward: Balance { value: 0 },
+            reference_gas_price,
+            gas_charge_reports: GasChargeReports::default(),
+            gas_pool: Balance { value: 0 },
+        }
+    }
+
+    /// Read the system state held by `object`.
+    pub fn from_object(object: &Object) -> SuiResult<Self> {
+        let move_object = object
+            .data
+            .try_as_move()
+            .filter(|o| o.type_ == Self::type_())
+            .ok_or_else(|| SuiError::InvalidCommittee {
+                error: format!("{:?} is not the Sui system state", object.id()),
+            })?;
+        bcs::from_bytes(move_object.contents()).map_err(|e| SuiError::InvalidCommittee {
+            error: e.to_string(),
+        })
+    }
+
+    /// The shared object holding this system state.
+    pub fn to_object(&self) -> Object {
+        let contents = bcs::to_bytes(self).expect("Serializing a Move object cannot fail");
+        Object::new_move(
+            MoveObject::new(Self::type_(), contents),
+            Owner::Shared,
+            TransactionDigest::genesis(),
+        )
+    }
+
+    /// Whether the validators holding a quorum of the stake reported the gas fees they
+    /// collected, so that the epoch can advance.
+    pub fn gas_charges_reported(&self) -> bool {
+        self.gas_charge_reports.reported_stake() >= self.validators.quorum_stake_threshold
+    }
+
+    /// The committee of the current epoch, made of the active validators weighted by
+    /// their stake and the stake delegated to them.
+    pub fn get_committee(&self) -> SuiResult<Committee> {
+        let mut voting_rights = BTreeMap::new();
+        for validator in &self.validators.active_validators {
+            let name = validator.authority_name()?;
+            fp_ensure!(
+                voting_rights
+                    .insert(name, validator.voting_power())
+                    .is_none(),
+                SuiError::InvalidCommittee {
+                    error: format!("Duplicate validator {:?}", name),
+                }
+            );
+        }
+        fp_ensure!(
+            !voting_rights.is_empty(),
+            SuiError::InvalidCommittee {
+                error: "No active validator".to_owned(),
+            }
+        );
+        Ok(Committee::new(self.epoch, voting_rights))
+    }
+}
+
+/// Rust version of the Move Sui::GasSchedule::Proposal type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GasScheduleProposal {
+    pub schedule: Vec<u8>,
+    pub voters: Vec<SuiAddress>,
+    pub stake: u64,
+}
+
+/// Rust version of the Move Sui::GasSchedule::GasSchedule type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GasSchedule {
+    pub id: VersionedID,
+    pub version: u64,
+    pub schedule: Vec<u8>,
+    /// A Move Option, which is encoded as a vector of at most one element.
+    pub pending_schedule: Vec<Vec<u8>>,
+    pub pending_epoch: EpochId,
+    pub proposals: Vec<GasScheduleProposal>,
+    pub proposal_epoch: EpochId,
+}
+
+impl GasSchedule {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: GAS_SCHEDULE_MODULE_NAME.to_owned(),
+            name: GAS_SCHEDULE_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    /// The gas schedule created at genesis, holding the initial cost schedule.
+    pub fn genesis() -> Self {
+        GasSchedule {
+            id: VersionedID::new(SUI_GAS_SCHEDULE_OBJECT_ID, OBJECT_START_VERSION),
+            version: 0,
+            schedule: INITIAL_SUI_COST_SCHEDULE.to_bytes(),
+            pending_schedule: vec![],
+            pending_epoch: 0,
+            proposals: vec![],
+            proposal_epoch: 0,
+        }
+    }
+
+    /// The shared object holding this gas schedule.
+    pub fn to_object(&self) -> Object {
+        let contents = bcs::to_bytes(self).expect("Serializing a Move object cannot fail");
+        Object::new_move(
+            MoveObject::new(Self::type_(), contents),
+            Owner::Shared,
+            TransactionDigest::genesis(),
+        )
+    }
+
+    /// The version and the BCS-encoded cost schedule in effect at `epoch`. The schedule
+    /// voted for by the validators is only applied to the Move object the next time it is
+    /// voted on, so it may already be in effect.
+    pub fn schedule_at(&self, epoch: EpochId) -> (u64, &[u8]) {
+        match self.pending_schedule.first() {
+            Some(pending_schedule) if epoch >= self.pending_epoch => {
+                (self.version + 1, pending_schedule)
+            }
+            _ => (self.version, &self.schedule),
+        }
+    }
+
+    /// The version and the cost schedule in effect at `epoch`.
+    pub fn cost_schedule_at(&self, epoch: EpochId) -> SuiResult<(u64, SuiCostSchedule)> {
+        let (version, schedule) = self.schedule_at(epoch);
+        Ok((version, SuiCostSchedule::from_bytes(schedule)?))
+    }
+
+    /// Read the gas schedule held by `object`.
+    pub fn from_object(object: &Object) -> SuiResult<Self> {
+        let move_object = object
+            .data
+            .try_as_move()
+            .filter(|o| o.type_ == Self::type_())
+            .ok_or_else(|| SuiError::InvalidCostSchedule {
+                error: format!("{:?} is not the gas schedule", object.id()),
+            })?;
+        bcs::from_bytes(move_object.contents()).map_err(|e| SuiError::InvalidCostSchedule {
+            error: e.to_string(),
+        })
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::{ObjectDigest, SequenceNumber};
+use std::cell::RefCell;
+
+/// An in-memory store of the nodes of a tree.
+#[derive(Default)]
+struct MemoryTree(RefCell<BTreeMap<StateNodeKey, StateNode>>);
+
+impl MemoryTree {
+    fn get(&self, key: &StateNodeKey) -> SuiResult<Option<StateNode>> {
+        Ok(self.0.borrow().get(key).cloned())
+    }
+
+    fn update(&self, updates: Vec<(ObjectID, Option<ObjectRef>)>) -> StateDigest {
+        let (root, changes) = update_state_tree(|key| self.get(key), updates).unwrap();
+        let mut nodes = self.0.borrow_mut();
+        for (key, node) in changes {
+            match node {
+                Some(node) => nodes.insert(key, node),
+                None => nodes.remove(&key),
+            };
+        }
+        root
+    }
+
+    fn prove(&self, object_id: ObjectID) -> ObjectProof {
+        ObjectProof::new(|key| self.get(key), object_id).unwrap()
+    }
+}
+
+fn object_ref(object_id: ObjectID, version: u64) -> ObjectRef {
+    (
+        object_id,
+        SequenceNumber::from(version),
+        ObjectDigest::new([version as u8; 32]),
+    )
+}
+
+#[test]
+fn test_state_root_does_not_depend_on_update_order() {
+    let refs: Vec<_> = (0..10)
+        .map(|version| object_ref(ObjectID::random(), version))
+        .collect();
+
+    let tree = MemoryTree::default();
+    let mut root = EMPTY_STATE_DIGEST;
+    for object_ref in &refs {
+        root = tree.update(vec![(object_ref.0, Some(*object_ref))]);
+    }
+
+    let reversed_tree = MemoryTree::default();
+    let reversed_root = reversed_tree.update(
+        refs.iter()
+            .rev()
+            .map(|object_ref| (object_ref.0, Some(*object_ref)))
+            .collect(),
+    );
+    assert_eq!(root, reversed_root);
+    assert_eq!(*tree.0.borrow(), *reversed_tree.0.borrow());
+    assert_ne!(root, EMPTY_STATE_DIGEST);
+
+    // Removing objects one by one gives the same tree as never inserting them.
+    let (removed, kept) = refs.split_at(6);
+    for object_ref in removed {
+        root = tree.update(vec![(object_ref.0, None)]);
+    }
+    let kept_tree = MemoryTree::default();
+    let kept_root = kept_tree.update(
+        kept.iter()
+            .map(|object_ref| (object_ref.0, Some(*object_ref)))
+            .collect(),
+    );
+    assert_eq!(root, kept_root);
+    assert_eq!(*tree.0.borrow(), *kept_tree.0.borrow());
+
+    // Removing all objects gives back the empty tree.
+    let root = tree.update(refs.iter().map(|object_ref| (object_ref.0, None)).collect());
+    assert_eq!(root, EMPTY_STATE_DIGEST);
+    assert!(tree.0.borrow().is_empty());
+}
+
+#[test]
+fn test_object_proofs() {
+    let tree = MemoryTree::default();
+    let first = object_ref(ObjectID::random(), 1);
+    let second = object_ref(ObjectID::random(), 1);
+    let root = tree.update(vec![(first.0, Some(first)), (second.0, Some(second))]);
+
+    // Inclusion.
+    let proof = tree.prove(first.0);
+    assert_eq!(proof.object_ref, Some(first));
+    proof.verify(&root).unwrap();
+
+    // Non-inclusion.
+    let missing = ObjectID::random();
+    let proof = tree.prove(missing);
+    assert_eq!(proof.object_ref, None);
+    proof.verify(&root).unwrap();
+
+    // A proof for another version of the object, or claiming a missing object
+    // exists, does not verify.
+    let mut proof = tree.prove(first.0);
+    proof.object_ref = Some(object_ref(first.0, 2));
+    assert!(proof.verify(&root).is_err());
+    let mut proof = tree.prove(missing);
+    proof.object_ref = Some(object_ref(missing, 1));
+    proof.other_object = None;
+    assert!(proof.verify(&root).is_err());
+
+    // Proofs are against the latest root.
+    let updated = object_ref(first.0, 2);
+    let old_proof = tree.prove(first.0);
+    let new_root = tree.update(vec![(first.0, Some(updated))]);
+    assert!(old_proof.verify(&new_root).is_err());
+    tree.prove(first.0).verify(&new_root).unwrap();
+    tree.prove(second.0).verify(&new_root).unwrap();
+}
+
+#[test]
+fn test_proofs_are_logarithmic() {
+    let refs: Vec<_> = (0..256)
+        .map(|_| object_ref(ObjectID::random(), 1))
+        .collect();
+    let tree = MemoryTree::default();
+    let root = tree.update(
+        refs.iter()
+            .map(|object_ref| (object_ref.0, Some(*object_ref)))
+            .collect(),
+    );
+
+    // Random IDs rarely share more than a few bits beyond log2(256) = 8.
+    for object_ref in &refs {
+        let proof = tree.prove(object_ref.0);
+        assert!(proof.siblings.len() < 32);
+        proof.verify(&root).unwrap();
+    }
+    // A single object is the root of the tree.
+    let single = MemoryTree::default();
+    let root = single.update(vec![(refs[0].0, Some(refs[0]))]);
+    let proof = single.prove(refs[0].0);
+    assert!(proof.siblings.is_empty());
+    proof.verify(&root).unwrap();
+}
+
+#[test]
+fn test_neighbouring_objects() {
+    // Two IDs that only differ in the last bit share all their ancestors.
+    let mut bytes = [0xabu8; ObjectID::LENGTH];
+    let left = ObjectID::new(bytes);
+    bytes[ObjectID::LENGTH - 1] ^= 1;
+    let right = ObjectID::new(bytes);
+
+    let tree = MemoryTree::default();
+    let left_ref = object_ref(left, 1);
+    let right_ref = object_ref(right, 1);
+    let root = tree.update(vec![(left, Some(left_ref)), (right, Some(right_ref))]);
+
+    let proof = tree.prove(left);
+    assert_eq!(proof.siblings.len(), STATE_TREE_DEPTH);
+    assert_eq!(
+        proof
+            .siblings
+            .iter()
+            .filter(|sibling| **sibling != EMPTY_STATE_DIGEST)
+            .count(),
+        1
+    );
+    proof.verify(&root).unwrap();
+    tree.prove(right).verify(&root).unwrap();
+
+    // A missing object sharing a prefix with them is proven absent by the empty sibling of
+    // their common ancestor.
+    bytes[0] ^= 0x01;
+    let missing = ObjectID::new(bytes);
+    tree.prove(missing).verify(&root).unwrap();
+
+    // A truncated proof is rejected.
+    let mut proof = tree.prove(right);
+    proof.siblings.pop();
+    assert!(proof.verify(&root).is_err());
+
+    // Once one of them is removed, the other moves up to the root.
+    let root = tree.update(vec![(right, None)]);
+    assert_eq!(tree.0.borrow().len(), 1);
+    let proof = tree.prove(left);
+    assert!(proof.siblings.is_empty());
+    proof.verify(&root).unwrap();
+
+    // An absent object is proven by the leaf found in its place.
+    let proof = tree.prove(right);
+    assert_eq!(proof.other_object, Some(left_ref));
+    proof.verify(&root).unwrap();
+}